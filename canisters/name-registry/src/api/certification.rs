@@ -0,0 +1,10 @@
+use candid::{CandidType, Deserialize};
+
+/// Certification information to validate a query.
+#[derive(CandidType, Deserialize, Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Certification {
+    /// The ic certificate that contains the certified variable with the root hash of the canister state tree.
+    pub ic_certificate: Vec<u8>,
+    /// The CBOR encoded state tree of the canister, pruned to the data of the query.
+    pub state_tree: Vec<u8>,
+}
@@ -0,0 +1,14 @@
+use crate::{api::UpdateOperationResult, types::RegistrationController};
+use candid::{CandidType, Deserialize};
+
+/// Input parameters for the `create_zone` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct CreateZoneInput {
+    /// The apex domain of the zone to create, must end with a dot (.).
+    pub domain: String,
+    /// The principals that control the zone and their roles.
+    pub controllers: Vec<RegistrationController>,
+}
+
+/// Result of the `create_zone` operation.
+pub type CreateZoneResult = UpdateOperationResult;
@@ -0,0 +1,25 @@
+use crate::api::{PaginationInfo, PaginationOptions};
+use candid::{CandidType, Deserialize};
+
+/// Input parameters for the `get_domains` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct GetDomainsInput {
+    /// Pagination options for the result set, if not specified the default values will be used.
+    pub options: Option<PaginationOptions>,
+}
+
+/// Item of the `get_domains` result set.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetDomainsItem {
+    /// The domain name.
+    pub domain: String,
+}
+
+/// Result of the `get_domains` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetDomainsResult {
+    /// Pagination information about the result set.
+    pub info: PaginationInfo,
+    /// The list of domains registered that the caller of the operation has access to.
+    pub items: Vec<GetDomainsItem>,
+}
@@ -0,0 +1,20 @@
+use crate::api::{DomainRecord, PaginationInfo, PaginationOptions};
+use candid::{CandidType, Deserialize};
+
+/// Input parameters for the `get_records` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetRecordsInput {
+    /// The domain name to get records for, must end with a dot (.).
+    pub domain: String,
+    /// Pagination options for the result set, if not specified the default values will be used.
+    pub options: Option<PaginationOptions>,
+}
+
+/// Result of the `get_records` operation, contains the list of records and pagination information.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetRecordsResult {
+    /// The list of records for the specified domain.
+    pub records: Vec<DomainRecord>,
+    /// Pagination information about the result set.
+    pub info: PaginationInfo,
+}
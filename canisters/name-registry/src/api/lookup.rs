@@ -0,0 +1,22 @@
+use crate::api::{Certification, DomainRecord};
+use candid::{CandidType, Deserialize};
+
+/// DomainLookup is the result of a lookup operation, this structure is standard to enable clients to perform a
+/// name lookup and handle the result in a generic way.
+#[derive(CandidType, Deserialize, Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct DomainLookup {
+    // The list of answers that match the lookup, the answers section is the most important part of
+    // the lookup result as it contains the actual data that the client is looking for.
+    pub answers: Vec<DomainRecord>,
+    // Additionals are records that are not a direct match with the lookuped up record type but facilitate the process,
+    // e.g. returning the CID records from a NC lookup to prevent the client from having to perform another lookup.
+    pub additionals: Vec<DomainRecord>,
+    // Authorities contains records that point toward the authoritative naming canister/server for the domain.
+    pub authorities: Vec<DomainRecord>,
+    // The certification of the answers, only available in non-replicated query calls.
+    //
+    // The state tree contains the witness of the looked up RRset in the certified records tree.
+    pub certification: Option<Certification>,
+    // Set by the TLD operators when the registration of the looked up domain expired, its answers are then empty.
+    pub expired: Option<bool>,
+}
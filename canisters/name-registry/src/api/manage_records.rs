@@ -0,0 +1,75 @@
+use crate::types::OperationResult;
+use candid::{CandidType, Deserialize, Nat};
+
+/// Input parameters for a domain record operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct DomainRecordInput {
+    /// The domain name, e.g. "mydomain.tld.", the name is required for all operations and must end with a dot (.).
+    pub name: String,
+    /// The record type refers to the classification or category of a specific record within the system.
+    pub record_type: String,
+    /// The Time to Live (TTL) refers to the amount of time for which the record should be cached.
+    pub ttl: Nat,
+    /// The record data in a domain record refers to the specific information associated with that record type.
+    pub data: String,
+}
+
+/// Specifies the type of operation to perform on a record.
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ManageRecordOperationType {
+    /// If a record with the same name and type already exists, the operation will append to the list.
+    #[serde(rename = "append")]
+    Append,
+    /// This operation will override the existing records using the name and type as the key.
+    #[serde(rename = "override")]
+    Override,
+    /// This operation will remove the existing records using the name and type as the key.
+    #[serde(rename = "remove")]
+    Remove,
+}
+
+/// Input parameters for the `append` operation.
+pub type AppendRecordOperationInput = DomainRecordInput;
+
+/// Input parameters for the `override` operation.
+pub type OverrideRecordOperationInput = DomainRecordInput;
+
+/// Input parameters for the `remove` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct RemoveRecordOperationInput {
+    /// The name of the record to remove, same restrictions as the name of a DomainRecord apply.
+    pub name: String,
+    /// The type of the record to remove, if no type is specified all records with the name will be removed.
+    pub record_type: Option<String>,
+}
+
+/// The operation to execute on the records, the operation type specifies how the operation will be performed.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum ManageRecordsOperation {
+    /// If a record with the same name and type already exists, the operation will append to the list.
+    #[serde(rename = "append")]
+    Append(Vec<AppendRecordOperationInput>),
+    /// This operation will override the existing records using the name and type as the key.
+    #[serde(rename = "override")]
+    Override(Vec<OverrideRecordOperationInput>),
+    /// This operation will remove the existing records using the name and type as the key.
+    #[serde(rename = "remove")]
+    Remove(Vec<RemoveRecordOperationInput>),
+}
+
+/// Input parameters for the `manage_records` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ManageRecordsInput {
+    /// The domain name to manage records for, must end with a dot (.).
+    pub domain: String,
+    /// The list of operations to perform on the domain records.
+    pub operations: Vec<ManageRecordsOperation>,
+    /// Wether the changed records should be signed with the tECDSA api.
+    pub sign_with_tecdsa: Option<bool>,
+}
+
+/// A default type providing structure for update operations.
+pub type UpdateOperationResult = OperationResult;
+
+/// Result of the `manage_records` operation.
+pub type ManageRecordsResult = UpdateOperationResult;
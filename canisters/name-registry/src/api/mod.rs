@@ -0,0 +1,45 @@
+//! Candid types of the public canister interface, these mirror the definitions of `spec.did`.
+
+/// Types used to certify query responses.
+mod certification;
+pub use certification::*;
+
+/// Types used by the create zone operation.
+mod create_zone;
+pub use create_zone::*;
+
+/// Types used by the get domains operation.
+mod get_domains;
+pub use get_domains::*;
+
+/// Types used by the get records operation.
+mod get_records;
+pub use get_records::*;
+
+/// Types used by the lookup operation.
+mod lookup;
+pub use lookup::*;
+
+/// Types used by the manage records operation.
+mod manage_records;
+pub use manage_records::*;
+
+/// Types used to paginate result sets.
+mod pagination;
+pub use pagination::*;
+
+/// Types used by the register operation of the root and TLD operator canisters.
+mod register;
+pub use register::*;
+
+/// Types to represent a domain record in the canister interface.
+mod record;
+pub use record::*;
+
+/// Types used by the zone history operations.
+mod zone_history;
+pub use zone_history::*;
+
+/// Types used by the zone file operations.
+mod zone_file;
+pub use zone_file::*;
@@ -0,0 +1,65 @@
+use crate::common::{DEFAULT_PAGINATION_LIMIT, MAX_PAGINATION_LIMIT};
+use candid::{CandidType, Deserialize};
+
+/// Contains information about the pagination of a result set.
+#[derive(CandidType, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PaginationInfo {
+    /// The total number of items of a result set.
+    ///
+    /// Counting the items can require a scan of the whole result set, hence listings may only count them for the
+    /// first page and leave it empty for the pages requested with a cursor.
+    pub total: Option<u64>,
+    /// The number of items that were returned in the result set.
+    pub limit: u64,
+    /// The offset of the first record in the result set.
+    pub start: u64,
+    /// An opaque cursor pointing to the last item of the result set, only set if there are more items.
+    pub next_cursor: Option<String>,
+}
+
+/// Specify the pagination options for a result set.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct PaginationOptions {
+    /// The offset of the first record in the result set, allowing the client to skip records.
+    pub start: u64,
+    /// The maximum number of records to return in the result set.
+    pub limit: u64,
+    /// An opaque cursor returned by a previous page, the result set continues right after it.
+    pub cursor: Option<String>,
+}
+
+impl PaginationOptions {
+    /// Returns the number of items to return, capped at the maximum page size.
+    pub fn effective_limit(&self) -> u64 {
+        self.limit.min(MAX_PAGINATION_LIMIT)
+    }
+}
+
+impl Default for PaginationOptions {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            limit: DEFAULT_PAGINATION_LIMIT,
+            cursor: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pagination_limit_is_capped() {
+        let options = PaginationOptions {
+            limit: MAX_PAGINATION_LIMIT + 1,
+            ..Default::default()
+        };
+
+        assert_eq!(options.effective_limit(), MAX_PAGINATION_LIMIT);
+        assert_eq!(
+            PaginationOptions::default().effective_limit(),
+            DEFAULT_PAGINATION_LIMIT
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use crate::types::DomainZoneEntry;
+use candid::{CandidType, Deserialize, Nat};
+
+/// DomainRecord represents a Chain Name System (CNS) record item as exposed by the canister interface.
+///
+/// Contrary to the stored record, the name is always the fully qualified domain name.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct DomainRecord {
+    /// The domain name, e.g. "mydomain.tld.", always ending with a dot (.).
+    pub name: String,
+    /// The record type, e.g. "CID", "A", "CNAME", "TXT", "MX", "AAAA", "NC", "NS".
+    pub record_type: String,
+    /// The Time to Live (TTL) of the record in seconds.
+    pub ttl: Nat,
+    /// The record data, its format depends on the record type.
+    pub data: String,
+}
+
+impl From<&DomainZoneEntry> for DomainRecord {
+    fn from(entry: &DomainZoneEntry) -> Self {
+        let record = entry.record();
+
+        Self {
+            name: record.name.to_fully_qualified_name(&entry.zone().name),
+            record_type: record.record_type.clone(),
+            ttl: Nat::from(record.ttl),
+            data: record.data.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DomainRecord as ZoneRecord, DomainZone, RecordName, ZoneApexDomain};
+
+    #[test]
+    fn domain_record_uses_fully_qualified_name() {
+        let apex_domain = ZoneApexDomain::new(String::from("internetcomputer.tld.")).unwrap();
+        let entry = DomainZoneEntry::new(
+            DomainZone::new(apex_domain.clone()),
+            ZoneRecord::new(
+                RecordName::new(String::from("wiki"), &apex_domain).unwrap(),
+                "CID".to_string(),
+                3600,
+                "qoctq-giaaa-aaaaa-aaaea-cai".to_string(),
+            ),
+        );
+
+        let record = DomainRecord::from(&entry);
+
+        assert_eq!(record.name, "wiki.internetcomputer.tld.");
+        assert_eq!(record.record_type, "CID");
+        assert_eq!(record.ttl, Nat::from(3600u32));
+        assert_eq!(record.data, "qoctq-giaaa-aaaaa-aaaea-cai");
+    }
+}
@@ -0,0 +1,12 @@
+use crate::{api::DomainRecord, types::RegistrationController};
+use candid::{CandidType, Deserialize};
+
+/// Input parameters for the `register` operation of the root and TLD operator canisters, which register a domain
+/// together with its records.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct RegistrationRecords {
+    /// The controllers of the registered domain.
+    pub controllers: Vec<RegistrationController>,
+    /// The records of the registered domain.
+    pub records: Option<Vec<DomainRecord>>,
+}
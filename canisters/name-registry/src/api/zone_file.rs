@@ -0,0 +1,28 @@
+use crate::api::UpdateOperationResult;
+use candid::{CandidType, Deserialize};
+
+/// Input parameters for the `export_zone` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ExportZoneInput {
+    /// The apex domain of the zone to export, must end with a dot (.).
+    pub domain: String,
+}
+
+/// Result of the `export_zone` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ExportZoneResult {
+    /// The records of the zone in the RFC 1035 master file format.
+    pub zone_file: String,
+}
+
+/// Input parameters for the `import_zone` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ImportZoneInput {
+    /// The apex domain of the zone to import the records into, must end with a dot (.).
+    pub domain: String,
+    /// The records of the zone in the RFC 1035 master file format, the apex domain is its initial origin.
+    pub zone_file: String,
+}
+
+/// Result of the `import_zone` operation.
+pub type ImportZoneResult = UpdateOperationResult;
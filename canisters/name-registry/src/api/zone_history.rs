@@ -0,0 +1,84 @@
+use crate::{
+    api::{DomainRecord, ManageRecordOperationType, PaginationInfo, PaginationOptions},
+    types::{
+        DomainRecord as ZoneRecord, DomainZone, DomainZoneEntry, ZoneHistoryEntry,
+        ZoneHistoryOperation,
+    },
+};
+use candid::{CandidType, Deserialize, Principal};
+
+/// Input parameters for the `get_zone_history` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetZoneHistoryInput {
+    /// The apex domain of the zone to get the history for, must end with a dot (.).
+    pub domain: String,
+    /// Pagination options for the result set, if not specified the default values will be used.
+    pub options: Option<PaginationOptions>,
+}
+
+/// A change made to an RRset of a zone by the `manage_records` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ZoneHistoryChange {
+    /// The position of the change in the history of the zone, starting at 0.
+    pub sequence: u64,
+    /// The time of the change in nanoseconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// The principal that made the change.
+    pub caller: Principal,
+    /// The operation that made the change.
+    pub operation: ManageRecordOperationType,
+    /// The fully qualified name of the changed RRset.
+    pub name: String,
+    /// The record type of the changed RRset.
+    pub record_type: String,
+    /// The records of the RRset before the change.
+    pub before: Vec<DomainRecord>,
+    /// The records of the RRset after the change.
+    pub after: Vec<DomainRecord>,
+}
+
+impl From<ZoneHistoryOperation> for ManageRecordOperationType {
+    fn from(operation: ZoneHistoryOperation) -> Self {
+        match operation {
+            ZoneHistoryOperation::Append => Self::Append,
+            ZoneHistoryOperation::Override => Self::Override,
+            ZoneHistoryOperation::Remove => Self::Remove,
+        }
+    }
+}
+
+impl From<&ZoneHistoryEntry> for ZoneHistoryChange {
+    fn from(entry: &ZoneHistoryEntry) -> Self {
+        let records = |records: &[ZoneRecord]| {
+            records
+                .iter()
+                .map(|record| {
+                    DomainRecord::from(&DomainZoneEntry::new(
+                        DomainZone::new(entry.zone.clone()),
+                        record.clone(),
+                    ))
+                })
+                .collect()
+        };
+
+        Self {
+            sequence: entry.sequence,
+            timestamp: entry.timestamp,
+            caller: entry.caller,
+            operation: entry.operation.into(),
+            name: entry.name.to_fully_qualified_name(&entry.zone),
+            record_type: entry.record_type.clone(),
+            before: records(&entry.before),
+            after: records(&entry.after),
+        }
+    }
+}
+
+/// Result of the `get_zone_history` operation.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetZoneHistoryResult {
+    /// The changes of the zone in the order they were made.
+    pub items: Vec<ZoneHistoryChange>,
+    /// Pagination information about the result set.
+    pub info: PaginationInfo,
+}
@@ -4,6 +4,31 @@ pub const MAX_DOMAIN_NAME_LENGTH: usize = 255;
 /// The maximum length of a label in a domain name.
 pub const MAX_DOMAIN_LABEL_LENGTH: usize = 63;
 
+/// The reserved record type that can only be used in lookups to retrieve all records of a domain.
+pub const ANY_RECORD_TYPE: &str = "ANY";
+
+/// The label that makes a record name a wildcard that matches the names without records of their own.
+pub const WILDCARD_LABEL: &str = "*";
+
+/// The maximum number of CNAME records followed by a lookup, longer chains are answered up to this length.
+pub const MAX_CNAME_CHAIN_LENGTH: usize = 8;
+
+/// The maximum number of changes kept in the history of a zone, the oldest changes are removed first.
+pub const MAX_ZONE_HISTORY_LENGTH: u64 = 1000;
+
+/// The version of the layout of the data kept in stable memory, it has to be increased together with a migration
+/// whenever the layout of the stored data changes.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// The version of the data written before the storage header was introduced.
+pub const LEGACY_SCHEMA_VERSION: u32 = 0;
+
+/// The default number of items returned by a paginated result set.
+pub const DEFAULT_PAGINATION_LIMIT: u64 = 100;
+
+/// The maximum number of items that can be returned by a paginated result set.
+pub const MAX_PAGINATION_LIMIT: u64 = 1000;
+
 /// The maximum value of an ASCII character accepted in a domain name, which is 122.
 pub const MAX_DOMAIN_ASCII_CHAR_VALUE: u8 = b'z';
 
@@ -25,3 +50,24 @@ pub const MAX_DOMAIN_NAME: [u8; MAX_DOMAIN_NAME_LENGTH] = {
 
     max_domain_name
 };
+
+/// The label of the certified records subtree, the certified data of the canister is the hash of this subtree.
+pub const CERTIFIED_RECORDS_LABEL: &[u8] = b"records";
+
+/// The ttl of the SOA record created for new zones.
+pub const DEFAULT_SOA_TTL: u32 = 3600;
+
+/// The seconds after which secondary name servers should refresh a new zone.
+pub const DEFAULT_SOA_REFRESH: u32 = 7200;
+
+/// The seconds after which a failed refresh of a new zone should be retried.
+pub const DEFAULT_SOA_RETRY: u32 = 3600;
+
+/// The seconds after which secondary name servers stop answering for a new zone if it can't be refreshed.
+pub const DEFAULT_SOA_EXPIRE: u32 = 1209600;
+
+/// The ttl of negative responses of a new zone.
+pub const DEFAULT_SOA_MINIMUM: u32 = 3600;
+
+/// The label of the mailbox responsible for a new zone, which is prefixed to the apex domain.
+pub const DEFAULT_SOA_MAILBOX: &str = "hostmaster";
@@ -0,0 +1,30 @@
+use super::update_certified_data;
+use crate::services::{CertificationService, MigrationService};
+use ic_cdk::{init, post_upgrade, pre_upgrade, trap};
+
+/// The stored data of a new canister is written with the current schema version, and the certified data is set
+/// so that lookups can be verified before the first update.
+#[init]
+fn init() {
+    MigrationService::default().save_schema_version();
+    update_certified_data();
+}
+
+/// The data is kept in stable memory, only the schema version it was written with has to be saved for the
+/// next version of the canister.
+#[pre_upgrade]
+fn pre_upgrade() {
+    MigrationService::default().save_schema_version();
+}
+
+/// The stored data is migrated to the current schema version before anything else reads it, and the certified
+/// records tree is rebuilt since it is kept in heap memory, which is cleared on upgrades.
+#[post_upgrade]
+fn post_upgrade() {
+    if let Err(error) = MigrationService::default().migrate() {
+        trap(&error.to_string());
+    }
+
+    CertificationService::default().certify_all();
+    update_certified_data();
+}
@@ -0,0 +1,27 @@
+use crate::{
+    api::{Certification, DomainLookup},
+    services::{CertificationService, LookupService},
+};
+use ic_cdk::{api::data_certificate, query, trap};
+
+/// Lookup a domain name and return the records that match the specified record type.
+///
+/// The answers are certified when the data certificate is available, which is the case for non-replicated queries.
+#[query]
+fn lookup(domain: String, record_type: String) -> DomainLookup {
+    let mut lookup = LookupService::default().lookup(&domain, &record_type);
+    lookup.certification = data_certificate().map(|ic_certificate| Certification {
+        ic_certificate,
+        state_tree: CertificationService::default().witness(&domain, &record_type, &lookup),
+    });
+
+    lookup
+}
+
+/// Lookup the records of a domain name as they were at the given time in nanoseconds since the UNIX epoch.
+#[query]
+fn lookup_at(domain: String, record_type: String, timestamp: u64) -> DomainLookup {
+    LookupService::default()
+        .lookup_at(&domain, &record_type, timestamp)
+        .unwrap_or_else(|error| trap(&error.to_string()))
+}
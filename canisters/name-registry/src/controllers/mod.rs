@@ -0,0 +1,31 @@
+//! Canister endpoints of the name registry, as defined in `spec.did`.
+
+/// Hooks of the canister lifecycle.
+mod lifecycle;
+
+/// Endpoints to resolve domain names.
+mod lookup;
+
+/// Endpoints to manage the records of the zones.
+mod records;
+
+/// Endpoints to provide information about the zones.
+mod zones;
+
+use crate::{services::CertificationService, types::CallContext};
+use ic_cdk::{
+    api::{is_controller, set_certified_data, time},
+    caller,
+};
+
+/// Builds the context of the current call, used by the services to authorize the caller.
+fn call_context() -> CallContext {
+    let caller = caller();
+
+    CallContext::new(caller, is_controller(&caller), time())
+}
+
+/// Sets the certified data of the canister to the root hash of the certified records tree.
+fn update_certified_data() {
+    set_certified_data(&CertificationService::default().certified_data());
+}
@@ -0,0 +1,43 @@
+use super::{call_context, update_certified_data};
+use crate::{
+    api::{
+        GetRecordsInput, GetRecordsResult, GetZoneHistoryInput, GetZoneHistoryResult,
+        ManageRecordsInput, ManageRecordsResult,
+    },
+    services::{RecordsService, ZoneHistoryService},
+};
+use ic_cdk::{query, trap, update};
+
+/// Get records of the specified domain, the result set is paginated.
+#[query]
+fn get_records(input: GetRecordsInput) -> GetRecordsResult {
+    RecordsService::default()
+        .get_records(&input)
+        .unwrap_or_else(|error| trap(&error.to_string()))
+}
+
+/// Get the changes made to the records of the specified zone, the result set is paginated.
+#[query]
+fn get_zone_history(input: GetZoneHistoryInput) -> GetZoneHistoryResult {
+    ZoneHistoryService::default()
+        .get_zone_history(&call_context(), &input)
+        .unwrap_or_else(|error| trap(&error.to_string()))
+}
+
+/// Manage records of the specified domain based on the list of operations.
+#[update]
+fn manage_records(input: ManageRecordsInput) -> ManageRecordsResult {
+    let result = RecordsService::default().manage_records(&call_context(), &input);
+    update_certified_data();
+
+    match result {
+        Ok(()) => ManageRecordsResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => ManageRecordsResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
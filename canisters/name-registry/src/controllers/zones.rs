@@ -0,0 +1,61 @@
+use super::{call_context, update_certified_data};
+use crate::{
+    api::{
+        CreateZoneInput, CreateZoneResult, ExportZoneInput, ExportZoneResult, GetDomainsInput,
+        GetDomainsResult, ImportZoneInput, ImportZoneResult,
+    },
+    services::{ZoneFileService, ZonesService},
+};
+use ic_cdk::{query, trap, update};
+
+/// Get the list of domains registered that the caller of the operation has access to.
+#[query]
+fn get_domains(input: GetDomainsInput) -> GetDomainsResult {
+    ZonesService::default()
+        .get_domains(&call_context(), &input)
+        .unwrap_or_else(|error| trap(&error.to_string()))
+}
+
+/// Create a new zone and assign the controllers that can manage it.
+#[update]
+fn create_zone(input: CreateZoneInput) -> CreateZoneResult {
+    let result = ZonesService::default().create_zone(&call_context(), &input);
+    update_certified_data();
+
+    match result {
+        Ok(()) => CreateZoneResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => CreateZoneResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
+
+/// Export the records of the specified zone as a zone file in the RFC 1035 master file format.
+#[query]
+fn export_zone(input: ExportZoneInput) -> ExportZoneResult {
+    ZoneFileService::default()
+        .export_zone(&input)
+        .unwrap_or_else(|error| trap(&error.to_string()))
+}
+
+/// Replace the records of the specified zone with the records of a zone file in the RFC 1035 master file format.
+#[update]
+fn import_zone(input: ImportZoneInput) -> ImportZoneResult {
+    let result = ZoneFileService::default().import_zone(&call_context(), &input);
+    update_certified_data();
+
+    match result {
+        Ok(()) => ImportZoneResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => ImportZoneResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
@@ -0,0 +1,46 @@
+use crate::errors::ZoneApexDomainError;
+
+/// Container for create zone errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum CreateZoneError {
+    /// The domain of the zone is invalid
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The caller is not allowed to create zones
+    #[error("Currently only a canister controller can create zones, caller: {caller}")]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+    },
+
+    /// A zone with the same apex domain already exists
+    #[error("Zone {domain:?} already exists")]
+    ZoneAlreadyExists {
+        /// The apex domain of the zone
+        domain: String,
+    },
+
+    /// The zone has more controllers than allowed
+    #[error("Too many controllers. Received {controllers:?}, expected smaller or equal to {max_controllers:?}")]
+    TooManyControllers {
+        /// The actual number of controllers
+        controllers: usize,
+        /// The max number of controllers
+        max_controllers: usize,
+    },
+
+    /// The same principal is listed more than once as a controller
+    #[error("Controller {controller_id} is listed more than once")]
+    DuplicateController {
+        /// The principal of the duplicated controller
+        controller_id: String,
+    },
+
+    /// A controller needs at least one role
+    #[error("Controller {controller_id} has no roles")]
+    MissingControllerRoles {
+        /// The principal of the controller without roles
+        controller_id: String,
+    },
+}
@@ -0,0 +1,86 @@
+use crate::errors::{RecordDataError, RecordNameError, RecordTypeError};
+
+/// Container for domain record input errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum DomainRecordInputError {
+    /// The record name can't be used within the zone
+    #[error("Record name {name:?} is invalid: {error}")]
+    InvalidRecordName {
+        /// The invalid record name
+        name: String,
+        /// The reason why the name is invalid
+        error: RecordNameError,
+    },
+
+    /// The record type is empty
+    #[error(r#"Record type is empty"#)]
+    NonEmptyRecordType,
+
+    /// The record type is too long
+    #[error("Record type is too long. Received {record_type_length:?}, expected smaller or equal to {max_record_type_length:?}")]
+    RecordTypeTooLong {
+        /// The actual record type length
+        record_type_length: usize,
+        /// The max record type length
+        max_record_type_length: usize,
+    },
+
+    /// Record types can only contain alphanumeric characters
+    #[error("Record type can only contain alphanumeric characters. Received {record_type:?}")]
+    InvalidRecordType {
+        /// The invalid record type
+        record_type: String,
+    },
+
+    /// The record type is reserved for lookups and can't be stored
+    #[error("Record type {record_type:?} is reserved and can't be used in records")]
+    ReservedRecordType {
+        /// The reserved record type
+        record_type: String,
+    },
+
+    /// The record type is not one of the supported record types
+    #[error("{0}")]
+    UnsupportedRecordType(RecordTypeError),
+
+    /// The record type is required in every zone and can't be removed
+    #[error("Record type {record_type:?} is required in every zone and can't be removed")]
+    RequiredRecordType {
+        /// The required record type
+        record_type: String,
+    },
+
+    /// The SOA record can only be set at the apex of the zone
+    #[error("SOA record can only be set at the zone apex. Received {name:?}")]
+    SoaRecordNotAtApex {
+        /// The record name that is not the zone apex
+        name: String,
+    },
+
+    /// The TTL does not fit the supported range
+    #[error("Record TTL is too large. Received {ttl}, expected smaller or equal to {max_ttl:?}")]
+    TtlTooLarge {
+        /// The actual TTL
+        ttl: String,
+        /// The max TTL
+        max_ttl: u32,
+    },
+
+    /// The record data is too long
+    #[error("Record data is too long. Received {data_length:?}, expected smaller or equal to {max_data_length:?}")]
+    DataTooLong {
+        /// The actual data length
+        data_length: usize,
+        /// The max data length
+        max_data_length: usize,
+    },
+
+    /// The record data does not match the format of the record type
+    #[error("Record data is invalid for record type {record_type:?}: {error}")]
+    InvalidRecordData {
+        /// The record type of the record
+        record_type: String,
+        /// The reason why the data is invalid
+        error: RecordDataError,
+    },
+}
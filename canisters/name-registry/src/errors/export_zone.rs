@@ -0,0 +1,16 @@
+use crate::errors::ZoneApexDomainError;
+
+/// Container for export zone errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum ExportZoneError {
+    /// The domain of the zone is invalid
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The zone does not exist
+    #[error("Zone {domain:?} does not exist")]
+    ZoneNotFound {
+        /// The apex domain of the zone
+        domain: String,
+    },
+}
@@ -0,0 +1,13 @@
+use crate::errors::{PaginationError, ZoneApexDomainError};
+
+/// Container for get records errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum GetRecordsError {
+    /// The domain of the zone is invalid
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The pagination options are invalid
+    #[error("{0}")]
+    InvalidPagination(PaginationError),
+}
@@ -0,0 +1,22 @@
+use crate::errors::{PaginationError, ZoneApexDomainError};
+
+/// Container for get zone history errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum GetZoneHistoryError {
+    /// The domain of the zone is invalid
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The caller is not a controller of the zone
+    #[error("Caller {caller} is not allowed to get the history of {domain:?}")]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+        /// The apex domain of the zone
+        domain: String,
+    },
+
+    /// The pagination options are invalid
+    #[error("{0}")]
+    InvalidPagination(PaginationError),
+}
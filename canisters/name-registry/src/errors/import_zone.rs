@@ -0,0 +1,17 @@
+use crate::errors::{ManageRecordsError, ZoneApexDomainError, ZoneFileError};
+
+/// Container for import zone errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum ImportZoneError {
+    /// The domain of the zone is invalid
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The zone file could not be parsed, no record was changed
+    #[error("Zone file is invalid: {0}")]
+    InvalidZoneFile(ZoneFileError),
+
+    /// The records of the zone file could not be applied to the zone, no record was changed
+    #[error("{0}")]
+    ManageRecords(ManageRecordsError),
+}
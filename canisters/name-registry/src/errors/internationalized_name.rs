@@ -0,0 +1,28 @@
+/// Container for internationalized domain name errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum InternationalizedNameError {
+    /// The label has a code point that is not allowed in domain names by IDNA 2008, e.g. symbols or emojis
+    #[error("Domain name label {label:?} has the disallowed code point {code_point:?} (U+{:04X})", *code_point as u32)]
+    DisallowedCodePoint {
+        /// The invalid domain name label
+        label: String,
+        /// The disallowed code point
+        code_point: char,
+    },
+
+    /// The label mixes right-to-left and left-to-right characters in a way that can be displayed ambiguously,
+    /// as defined by the bidi rule of RFC 5893
+    #[error("Domain name label {label:?} does not satisfy the bidi rule for right-to-left text")]
+    BidiRule {
+        /// The invalid domain name label
+        label: String,
+    },
+
+    /// The label is not a valid internationalized label, e.g. an A-label with invalid punycode or a label
+    /// that starts with a combining mark
+    #[error("Domain name label {label:?} is not a valid internationalized label")]
+    InvalidLabel {
+        /// The invalid domain name label
+        label: String,
+    },
+}
@@ -0,0 +1,16 @@
+/// Container for point in time lookup errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum LookupAtError {
+    /// The oldest changes of the zone were removed from its history, which only goes back to the given time
+    #[error(
+        "History of {domain:?} is not available before {oldest_timestamp}, received {timestamp}"
+    )]
+    HistoryUnavailable {
+        /// The apex domain of the zone
+        domain: String,
+        /// The requested time in nanoseconds since the UNIX epoch
+        timestamp: u64,
+        /// The time of the oldest change kept in the history of the zone
+        oldest_timestamp: u64,
+    },
+}
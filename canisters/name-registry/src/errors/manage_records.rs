@@ -0,0 +1,38 @@
+use crate::errors::{DomainRecordInputError, ZoneApexDomainError};
+
+/// Container for manage records errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum ManageRecordsError {
+    /// The domain of the zone that is being managed is invalid
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The caller is not a controller of the zone with a role that allows changing records
+    #[error("Caller {caller} is not allowed to manage the records of {domain:?}")]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+        /// The apex domain of the zone
+        domain: String,
+    },
+
+    /// Signing records with the tECDSA api is not available yet
+    #[error(r#"Signing records with tECDSA is not supported"#)]
+    SigningNotSupported,
+
+    /// One of the operations has invalid records, no operation was applied
+    #[error("Operation at index {index:?} failed: {error}")]
+    InvalidOperation {
+        /// The index of the failed operation
+        index: usize,
+        /// The reason why the operation failed
+        error: DomainRecordInputError,
+    },
+
+    /// A name would have a CNAME record together with other records, no operation was applied
+    #[error("Name {name:?} can't have a CNAME record together with other records")]
+    CnameConflict {
+        /// The fully qualified name with the conflicting records
+        name: String,
+    },
+}
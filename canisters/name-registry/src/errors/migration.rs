@@ -0,0 +1,12 @@
+/// Container for stable memory migration errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum MigrationError {
+    /// The stored data was written by a newer version of the canister, downgrades are not supported
+    #[error("Stored schema version {schema_version} is newer than the supported version {supported_schema_version}")]
+    UnsupportedSchemaVersion {
+        /// The version of the stored data
+        schema_version: u32,
+        /// The latest version known by the canister
+        supported_schema_version: u32,
+    },
+}
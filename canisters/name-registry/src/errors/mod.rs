@@ -1,9 +1,65 @@
 //! Various error types for failure scenarios
 
+/// Error types for the create zone operation.
+mod create_zone;
+pub use create_zone::*;
+
+/// Error types for the domain record input.
+mod domain_record_input;
+pub use domain_record_input::*;
+
+/// Error types for the export zone operation.
+mod export_zone;
+pub use export_zone::*;
+
+/// Error types for the get records operation.
+mod get_records;
+pub use get_records::*;
+
+/// Error types for the get zone history operation.
+mod get_zone_history;
+pub use get_zone_history::*;
+
+/// Error types for the import zone operation.
+mod import_zone;
+pub use import_zone::*;
+
+/// Error types for internationalized domain names.
+mod internationalized_name;
+pub use internationalized_name::*;
+
+/// Error types for the point in time lookup operation.
+mod lookup_at;
+pub use lookup_at::*;
+
+/// Error types for the manage records operation.
+mod manage_records;
+pub use manage_records::*;
+
+/// Error types for the stable memory migrations.
+mod migration;
+pub use migration::*;
+
+/// Error types for paginated result sets.
+mod pagination;
+pub use pagination::*;
+
+/// Error types for the record data.
+mod record_data;
+pub use record_data::*;
+
 /// Error types for the record name.
 mod record_name;
 pub use record_name::*;
 
+/// Error types for the record type.
+mod record_type;
+pub use record_type::*;
+
 /// Error types for the zone apex domain.
 mod zone_apex_domain;
 pub use zone_apex_domain::*;
+
+/// Error types for zone files.
+mod zone_file;
+pub use zone_file::*;
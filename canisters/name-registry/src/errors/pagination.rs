@@ -0,0 +1,10 @@
+/// Container for pagination errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum PaginationError {
+    /// The cursor was not issued by a previous page of the same result set
+    #[error("Pagination cursor is invalid. Received {cursor:?}")]
+    InvalidCursor {
+        /// The invalid cursor
+        cursor: String,
+    },
+}
@@ -0,0 +1,97 @@
+use crate::errors::ZoneApexDomainError;
+
+/// Container for record data errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum RecordDataError {
+    /// The data of an A record must be an IPv4 address
+    #[error("Record data is not a valid IPv4 address. Received {data:?}")]
+    InvalidIpv4Address {
+        /// The invalid record data
+        data: String,
+    },
+
+    /// The data of an AAAA record must be an IPv6 address
+    #[error("Record data is not a valid IPv6 address. Received {data:?}")]
+    InvalidIpv6Address {
+        /// The invalid record data
+        data: String,
+    },
+
+    /// The data of the record must be a principal in its textual representation
+    #[error("Record data is not a valid principal. Received {data:?}")]
+    InvalidPrincipal {
+        /// The invalid record data
+        data: String,
+    },
+
+    /// The data of the record must be a fully qualified domain name
+    #[error("Record data is not a valid domain name {data:?}: {error}")]
+    InvalidDomainName {
+        /// The invalid record data
+        data: String,
+        /// The reason why the domain name is invalid
+        error: ZoneApexDomainError,
+    },
+
+    /// The data of an MX record must be a preference followed by the mail exchange domain name
+    #[error("Record data must be a preference followed by a domain name, e.g. \"10 mail.mydomain.tld.\". Received {data:?}")]
+    InvalidMailExchange {
+        /// The invalid record data
+        data: String,
+    },
+
+    /// The data of an SRV record must be a priority, weight and port followed by the target domain name
+    #[error("Record data must be a priority, weight and port followed by a domain name, e.g. \"10 5 443 service.mydomain.tld.\". Received {data:?}")]
+    InvalidServiceLocation {
+        /// The invalid record data
+        data: String,
+    },
+
+    /// The data of a CAA record must be flags and a tag followed by a value
+    #[error("Record data must be flags and a tag followed by a value, e.g. \"0 issue ca.example\". Received {data:?}")]
+    InvalidCertificationAuthorityAuthorization {
+        /// The invalid record data
+        data: String,
+    },
+
+    /// The tag of a CAA record can only contain alphanumeric characters
+    #[error("Record tag can only contain alphanumeric characters. Received {tag:?}")]
+    InvalidTag {
+        /// The invalid tag
+        tag: String,
+    },
+
+    /// The data of an SOA record must be the primary name server, the responsible mailbox and the zone timers
+    #[error("Record data must be two domain names followed by the serial, refresh, retry, expire and minimum values, e.g. \"ns1.mydomain.tld. hostmaster.mydomain.tld. 1 7200 3600 1209600 3600\". Received {data:?}")]
+    InvalidStartOfAuthority {
+        /// The invalid record data
+        data: String,
+    },
+
+    /// A numeric field of the record data is not a number in the supported range
+    #[error("Record {field} is not a number between 0 and {max_value:?}. Received {value:?}")]
+    InvalidNumber {
+        /// The name of the numeric field
+        field: String,
+        /// The invalid value
+        value: String,
+        /// The max value of the field
+        max_value: u32,
+    },
+
+    /// The data of a TXT record has a quoted character string that is not terminated
+    #[error("Record data has an unterminated quoted string. Received {data:?}")]
+    UnterminatedString {
+        /// The invalid record data
+        data: String,
+    },
+
+    /// The character strings of a TXT record are limited in length
+    #[error("Record character string is too long. Received {string_length:?}, expected smaller or equal to {max_string_length:?}")]
+    StringTooLong {
+        /// The actual character string length
+        string_length: usize,
+        /// The max character string length
+        max_string_length: usize,
+    },
+}
@@ -1,3 +1,5 @@
+use crate::errors::InternationalizedNameError;
+
 /// Container for record name errors
 #[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
 pub enum RecordNameError {
@@ -39,6 +41,10 @@ pub enum RecordNameError {
     #[error(r#"A domain name can only have underscore (_) in the beginning"#)]
     MisplacedUnderscore,
 
+    /// The wildcard label can only be the leftmost label of the record name
+    #[error(r#"A record name can only have the wildcard label (*) in the beginning"#)]
+    MisplacedWildcard,
+
     /// Name labels can only contain alphanumeric characters and hyphens (-)
     #[error(
         "Name labels can only contain alphanumeric characters and hyphens (-). Received {label:?}"
@@ -47,4 +53,17 @@ pub enum RecordNameError {
         /// The invalid record name label
         label: String,
     },
+
+    /// The record name has an invalid internationalized label
+    #[error("{0}")]
+    InvalidInternationalizedName(InternationalizedNameError),
+
+    /// The fully qualified record name is not part of the zone
+    #[error("Record name {name:?} is not part of the zone {apex_domain:?}")]
+    NotInZone {
+        /// The fully qualified record name
+        name: String,
+        /// The apex domain of the zone
+        apex_domain: String,
+    },
 }
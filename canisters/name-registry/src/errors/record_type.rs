@@ -0,0 +1,10 @@
+/// Container for record type errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum RecordTypeError {
+    /// The record type is not one of the supported record types
+    #[error("Record type is not supported. Received {record_type:?}")]
+    UnsupportedRecordType {
+        /// The unsupported record type
+        record_type: String,
+    },
+}
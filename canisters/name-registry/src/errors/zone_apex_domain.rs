@@ -1,3 +1,5 @@
+use crate::errors::InternationalizedNameError;
+
 /// Container for zone apex domain errors
 #[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
 pub enum ZoneApexDomainError {
@@ -41,4 +43,8 @@ pub enum ZoneApexDomainError {
         /// The invalid domain name label
         label: String,
     },
+
+    /// The domain name has an invalid internationalized label
+    #[error("{0}")]
+    InvalidInternationalizedName(InternationalizedNameError),
 }
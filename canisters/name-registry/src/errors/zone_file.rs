@@ -0,0 +1,71 @@
+use crate::errors::DomainRecordInputError;
+
+/// Container for zone file errors, every error has the line number where it was found starting at 1
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum ZoneFileError {
+    /// The zone file has a directive other than $ORIGIN and $TTL, e.g. $INCLUDE
+    #[error("Line {line:?}: directive {directive:?} is not supported")]
+    UnsupportedDirective {
+        /// The line of the directive
+        line: usize,
+        /// The unsupported directive
+        directive: String,
+    },
+
+    /// The value of a $ORIGIN or $TTL directive is missing or invalid
+    #[error("Line {line:?}: directive {directive:?} has an invalid value {value:?}")]
+    InvalidDirective {
+        /// The line of the directive
+        line: usize,
+        /// The directive
+        directive: String,
+        /// The invalid value
+        value: String,
+    },
+
+    /// A record starts with whitespace but there is no previous record to take its owner name from
+    #[error("Line {line:?}: record has no owner name")]
+    MissingOwner {
+        /// The line of the record
+        line: usize,
+    },
+
+    /// The record has no record type after its owner name, ttl and class
+    #[error("Line {line:?}: record has no record type")]
+    MissingRecordType {
+        /// The line of the record
+        line: usize,
+    },
+
+    /// Only records of the Internet class are supported
+    #[error("Line {line:?}: class {class:?} is not supported, only IN is")]
+    UnsupportedClass {
+        /// The line of the record
+        line: usize,
+        /// The unsupported class
+        class: String,
+    },
+
+    /// The parentheses that group a record over several lines are not balanced
+    #[error("Line {line:?}: parentheses are not balanced")]
+    UnbalancedParentheses {
+        /// The line where the unbalanced parenthesis was found
+        line: usize,
+    },
+
+    /// A quoted string is not terminated at the end of the line
+    #[error("Line {line:?}: quoted string is not terminated")]
+    UnterminatedString {
+        /// The line of the string
+        line: usize,
+    },
+
+    /// The record is not valid for the zone
+    #[error("Line {line:?}: {error}")]
+    InvalidRecord {
+        /// The line of the record
+        line: usize,
+        /// The reason why the record is invalid
+        error: DomainRecordInputError,
+    },
+}
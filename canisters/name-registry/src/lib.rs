@@ -6,9 +6,11 @@
 //! The name registry canister is responsible for managing domain zones and their name records while enabling
 //! the resolution through standard protocols.
 
+pub mod api;
 pub mod builders;
 pub mod common;
 pub mod errors;
 pub mod repositories;
+pub mod services;
 pub mod types;
 pub mod utils;
@@ -6,13 +6,14 @@
 //! The name registry canister is responsible for managing domain zones and their name records while enabling
 //! the resolution through standard protocols.
 
+pub mod api;
 pub mod builders;
 pub mod common;
+mod controllers;
 pub mod errors;
 pub mod repositories;
+pub mod services;
 pub mod types;
 pub mod utils;
 
-fn main() {
-    println!("Not implemented");
-}
+fn main() {}
@@ -0,0 +1,238 @@
+use crate::common::CERTIFIED_RECORDS_LABEL;
+use ic_certified_map::{fork, labeled, labeled_hash, AsHashTree, Hash, HashTree, RbTree};
+use serde::Serialize;
+use std::cell::RefCell;
+
+/// The certified records tree, it maps the fully qualified name of a record to the hashes of its RRsets
+/// indexed by their record type.
+pub type CertifiedRecordsTree = RbTree<Vec<u8>, RbTree<Vec<u8>, Hash>>;
+
+thread_local! {
+  /// The certified records tree is kept in heap memory and has to be rebuilt from the stored records after an upgrade.
+  static TREE: RefCell<CertifiedRecordsTree> = const { RefCell::new(RbTree::new()) };
+}
+
+/// A repository that keeps the hash tree used to certify the records of the name registry.
+pub struct CertifiedRecordsRepository {}
+
+/// Enables the initialization of the CertifiedRecords repository.
+impl CertifiedRecordsRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for CertifiedRecordsRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CertifiedRecordsRepository {
+    /// Sets the hash of the RRset with the given name and type.
+    pub fn insert(&self, name: &str, record_type: &str, hash: Hash) {
+        TREE.with(|tree| {
+            let mut tree = tree.borrow_mut();
+            if tree.get(name.as_bytes()).is_none() {
+                tree.insert(name.as_bytes().to_vec(), RbTree::new());
+            }
+
+            tree.modify(name.as_bytes(), |record_types| {
+                record_types.insert(record_type.as_bytes().to_vec(), hash);
+            });
+        })
+    }
+
+    /// Removes the RRset with the given name and type, names without any RRset are removed from the tree.
+    pub fn remove(&self, name: &str, record_type: &str) {
+        TREE.with(|tree| {
+            let mut tree = tree.borrow_mut();
+            tree.modify(name.as_bytes(), |record_types| {
+                record_types.delete(record_type.as_bytes());
+            });
+
+            if tree
+                .get(name.as_bytes())
+                .is_some_and(|record_types| record_types.is_empty())
+            {
+                tree.delete(name.as_bytes());
+            }
+        })
+    }
+
+    /// Returns the hash of the RRset with the given name and type.
+    pub fn get(&self, name: &str, record_type: &str) -> Option<Hash> {
+        TREE.with(|tree| {
+            tree.borrow()
+                .get(name.as_bytes())
+                .and_then(|record_types| record_types.get(record_type.as_bytes()).copied())
+        })
+    }
+
+    /// Returns true if the name has at least one certified RRset.
+    pub fn contains(&self, name: &str) -> bool {
+        TREE.with(|tree| tree.borrow().get(name.as_bytes()).is_some())
+    }
+
+    /// Returns the name and type of all the certified RRsets.
+    pub fn rrsets(&self) -> Vec<(String, String)> {
+        TREE.with(|tree| {
+            let mut rrsets = Vec::new();
+            tree.borrow().for_each(|name, record_types| {
+                record_types.for_each(|record_type, _| {
+                    rrsets.push((
+                        String::from_utf8_lossy(name).to_string(),
+                        String::from_utf8_lossy(record_type).to_string(),
+                    ));
+                });
+            });
+
+            rrsets
+        })
+    }
+
+    /// Returns the hash that has to be set as the certified data of the canister.
+    pub fn certified_data(&self) -> Hash {
+        TREE.with(|tree| labeled_hash(CERTIFIED_RECORDS_LABEL, &tree.borrow().root_hash()))
+    }
+
+    /// Returns the CBOR encoded witness of the RRsets with the given names and types, if no type is given for a
+    /// name the witness contains all the RRsets of the name.
+    ///
+    /// If an RRset does not exist the witness is a proof of its absence.
+    pub fn witness(&self, rrsets: &[(&str, Option<&str>)]) -> Vec<u8> {
+        TREE.with(|tree| serialize_tree(&build_witness(&tree.borrow(), rrsets)))
+    }
+}
+
+fn build_witness<'a>(
+    tree: &'a CertifiedRecordsTree,
+    rrsets: &[(&str, Option<&str>)],
+) -> HashTree<'a> {
+    let witness = rrsets
+        .iter()
+        .map(|(name, record_type)| match record_type {
+            Some(record_type) => tree.nested_witness(name.as_bytes(), |record_types| {
+                record_types.witness(record_type.as_bytes())
+            }),
+            None => tree.witness(name.as_bytes()),
+        })
+        .reduce(merge_witnesses)
+        .unwrap_or_else(|| HashTree::Pruned(tree.root_hash()));
+
+    labeled(CERTIFIED_RECORDS_LABEL, witness)
+}
+
+/// Merges two witnesses of the same tree into a witness that reveals every node revealed by any of them.
+fn merge_witnesses<'a>(left: HashTree<'a>, right: HashTree<'a>) -> HashTree<'a> {
+    match (left, right) {
+        (HashTree::Pruned(_), witness) | (witness, HashTree::Pruned(_)) => witness,
+        (HashTree::Fork(left), HashTree::Fork(right)) => {
+            let (left_left, left_right) = *left;
+            let (right_left, right_right) = *right;
+
+            fork(
+                merge_witnesses(left_left, right_left),
+                merge_witnesses(left_right, right_right),
+            )
+        }
+        (HashTree::Labeled(label, left), HashTree::Labeled(_, right)) => {
+            labeled(label, merge_witnesses(*left, *right))
+        }
+        (witness, _) => witness,
+    }
+}
+
+/// Serializes the hash tree to CBOR with the self describe tag, as expected by the IC tooling.
+fn serialize_tree(tree: &HashTree<'_>) -> Vec<u8> {
+    let mut bytes = vec![];
+    let mut serializer = serde_cbor::Serializer::new(&mut bytes);
+    serializer.self_describe().unwrap();
+    tree.serialize(&mut serializer).unwrap();
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_remove_rrsets() {
+        let repository = CertifiedRecordsRepository::default();
+        let empty_certified_data = repository.certified_data();
+
+        repository.insert("mydomain.tld.", "TXT", [1; 32]);
+        repository.insert("mydomain.tld.", "CID", [2; 32]);
+
+        assert_eq!(repository.get("mydomain.tld.", "TXT"), Some([1; 32]));
+        assert_eq!(
+            repository.rrsets(),
+            vec![
+                (String::from("mydomain.tld."), String::from("CID")),
+                (String::from("mydomain.tld."), String::from("TXT")),
+            ]
+        );
+        assert_ne!(repository.certified_data(), empty_certified_data);
+
+        repository.remove("mydomain.tld.", "TXT");
+        repository.remove("mydomain.tld.", "CID");
+
+        assert_eq!(repository.get("mydomain.tld.", "TXT"), None);
+        assert!(repository.rrsets().is_empty());
+        assert_eq!(repository.certified_data(), empty_certified_data);
+    }
+
+    #[test]
+    fn witness_reconstructs_certified_data() {
+        let repository = CertifiedRecordsRepository::default();
+        repository.insert("mydomain.tld.", "TXT", [1; 32]);
+        repository.insert("other.tld.", "TXT", [2; 32]);
+
+        for rrsets in [
+            vec![("mydomain.tld.", Some("TXT"))],
+            vec![("mydomain.tld.", Some("CID"))],
+            vec![("mydomain.tld.", None)],
+            vec![("unknown.tld.", Some("TXT"))],
+            vec![("mydomain.tld.", Some("TXT")), ("other.tld.", Some("TXT"))],
+            vec![("mydomain.tld.", None), ("mydomain.tld.", Some("TXT"))],
+        ] {
+            let root_hash = TREE.with(|tree| build_witness(&tree.borrow(), &rrsets).reconstruct());
+
+            assert_eq!(root_hash, repository.certified_data());
+            assert!(!repository.witness(&rrsets).is_empty());
+        }
+    }
+
+    #[test]
+    fn merged_witness_reveals_every_rrset() {
+        let repository = CertifiedRecordsRepository::default();
+        repository.insert("mydomain.tld.", "TXT", [1; 32]);
+        repository.insert("mydomain.tld.", "CID", [2; 32]);
+        repository.insert("other.tld.", "TXT", [3; 32]);
+        repository.insert("third.tld.", "TXT", [4; 32]);
+
+        TREE.with(|tree| {
+            let tree = tree.borrow();
+            let witness = build_witness(
+                &tree,
+                &[("mydomain.tld.", Some("TXT")), ("third.tld.", Some("TXT"))],
+            );
+            let revealed = leaves(&witness);
+
+            assert!(revealed.contains(&[1; 32].to_vec()));
+            assert!(revealed.contains(&[4; 32].to_vec()));
+            assert!(!revealed.contains(&[2; 32].to_vec()));
+            assert!(!revealed.contains(&[3; 32].to_vec()));
+        });
+    }
+
+    fn leaves(tree: &HashTree) -> Vec<Vec<u8>> {
+        match tree {
+            HashTree::Fork(forks) => [leaves(&forks.0), leaves(&forks.1)].concat(),
+            HashTree::Labeled(_, tree) => leaves(tree),
+            HashTree::Leaf(value) => vec![value.to_vec()],
+            HashTree::Empty | HashTree::Pruned(_) => vec![],
+        }
+    }
+}
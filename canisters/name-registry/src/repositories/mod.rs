@@ -1,9 +1,25 @@
 //! Repositories for domains and related data.
 
+/// Repository for the hash tree of the certified records.
+mod certified_records;
+pub use certified_records::*;
+
 /// Common configurations for repositories.
 mod setup;
 pub use setup::*;
 
+/// Repository for the header of the data kept in stable memory.
+mod storage_header;
+pub use storage_header::*;
+
 /// Repository for domain zones.
 mod zone;
 pub use zone::*;
+
+/// Repository for the metadata of domain zones.
+mod zone_metadata;
+pub use zone_metadata::*;
+
+/// Repository for the history of the changes made to domain zones.
+mod zone_history;
+pub use zone_history::*;
@@ -4,9 +4,22 @@ use ic_stable_structures::{
 };
 use std::cell::RefCell;
 
+/// Stable memory id used to store the header that describes the data kept in stable memory.
+pub const STORAGE_HEADER_MEMORY_ID: MemoryId = MemoryId::new(0);
+
 /// Stable memory id used to store the domain zones.
 pub const DOMAIN_ZONES_MEMORY_ID: MemoryId = MemoryId::new(1);
 
+/// Stable memory id the domain zones are rewritten to when they are stored in [DOMAIN_ZONES_MEMORY_ID], and the
+/// other way around, which lets the rewrite read the previous layout while writing the next one.
+pub const DOMAIN_ZONES_SWAP_MEMORY_ID: MemoryId = MemoryId::new(4);
+
+/// Stable memory id used to store the metadata of the domain zones.
+pub const DOMAIN_ZONE_METADATA_MEMORY_ID: MemoryId = MemoryId::new(2);
+
+/// Stable memory id used to store the history of the changes made to the domain zones.
+pub const ZONE_HISTORY_MEMORY_ID: MemoryId = MemoryId::new(3);
+
 /// Memory layout for the stable memory.
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -37,7 +50,6 @@ pub trait Repository<Record> {
 /// Responsible for searching for records in a repository.
 pub trait RepositorySearch<SearchInput, SearchResultItem>
 where
-    SearchInput: RepositorySearchInto<SearchResultItem>,
     Self: Repository<SearchResultItem>,
 {
     /// Searches for records in a repository based on the search input and returns a list of results
@@ -45,11 +57,12 @@ where
     fn search(&self, input: &SearchInput) -> Vec<SearchResultItem>;
 }
 
-/// This trait facilitates the mapping between a search input and the target ranges for searching in a repository.
-pub trait RepositorySearchInto<SearchResultItem> {
+/// This trait facilitates the mapping between a search input and the target ranges of keys for searching in a
+/// repository.
+pub trait RepositorySearchInto<Key> {
     /// Converts the search input into a lower range key for searching in a repository.
-    fn map_to_lower_range_key(&self) -> Result<SearchResultItem, String>;
+    fn map_to_lower_range_key(&self) -> Result<Key, String>;
 
     /// Converts the search input into a upper range key for searching in a repository.
-    fn map_to_upper_range_key(&self) -> Result<SearchResultItem, String>;
+    fn map_to_upper_range_key(&self) -> Result<Key, String>;
 }
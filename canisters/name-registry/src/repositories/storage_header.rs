@@ -0,0 +1,66 @@
+use crate::{
+    repositories::{with_memory_manager, Memory, STORAGE_HEADER_MEMORY_ID},
+    types::StorageHeader,
+};
+use ic_stable_structures::Cell;
+use std::cell::RefCell;
+
+/// The database schema for the StorageHeader repository.
+pub type StorageHeaderDatabase = Cell<StorageHeader, Memory>;
+
+thread_local! {
+  /// The memory reference to the StorageHeader repository, canisters that were installed before the header was
+  /// introduced start with the default header.
+  static DB: RefCell<StorageHeaderDatabase> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      Cell::init(memory_manager.get(STORAGE_HEADER_MEMORY_ID), StorageHeader::default())
+        .expect("the storage header memory must be readable")
+    )
+  })
+}
+
+/// A repository that keeps the header describing the data stored in stable memory.
+pub struct StorageHeaderRepository {}
+
+/// Enables the initialization of the StorageHeader repository.
+impl StorageHeaderRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for StorageHeaderRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StorageHeaderRepository {
+    /// Returns the stored header.
+    pub fn get(&self) -> StorageHeader {
+        DB.with(|m| m.borrow().get().clone())
+    }
+
+    /// Replaces the stored header.
+    pub fn set(&self, header: StorageHeader) {
+        DB.with(|m| {
+            m.borrow_mut()
+                .set(header)
+                .expect("the storage header must fit in stable memory");
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_defaults_to_legacy_schema() {
+        let repository = StorageHeaderRepository::default();
+        assert_eq!(repository.get(), StorageHeader::default());
+
+        repository.set(StorageHeader::new(1));
+        assert_eq!(repository.get(), StorageHeader::new(1));
+    }
+}
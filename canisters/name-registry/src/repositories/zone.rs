@@ -1,23 +1,29 @@
 use crate::{
-    repositories::{with_memory_manager, Memory, Repository, DOMAIN_ZONES_MEMORY_ID},
-    types::{DomainZoneEntry, DomainZoneEntryInput},
+    repositories::{
+        with_memory_manager, Memory, Repository, StorageHeaderRepository, DOMAIN_ZONES_MEMORY_ID,
+        DOMAIN_ZONES_SWAP_MEMORY_ID,
+    },
+    types::{
+        DomainZoneEntry, DomainZoneEntryInput, DomainZoneKey, DomainZoneValue, RecordName,
+        ZoneApexDomain,
+    },
 };
-use ic_stable_structures::BTreeMap;
-use std::cell::RefCell;
+use ic_stable_structures::{memory_manager::MemoryId, BTreeMap, BoundedStorable};
+use std::{cell::RefCell, ops::Bound};
 
 use super::{RepositorySearch, RepositorySearchInto};
 
 /// The database schema for the DomainZone repository.
 ///
-/// DomainZone is used as the key & value hereby transorming the BTreeMap into a Set and enabling a more efficient
-/// lookup and update operations when accessing stable memory.
-pub type DomainZoneDatabase = BTreeMap<DomainZoneEntry, (), Memory>;
+/// The records are indexed by their order preserving binary key, which makes the searches range scans over the
+/// keys, while their ttl and data are kept in the value.
+pub type DomainZoneDatabase = BTreeMap<DomainZoneKey, DomainZoneValue, Memory>;
 
 thread_local! {
   /// The memory reference to the DomainZone repository.
   static DB: RefCell<DomainZoneDatabase> = with_memory_manager(|memory_manager| {
     RefCell::new(
-      BTreeMap::init(memory_manager.get(DOMAIN_ZONES_MEMORY_ID))
+      BTreeMap::init(memory_manager.get(DomainZoneRepository::memory_id()))
     )
   })
 }
@@ -38,18 +44,149 @@ impl Default for DomainZoneRepository {
     }
 }
 
+impl DomainZoneRepository {
+    /// Searches for records that match the search input in their sort order, starting right after the
+    /// given entry if any, skipping the first `skip` matches and returning at most `limit` records.
+    pub fn search_after(
+        &self,
+        input: &DomainZoneEntryInput,
+        after: Option<&DomainZoneEntry>,
+        skip: usize,
+        limit: usize,
+    ) -> Vec<DomainZoneEntry> {
+        DB.with(|m| {
+            let start_key = match after {
+                Some(entry) => Bound::Excluded(entry.key()),
+                None => Bound::Included(input.map_to_lower_range_key().unwrap()),
+            };
+            let end_key = Bound::Included(input.map_to_upper_range_key().unwrap());
+
+            m.borrow()
+                .range((start_key, end_key))
+                .skip(skip)
+                .take(limit)
+                .map(|(key, value)| DomainZoneEntry::from_key_value(&key, value))
+                .collect()
+        })
+    }
+
+    /// Returns the records of the zone that are at or below the record name, in their sort order.
+    ///
+    /// The names below a name are stored right after it, which makes the search a single range scan.
+    pub fn search_subtree(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        record_name: &RecordName,
+    ) -> Vec<DomainZoneEntry> {
+        DB.with(|m| {
+            let start_key = DomainZoneKey::subtree_prefix(apex_domain, Some(record_name));
+            let end_key = start_key.prefix_upper_bound();
+
+            m.borrow()
+                .range(start_key..=end_key)
+                .map(|(key, value)| DomainZoneEntry::from_key_value(&key, value))
+                .collect()
+        })
+    }
+
+    /// Returns `true` if the zone has records at or below the record name, without reading them.
+    pub fn has_subtree(&self, apex_domain: &ZoneApexDomain, record_name: &RecordName) -> bool {
+        DB.with(|m| {
+            let start_key = DomainZoneKey::subtree_prefix(apex_domain, Some(record_name));
+            let end_key = start_key.prefix_upper_bound();
+
+            m.borrow().range(start_key..=end_key).next().is_some()
+        })
+    }
+
+    /// Calls the function for each of the stored records, in their sort order.
+    pub fn for_each(&self, mut f: impl FnMut(DomainZoneEntry)) {
+        DB.with(|m| {
+            m.borrow()
+                .iter()
+                .for_each(|(key, value)| f(DomainZoneEntry::from_key_value(&key, value)))
+        })
+    }
+
+    /// Returns the number of records that match the search input.
+    pub fn count(&self, input: &DomainZoneEntryInput) -> u64 {
+        DB.with(|m| {
+            let start_key = input.map_to_lower_range_key().unwrap();
+            let end_key = input.map_to_upper_range_key().unwrap();
+
+            m.borrow().range(start_key..=end_key).count() as u64
+        })
+    }
+
+    /// Rewrites the stored records that were written with a previous layout, each stored record is read as a
+    /// `LegacyKey` and `LegacyValue` and converted to the `Key` and `Value` of the next layout by the migrate
+    /// function, which is the current layout unless further migrations follow.
+    ///
+    /// The records are written to the other one of the two memories of the zones, since the stored map can't hold
+    /// keys or values larger than the ones of the layout it was created with, and the storage header is updated to
+    /// point to it. The records are streamed from one memory to the other without being kept in heap memory,
+    /// hence the size of the zones is only bounded by the instruction limit of the upgrade the rewrite runs in.
+    pub fn rewrite<LegacyKey, LegacyValue, Key, Value>(
+        &self,
+        migrate: impl Fn(LegacyKey, LegacyValue) -> (Key, Value),
+    ) where
+        LegacyKey: BoundedStorable + Ord + Clone,
+        LegacyValue: BoundedStorable,
+        Key: BoundedStorable + Ord + Clone,
+        Value: BoundedStorable,
+    {
+        let storage_header_repository = StorageHeaderRepository::default();
+        let mut header = storage_header_repository.get();
+        let swapped = !header.domain_zones_swapped.unwrap_or_default();
+        let (legacy_memory_id, memory_id) = match swapped {
+            true => (DOMAIN_ZONES_MEMORY_ID, DOMAIN_ZONES_SWAP_MEMORY_ID),
+            false => (DOMAIN_ZONES_SWAP_MEMORY_ID, DOMAIN_ZONES_MEMORY_ID),
+        };
+
+        with_memory_manager(|memory_manager| {
+            let legacy_entries = BTreeMap::<LegacyKey, LegacyValue, Memory>::init(
+                memory_manager.get(legacy_memory_id),
+            );
+            let mut entries = BTreeMap::<Key, Value, Memory>::new(memory_manager.get(memory_id));
+            for (legacy_key, legacy_value) in legacy_entries.iter() {
+                let (key, value) = migrate(legacy_key, legacy_value);
+                entries.insert(key, value);
+            }
+            legacy_entries.clear();
+        });
+
+        header.domain_zones_swapped = Some(swapped).filter(|swapped| *swapped);
+        storage_header_repository.set(header);
+        with_memory_manager(|memory_manager| {
+            DB.with(|m| *m.borrow_mut() = BTreeMap::init(memory_manager.get(memory_id)))
+        });
+    }
+
+    /// Returns the memory id the domain zones are stored in according to the storage header.
+    fn memory_id() -> MemoryId {
+        match StorageHeaderRepository::default()
+            .get()
+            .domain_zones_swapped
+            .unwrap_or_default()
+        {
+            true => DOMAIN_ZONES_SWAP_MEMORY_ID,
+            false => DOMAIN_ZONES_MEMORY_ID,
+        }
+    }
+}
+
 /// Common interfaces for the DomainZone repository, it enables storing, retrieving and removing domain zones.
 impl Repository<DomainZoneEntry> for DomainZoneRepository {
     fn exists(&self, record: &DomainZoneEntry) -> bool {
-        DB.with(|m| m.borrow().get(record).is_some())
+        DB.with(|m| m.borrow().contains_key(&record.key()))
     }
 
     fn insert(&self, record: DomainZoneEntry) {
-        DB.with(|m| m.borrow_mut().insert(record, ()));
+        DB.with(|m| m.borrow_mut().insert(record.key(), record.value()));
     }
 
     fn remove(&self, record: &DomainZoneEntry) -> bool {
-        DB.with(|m| m.borrow_mut().remove(record).is_some())
+        DB.with(|m| m.borrow_mut().remove(&record.key()).is_some())
     }
 }
 
@@ -63,7 +200,7 @@ impl RepositorySearch<DomainZoneEntryInput, DomainZoneEntry> for DomainZoneRepos
             let results = m
                 .borrow()
                 .range(start_key..=end_key)
-                .map(|(k, _)| k)
+                .map(|(key, value)| DomainZoneEntry::from_key_value(&key, value))
                 .collect::<Vec<DomainZoneEntry>>();
 
             results
@@ -81,6 +218,32 @@ mod tests {
         RecordName, ZoneApexDomain,
     };
 
+    #[test]
+    fn rewrite_domain_zones_with_new_layout() {
+        let apex_domain = ZoneApexDomain::new(String::from("internetcomputer.tld.")).unwrap();
+        let repository = DomainZoneRepository::default();
+        for data in ["first", "second"] {
+            repository.insert(DomainZoneEntry::new(
+                DomainZone::new(apex_domain.clone()),
+                DomainRecord::new(
+                    RecordName::default(),
+                    DomainRecordTypes::TXT.to_string(),
+                    60,
+                    String::from(data),
+                ),
+            ));
+        }
+
+        repository.rewrite(|key: DomainZoneKey, value: DomainZoneValue| {
+            (key, DomainZoneValue { ttl: 3600, ..value })
+        });
+
+        let mut entries = Vec::new();
+        repository.for_each(|entry| entries.push(entry));
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|entry| entry.record().ttl == 3600));
+    }
+
     #[test]
     fn init_domain_zone_repository() {
         let repository = DomainZoneRepository::default();
@@ -224,6 +387,140 @@ mod tests {
         assert_eq!(results_canister.len(), 1);
     }
 
+    #[test]
+    fn search_domain_zone_record_name_sorted_before_apex() {
+        let repository = DomainZoneRepository::default();
+        let apex_domain = ZoneApexDomain::new(String::from("numbers.tld.")).unwrap();
+        repository.insert(DomainZoneEntry::new(
+            DomainZone {
+                name: apex_domain.clone(),
+            },
+            DomainRecord {
+                name: RecordName::new(String::from("1st"), &apex_domain).unwrap(),
+                record_type: DomainRecordTypes::TXT.to_string(),
+                ttl: 0,
+                data: "~first".to_string(),
+            },
+        ));
+
+        let results = repository.search(&DomainZoneEntryInput::new(
+            DomainZoneInput {
+                name: Some(apex_domain.deref().to_string()),
+            },
+            DomainRecordInput {
+                name: Some(String::from("1st")),
+                record_type: Some(DomainRecordTypes::TXT.to_string()),
+                ..Default::default()
+            },
+        ));
+
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn search_domain_zone_pages() {
+        let repository = DomainZoneRepository::default();
+        let apex_domain = ZoneApexDomain::new(String::from("pages.tld.")).unwrap();
+        let input = DomainZoneEntryInput::new(
+            DomainZoneInput {
+                name: Some(apex_domain.deref().to_string()),
+            },
+            DomainRecordInput::default(),
+        );
+        for name in ["a", "b", "c", "d"] {
+            repository.insert(DomainZoneEntry::new(
+                DomainZone {
+                    name: apex_domain.clone(),
+                },
+                DomainRecord {
+                    name: RecordName::new(String::from(name), &apex_domain).unwrap(),
+                    record_type: DomainRecordTypes::TXT.to_string(),
+                    ttl: 0,
+                    data: String::from(name),
+                },
+            ));
+        }
+
+        let first_page = repository.search_after(&input, None, 1, 2);
+        let second_page = repository.search_after(&input, first_page.last(), 0, 2);
+
+        assert_eq!(repository.count(&input), 4);
+        assert_eq!(
+            first_page
+                .iter()
+                .chain(second_page.iter())
+                .map(|entry| entry.record().data.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn search_domain_zone_subtree() {
+        let repository = DomainZoneRepository::default();
+        let apex_domain = ZoneApexDomain::new(String::from("subtree.tld.")).unwrap();
+        for name in ["@", "wiki", "en.wiki", "*.wiki", "wikis", "a.b.wiki"] {
+            repository.insert(DomainZoneEntry::new(
+                DomainZone {
+                    name: apex_domain.clone(),
+                },
+                DomainRecord {
+                    name: RecordName::new(String::from(name), &apex_domain).unwrap(),
+                    record_type: DomainRecordTypes::TXT.to_string(),
+                    ttl: 0,
+                    data: String::from(name),
+                },
+            ));
+        }
+
+        let results = repository.search_subtree(
+            &apex_domain,
+            &RecordName::new(String::from("wiki"), &apex_domain).unwrap(),
+        );
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|entry| entry.record().data.as_str())
+                .collect::<Vec<_>>(),
+            vec!["wiki", "*.wiki", "a.b.wiki", "en.wiki"]
+        );
+    }
+
+    #[test]
+    fn has_domain_zone_subtree() {
+        let repository = DomainZoneRepository::default();
+        let apex_domain = ZoneApexDomain::new(String::from("has-subtree.tld.")).unwrap();
+        repository.insert(DomainZoneEntry::new(
+            DomainZone {
+                name: apex_domain.clone(),
+            },
+            DomainRecord {
+                name: RecordName::new(String::from("a.b.wiki"), &apex_domain).unwrap(),
+                record_type: DomainRecordTypes::TXT.to_string(),
+                ttl: 0,
+                data: String::from("a.b.wiki"),
+            },
+        ));
+
+        for (name, exists) in [
+            ("wiki", true),
+            ("b.wiki", true),
+            ("wikis", false),
+            ("c.wiki", false),
+        ] {
+            assert_eq!(
+                repository.has_subtree(
+                    &apex_domain,
+                    &RecordName::new(String::from(name), &apex_domain).unwrap()
+                ),
+                exists,
+                "{}",
+                name
+            );
+        }
+    }
+
     #[test]
     fn get_domain_zone_exact_match() {
         let repository = DomainZoneRepository::default();
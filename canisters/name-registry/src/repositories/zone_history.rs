@@ -0,0 +1,277 @@
+use crate::{
+    repositories::{with_memory_manager, Memory, Repository, ZONE_HISTORY_MEMORY_ID},
+    types::{ZoneApexDomain, ZoneHistoryEntry, ZoneHistoryItem, ZoneHistoryKey},
+};
+use ic_stable_structures::BTreeMap;
+use std::{cell::RefCell, ops::Bound};
+
+/// The database schema for the ZoneHistory repository.
+///
+/// Each change is stored as several items indexed by the zone and the sequence of the change, which keeps the
+/// changes of a zone next to each other in the order they were made.
+pub type ZoneHistoryDatabase = BTreeMap<ZoneHistoryKey, ZoneHistoryItem, Memory>;
+
+thread_local! {
+  /// The memory reference to the ZoneHistory repository.
+  static DB: RefCell<ZoneHistoryDatabase> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      BTreeMap::init(memory_manager.get(ZONE_HISTORY_MEMORY_ID))
+    )
+  })
+}
+
+/// A repository that enables keeping the history of the changes made to domain zones in stable memory.
+pub struct ZoneHistoryRepository {}
+
+/// Enables the initialization of the ZoneHistory repository.
+impl ZoneHistoryRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for ZoneHistoryRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ZoneHistoryRepository {
+    /// Returns the sequence of the oldest change kept in the history of the zone.
+    pub fn first_sequence(&self, apex_domain: &ZoneApexDomain) -> Option<u64> {
+        let start_key = ZoneHistoryKey::prefix(apex_domain, None);
+        let end_key = start_key.prefix_upper_bound();
+
+        DB.with(|m| {
+            m.borrow()
+                .range(start_key..=end_key)
+                .next()
+                .map(|(key, _)| key.sequence())
+        })
+    }
+
+    /// Returns the sequence of the newest change kept in the history of the zone.
+    pub fn last_sequence(&self, apex_domain: &ZoneApexDomain) -> Option<u64> {
+        let start_key = ZoneHistoryKey::prefix(apex_domain, None);
+        let end_key = start_key.prefix_upper_bound();
+
+        DB.with(|m| {
+            m.borrow()
+                .iter_upper_bound(&end_key)
+                .next()
+                .filter(|(key, _)| key >= &start_key)
+                .map(|(key, _)| key.sequence())
+        })
+    }
+
+    /// Returns the number of changes kept in the history of the zone, the sequences of the kept changes are
+    /// contiguous since only the oldest changes are removed.
+    pub fn count(&self, apex_domain: &ZoneApexDomain) -> u64 {
+        match (
+            self.first_sequence(apex_domain),
+            self.last_sequence(apex_domain),
+        ) {
+            (Some(first_sequence), Some(last_sequence)) => last_sequence - first_sequence + 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the changes of the zone that match the predicate in the order they were made, starting right
+    /// after the change with the given sequence if any, skipping the first `skip` matches and returning at
+    /// most `limit` changes.
+    pub fn find_after(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        after: Option<u64>,
+        predicate: impl Fn(&ZoneHistoryEntry) -> bool,
+        skip: usize,
+        limit: usize,
+    ) -> Vec<ZoneHistoryEntry> {
+        let zone_prefix = ZoneHistoryKey::prefix(apex_domain, None);
+        let start_key = match after {
+            Some(sequence) => Bound::Excluded(
+                ZoneHistoryKey::prefix(apex_domain, Some(sequence)).prefix_upper_bound(),
+            ),
+            None => Bound::Included(zone_prefix.clone()),
+        };
+        let end_key = Bound::Included(zone_prefix.prefix_upper_bound());
+
+        let mut entries = Vec::new();
+        let mut skipped = 0;
+        let mut keep_entry = |entry: ZoneHistoryEntry| {
+            if !predicate(&entry) {
+                return true;
+            }
+
+            match skipped < skip {
+                true => skipped += 1,
+                false => entries.push(entry),
+            }
+
+            entries.len() < limit
+        };
+
+        DB.with(|m| {
+            let mut items = Vec::new();
+            let mut current_sequence = None;
+            for (key, item) in m.borrow().range((start_key, end_key)) {
+                let sequence = key.sequence();
+                if current_sequence.is_some_and(|current_sequence| current_sequence != sequence) {
+                    let entry = ZoneHistoryEntry::from_items(
+                        apex_domain.clone(),
+                        current_sequence.unwrap_or_default(),
+                        items.drain(..),
+                    );
+                    if !entry.map_or(true, &mut keep_entry) {
+                        return;
+                    }
+                }
+
+                current_sequence = Some(sequence);
+                items.push(item);
+            }
+
+            if let Some(sequence) = current_sequence {
+                if let Some(entry) =
+                    ZoneHistoryEntry::from_items(apex_domain.clone(), sequence, items)
+                {
+                    keep_entry(entry);
+                }
+            }
+        });
+
+        entries
+    }
+
+    /// Removes the changes of the zone that are older than the change with the given sequence.
+    pub fn remove_before(&self, apex_domain: &ZoneApexDomain, sequence: u64) {
+        self.remove_range((
+            Bound::Included(ZoneHistoryKey::prefix(apex_domain, None)),
+            Bound::Excluded(ZoneHistoryKey::prefix(apex_domain, Some(sequence))),
+        ));
+    }
+
+    /// Removes the items within the range of keys, returns `true` if any item was removed.
+    fn remove_range(&self, range: (Bound<ZoneHistoryKey>, Bound<ZoneHistoryKey>)) -> bool {
+        DB.with(|m| {
+            let keys = m
+                .borrow()
+                .range(range)
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>();
+
+            let mut m = m.borrow_mut();
+            for key in &keys {
+                m.remove(key);
+            }
+
+            !keys.is_empty()
+        })
+    }
+}
+
+/// Common interfaces for the ZoneHistory repository, it enables storing, retrieving and removing the changes
+/// made to domain zones.
+impl Repository<ZoneHistoryEntry> for ZoneHistoryRepository {
+    fn exists(&self, record: &ZoneHistoryEntry) -> bool {
+        let start_key = ZoneHistoryKey::prefix(&record.zone, Some(record.sequence));
+        let end_key = start_key.prefix_upper_bound();
+
+        DB.with(|m| m.borrow().range(start_key..=end_key).next().is_some())
+    }
+
+    fn insert(&self, record: ZoneHistoryEntry) {
+        DB.with(|m| {
+            let mut m = m.borrow_mut();
+            for (key, item) in record.items() {
+                m.insert(key, item);
+            }
+        })
+    }
+
+    fn remove(&self, record: &ZoneHistoryEntry) -> bool {
+        let start_key = ZoneHistoryKey::prefix(&record.zone, Some(record.sequence));
+        let end_key = start_key.prefix_upper_bound();
+
+        self.remove_range((Bound::Included(start_key), Bound::Included(end_key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DomainRecord, RecordName, ZoneHistoryOperation};
+    use candid::Principal;
+
+    fn entry(apex_domain: &str, sequence: u64, data: &[&str]) -> ZoneHistoryEntry {
+        let zone = ZoneApexDomain::new(String::from(apex_domain)).unwrap();
+        let name = RecordName::default();
+
+        ZoneHistoryEntry {
+            zone,
+            sequence,
+            timestamp: sequence * 1_000,
+            caller: Principal::anonymous(),
+            operation: ZoneHistoryOperation::Append,
+            name: name.clone(),
+            record_type: String::from("TXT"),
+            before: vec![],
+            after: data
+                .iter()
+                .map(|data| {
+                    DomainRecord::new(name.clone(), String::from("TXT"), 60, data.to_string())
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn insert_and_find_zone_history() {
+        let repository = ZoneHistoryRepository::default();
+        let zone = ZoneApexDomain::new(String::from("history.tld.")).unwrap();
+        repository.insert(entry("history.tld.", 0, &["first"]));
+        repository.insert(entry("history.tld.", 1, &["second", "third"]));
+        repository.insert(entry("history.tld.", 2, &[]));
+        repository.insert(entry("sub.history.tld.", 0, &["other"]));
+
+        assert_eq!(repository.first_sequence(&zone), Some(0));
+        assert_eq!(repository.last_sequence(&zone), Some(2));
+        assert_eq!(repository.count(&zone), 3);
+        assert!(repository.exists(&entry("history.tld.", 2, &[])));
+
+        let entries = repository.find_after(&zone, None, |_| true, 1, 10);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.sequence, entry.after.len()))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (2, 0)]
+        );
+        assert_eq!(
+            repository.find_after(&zone, Some(0), |entry| entry.after.is_empty(), 0, 10),
+            vec![entry("history.tld.", 2, &[])]
+        );
+        assert_eq!(repository.find_after(&zone, None, |_| true, 0, 1).len(), 1);
+    }
+
+    #[test]
+    fn remove_oldest_zone_history() {
+        let repository = ZoneHistoryRepository::default();
+        let zone = ZoneApexDomain::new(String::from("history.tld.")).unwrap();
+        for sequence in 0..4 {
+            repository.insert(entry("history.tld.", sequence, &["data"]));
+        }
+
+        repository.remove_before(&zone, 2);
+
+        assert_eq!(repository.first_sequence(&zone), Some(2));
+        assert_eq!(repository.count(&zone), 2);
+        assert!(repository.remove(&entry("history.tld.", 3, &["data"])));
+        assert_eq!(repository.count(&zone), 1);
+        assert_eq!(
+            ZoneHistoryRepository::default()
+                .last_sequence(&ZoneApexDomain::new(String::from("unknown.tld.")).unwrap()),
+            None
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    repositories::{with_memory_manager, Memory, Repository, DOMAIN_ZONE_METADATA_MEMORY_ID},
+    types::{DomainZoneMetadata, ZoneApexDomain},
+};
+use ic_stable_structures::BTreeMap;
+use std::{cell::RefCell, ops::Bound};
+
+/// The database schema for the DomainZoneMetadata repository.
+///
+/// The metadata is indexed by the apex domain of the zone, which also makes it the list of existing zones.
+pub type DomainZoneMetadataDatabase = BTreeMap<ZoneApexDomain, DomainZoneMetadata, Memory>;
+
+thread_local! {
+  /// The memory reference to the DomainZoneMetadata repository.
+  static DB: RefCell<DomainZoneMetadataDatabase> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      BTreeMap::init(memory_manager.get(DOMAIN_ZONE_METADATA_MEMORY_ID))
+    )
+  })
+}
+
+/// A repository that enables managing the metadata of domain zones in stable memory.
+pub struct DomainZoneMetadataRepository {}
+
+/// Enables the initialization of the DomainZoneMetadata repository.
+impl DomainZoneMetadataRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for DomainZoneMetadataRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DomainZoneMetadataRepository {
+    /// Returns the metadata of the zone with the given apex domain.
+    pub fn get(&self, apex_domain: &ZoneApexDomain) -> Option<DomainZoneMetadata> {
+        DB.with(|m| m.borrow().get(apex_domain))
+    }
+
+    /// Returns the metadata of the zones that match the predicate, sorted by their apex domain and starting
+    /// right after the given apex domain if any, skipping the first `skip` matches and returning at most `limit`.
+    pub fn find_after(
+        &self,
+        after: Option<&ZoneApexDomain>,
+        predicate: impl Fn(&DomainZoneMetadata) -> bool,
+        skip: usize,
+        limit: usize,
+    ) -> Vec<DomainZoneMetadata> {
+        let start_key = match after {
+            Some(apex_domain) => Bound::Excluded(apex_domain.clone()),
+            None => Bound::Unbounded,
+        };
+
+        DB.with(|m| {
+            m.borrow()
+                .range((start_key, Bound::Unbounded))
+                .map(|(_, metadata)| metadata)
+                .filter(|metadata| predicate(metadata))
+                .skip(skip)
+                .take(limit)
+                .collect()
+        })
+    }
+
+    /// Returns the number of zones that match the predicate.
+    pub fn count(&self, predicate: impl Fn(&DomainZoneMetadata) -> bool) -> u64 {
+        DB.with(|m| {
+            m.borrow()
+                .iter()
+                .filter(|(_, metadata)| predicate(metadata))
+                .count() as u64
+        })
+    }
+}
+
+/// Common interfaces for the DomainZoneMetadata repository, it enables storing, retrieving and removing
+/// the metadata of domain zones.
+impl Repository<DomainZoneMetadata> for DomainZoneMetadataRepository {
+    fn exists(&self, record: &DomainZoneMetadata) -> bool {
+        DB.with(|m| m.borrow().contains_key(&record.name))
+    }
+
+    fn insert(&self, record: DomainZoneMetadata) {
+        DB.with(|m| m.borrow_mut().insert(record.name.clone(), record));
+    }
+
+    fn remove(&self, record: &DomainZoneMetadata) -> bool {
+        DB.with(|m| m.borrow_mut().remove(&record.name).is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{RegistrationController, RegistrationControllerRole};
+    use candid::Principal;
+
+    fn metadata(apex_domain: &str, controller_id: Principal) -> DomainZoneMetadata {
+        DomainZoneMetadata::new(
+            ZoneApexDomain::new(String::from(apex_domain)).unwrap(),
+            vec![RegistrationController::new(
+                controller_id,
+                vec![RegistrationControllerRole::Registrant],
+            )],
+        )
+    }
+
+    #[test]
+    fn insert_and_remove_zone_metadata() {
+        let repository = DomainZoneMetadataRepository::default();
+        let zone_metadata = metadata("internetcomputer.tld.", Principal::anonymous());
+
+        repository.insert(zone_metadata.clone());
+        assert!(repository.exists(&zone_metadata));
+        assert_eq!(
+            repository.get(&zone_metadata.name),
+            Some(zone_metadata.clone())
+        );
+
+        assert!(repository.remove(&zone_metadata));
+        assert!(!repository.exists(&zone_metadata));
+    }
+
+    #[test]
+    fn find_zone_metadata_after() {
+        let repository = DomainZoneMetadataRepository::default();
+        let owner = Principal::from_slice(&[1; 29]);
+        repository.insert(metadata("a.tld.", owner));
+        repository.insert(metadata("b.tld.", Principal::anonymous()));
+        repository.insert(metadata("c.tld.", owner));
+        repository.insert(metadata("d.tld.", owner));
+
+        let owned = |metadata: &DomainZoneMetadata| metadata.is_controller(&owner);
+        let results = repository.find_after(
+            Some(&ZoneApexDomain::new(String::from("a.tld.")).unwrap()),
+            owned,
+            0,
+            1,
+        );
+
+        assert_eq!(repository.count(owned), 3);
+        assert_eq!(repository.count(|_| true), 4);
+        assert_eq!(results, vec![metadata("c.tld.", owner)]);
+    }
+}
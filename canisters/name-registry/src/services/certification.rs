@@ -0,0 +1,338 @@
+use crate::{
+    api::{DomainLookup, DomainRecord},
+    common::{ANY_RECORD_TYPE, WILDCARD_LABEL},
+    repositories::{CertifiedRecordsRepository, DomainZoneRepository},
+    services::LookupService,
+    types::{DomainRecordTypes, DomainZone, DomainZoneEntry, RecordName, ZoneApexDomain},
+    utils::{canonical_name, rrset_hash},
+};
+use std::collections::BTreeSet;
+
+/// A service that keeps the certified records tree in sync with the records of the zones, which enables
+/// lookups to be answered with certified query responses.
+///
+/// The tree contains the RRsets that a lookup would answer for each name, so changing the records of a zone or
+/// creating a zone that takes over names of its parent zone requires the affected RRsets to be certified again.
+pub struct CertificationService {
+    certified_records_repository: CertifiedRecordsRepository,
+    zone_repository: DomainZoneRepository,
+    lookup_service: LookupService,
+}
+
+impl CertificationService {
+    pub fn new() -> Self {
+        Self {
+            certified_records_repository: CertifiedRecordsRepository::default(),
+            zone_repository: DomainZoneRepository::default(),
+            lookup_service: LookupService::default(),
+        }
+    }
+
+    /// Updates the certified hashes of the RRsets identified by their fully qualified name and record type.
+    pub fn certify_rrsets(&self, rrsets: impl IntoIterator<Item = (String, String)>) {
+        for (name, record_type) in rrsets.into_iter().collect::<BTreeSet<_>>() {
+            let records = self.lookup_service.find_records(&name, Some(&record_type));
+
+            match records.is_empty() {
+                true => self
+                    .certified_records_repository
+                    .remove(&name, &record_type),
+                false => self.certified_records_repository.insert(
+                    &name,
+                    &record_type,
+                    rrset_hash(&records),
+                ),
+            }
+        }
+    }
+
+    /// Updates the certified hashes of the RRsets that the changed zone entries belong to.
+    ///
+    /// Changing the delegating records of a name changes which records are answered for the names below it, so
+    /// all the RRsets stored in the zone at or below the delegation point are certified again.
+    pub fn certify_entries(&self, entries: &[DomainZoneEntry]) {
+        let mut rrsets = BTreeSet::new();
+        for entry in entries {
+            let record = DomainRecord::from(entry);
+            if LookupService::is_delegation_record(&record.record_type)
+                && !entry.record().name.is_apex()
+            {
+                rrsets.extend(self.stored_rrsets_at_or_below(entry.zone(), &entry.record().name));
+            }
+
+            rrsets.insert((record.name, record.record_type));
+        }
+
+        self.certify_rrsets(rrsets);
+    }
+
+    /// Updates the certified hashes of the RRsets that the parent zone stores at or below the apex of the given
+    /// zone, which is required when a zone starts answering for names that were previously answered by its parent
+    /// zone.
+    pub fn certify_zone(&self, apex_domain: &ZoneApexDomain) {
+        let parent_zone = apex_domain
+            .split_once('.')
+            .and_then(|(_, parent)| self.lookup_service.find_zone(parent))
+            .and_then(|(parent_apex_domain, _)| {
+                let record_name =
+                    RecordName::from_fully_qualified_name(apex_domain, &parent_apex_domain).ok()?;
+
+                Some((DomainZone::new(parent_apex_domain), record_name))
+            });
+
+        if let Some((parent_zone, record_name)) = parent_zone {
+            self.certify_rrsets(self.stored_rrsets_at_or_below(&parent_zone, &record_name));
+        }
+    }
+
+    /// Certifies all the records stored in the zones, used to rebuild the certified records tree after an upgrade.
+    pub fn certify_all(&self) {
+        let mut rrsets = BTreeSet::new();
+        self.zone_repository.for_each(|entry| {
+            let record = DomainRecord::from(&entry);
+            rrsets.insert((record.name, record.record_type));
+        });
+
+        self.certify_rrsets(rrsets);
+    }
+
+    /// Returns the hash that has to be set as the certified data of the canister.
+    pub fn certified_data(&self) -> [u8; 32] {
+        self.certified_records_repository.certified_data()
+    }
+
+    /// Returns the CBOR encoded witness of the RRsets that answered the lookup of the domain and record type.
+    ///
+    /// Besides the looked up RRset, the witness contains the CNAME records followed by the lookup and the RRsets of
+    /// their targets, which proves that the chain was followed correctly. Lookups without answers also include the
+    /// CNAME RRset at the end of the chain and the SOA RRsets that locate the zones of the domain and of the end of
+    /// the chain, which proves that the chain was not cut short. Names without certified RRsets also include the
+    /// wildcard names that could have answered for them, which proves the synthesized answers as well as the
+    /// absence of answers that could have been synthesized.
+    pub fn witness(&self, domain: &str, record_type: &str, lookup: &DomainLookup) -> Vec<u8> {
+        let domain = canonical_name(domain);
+        let record_type = record_type.to_ascii_uppercase();
+        let record_type = (record_type != ANY_RECORD_TYPE).then_some(record_type.as_str());
+        let cname_record_type = DomainRecordTypes::CNAME.to_string();
+        let soa_record_type = DomainRecordTypes::SOA.to_string();
+
+        let mut rrsets = vec![(domain.as_str(), record_type)];
+        let mut chain_end = domain.as_str();
+        for cname in lookup
+            .additionals
+            .iter()
+            .filter(|record| record.record_type == cname_record_type)
+        {
+            rrsets.push((cname.name.as_str(), Some(cname_record_type.as_str())));
+            rrsets.push((cname.data.as_str(), record_type));
+            chain_end = cname.data.as_str();
+        }
+        let proves_chain_end = lookup.answers.is_empty()
+            && record_type.is_some_and(|record_type| record_type != cname_record_type);
+        if proves_chain_end {
+            rrsets.push((chain_end, Some(cname_record_type.as_str())));
+        }
+
+        let wildcard_rrsets = rrsets
+            .iter()
+            .filter(|(name, _)| !self.certified_records_repository.contains(name))
+            .flat_map(|(name, record_type)| {
+                self.wildcard_candidates(name)
+                    .into_iter()
+                    .map(move |wildcard_name| (wildcard_name, *record_type))
+            })
+            .collect::<Vec<_>>();
+        rrsets.extend(
+            wildcard_rrsets
+                .iter()
+                .map(|(wildcard_name, record_type)| (wildcard_name.as_str(), *record_type)),
+        );
+
+        let zone_apex_candidates = match proves_chain_end {
+            true => [domain.as_str(), chain_end]
+                .iter()
+                .flat_map(|name| self.zone_apex_candidates(name))
+                .collect(),
+            false => Vec::new(),
+        };
+        rrsets.extend(
+            zone_apex_candidates
+                .iter()
+                .map(|name| (name.as_str(), Some(soa_record_type.as_str()))),
+        );
+
+        self.certified_records_repository.witness(&rrsets)
+    }
+
+    /// Returns the names at and above the given name up to the first one with a certified SOA RRset, which is
+    /// the apex of the zone of the name.
+    fn zone_apex_candidates(&self, name: &str) -> Vec<String> {
+        let soa_record_type = DomainRecordTypes::SOA.to_string();
+        let mut candidates = Vec::new();
+        let mut name = name;
+        loop {
+            candidates.push(name.to_string());
+            if self
+                .certified_records_repository
+                .get(name, &soa_record_type)
+                .is_some()
+            {
+                break;
+            }
+
+            match name
+                .split_once('.')
+                .filter(|(_, parent)| !parent.is_empty())
+            {
+                Some((_, parent)) => name = parent,
+                None => break,
+            }
+        }
+
+        candidates
+    }
+
+    /// Returns the names that prove which wildcard name answers for a name without certified RRsets, which are the
+    /// wildcard names at its ancestors and the ancestors themselves, from the closest to the farthest one and up to
+    /// the first wildcard name or ancestor with certified RRsets.
+    ///
+    /// Wildcard names only answer for the names below their closest existing ancestor, and the apex of a zone
+    /// always has a certified SOA RRset, hence the candidates never go past the apex of the zone.
+    fn wildcard_candidates(&self, name: &str) -> Vec<String> {
+        let mut candidates = Vec::new();
+        let mut suffix = name;
+        while let Some((_, parent)) = suffix.split_once('.') {
+            if parent.is_empty() {
+                break;
+            }
+
+            let candidate = format!("{}.{}", WILDCARD_LABEL, parent);
+            let is_certified = self.certified_records_repository.contains(&candidate);
+            candidates.push(candidate);
+            if is_certified {
+                break;
+            }
+
+            candidates.push(parent.to_string());
+            if self.certified_records_repository.contains(parent) {
+                break;
+            }
+
+            suffix = parent;
+        }
+
+        candidates
+    }
+
+    fn stored_rrsets_at_or_below(
+        &self,
+        zone: &DomainZone,
+        record_name: &RecordName,
+    ) -> Vec<(String, String)> {
+        self.zone_repository
+            .search_subtree(&zone.name, record_name)
+            .iter()
+            .map(DomainRecord::from)
+            .map(|record| (record.name, record.record_type))
+            .collect()
+    }
+}
+
+impl Default for CertificationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        repositories::{DomainZoneMetadataRepository, Repository},
+        types::{
+            DomainRecord as ZoneRecord, DomainZone, DomainZoneEntry, DomainZoneMetadata, RecordName,
+        },
+    };
+
+    fn insert_record(apex_domain: &str, name: &str, data: &str) {
+        let apex_domain = ZoneApexDomain::new(String::from(apex_domain)).unwrap();
+        DomainZoneMetadataRepository::default()
+            .insert(DomainZoneMetadata::new(apex_domain.clone(), vec![]));
+        DomainZoneRepository::default().insert(DomainZoneEntry::new(
+            DomainZone::new(apex_domain.clone()),
+            ZoneRecord::new(
+                RecordName::new(String::from(name), &apex_domain).unwrap(),
+                String::from("TXT"),
+                60,
+                String::from(data),
+            ),
+        ));
+    }
+
+    fn rrset(name: &str) -> (String, String) {
+        (String::from(name), String::from("TXT"))
+    }
+
+    #[test]
+    fn certify_rrsets_follows_the_stored_records() {
+        let service = CertificationService::default();
+        let repository = CertifiedRecordsRepository::default();
+        insert_record("cert.tld.", "@", "first");
+        insert_record("cert.tld.", "@", "second");
+        service.certify_rrsets([rrset("cert.tld."), rrset("missing.cert.tld.")]);
+
+        assert_eq!(repository.rrsets(), vec![rrset("cert.tld.")]);
+        assert_eq!(
+            repository.get("cert.tld.", "TXT"),
+            Some(rrset_hash(
+                &LookupService::default().find_records("cert.tld.", Some("TXT"))
+            ))
+        );
+
+        let mut entries = Vec::new();
+        DomainZoneRepository::default().for_each(|entry| entries.push(entry));
+        for entry in entries {
+            DomainZoneRepository::default().remove(&entry);
+        }
+        service.certify_rrsets([rrset("cert.tld.")]);
+
+        assert!(repository.rrsets().is_empty());
+    }
+
+    #[test]
+    fn certify_zone_updates_names_taken_over_by_the_zone() {
+        let service = CertificationService::default();
+        insert_record("tld.", "wiki.cert", "parent");
+        insert_record("tld.", "other", "parent");
+        service.certify_all();
+
+        DomainZoneMetadataRepository::default().insert(DomainZoneMetadata::new(
+            ZoneApexDomain::new(String::from("cert.tld.")).unwrap(),
+            vec![],
+        ));
+        service.certify_zone(&ZoneApexDomain::new(String::from("cert.tld.")).unwrap());
+
+        assert_eq!(
+            CertifiedRecordsRepository::default().rrsets(),
+            vec![rrset("other.tld.")]
+        );
+    }
+
+    #[test]
+    fn certify_all_certifies_every_rrset() {
+        insert_record("cert.tld.", "@", "apex");
+        insert_record("cert.tld.", "wiki", "wiki");
+        insert_record("other.tld.", "@", "other");
+
+        CertificationService::default().certify_all();
+
+        assert_eq!(
+            CertifiedRecordsRepository::default().rrsets(),
+            vec![
+                rrset("cert.tld."),
+                rrset("other.tld."),
+                rrset("wiki.cert.tld."),
+            ]
+        );
+    }
+}
@@ -0,0 +1,742 @@
+use crate::{
+    api::{DomainLookup, DomainRecord},
+    common::{ANY_RECORD_TYPE, MAX_CNAME_CHAIN_LENGTH, WILDCARD_LABEL},
+    errors::LookupAtError,
+    repositories::{DomainZoneMetadataRepository, DomainZoneRepository, RepositorySearch},
+    services::{SoaService, ZoneHistoryService},
+    types::{
+        DomainRecordInput, DomainRecordTypes, DomainZoneEntryInput, DomainZoneInput, RecordName,
+        ZoneApexDomain,
+    },
+    utils::{canonical_name, domain_name_splits},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Deref,
+};
+
+/// A service that resolves domain names to the records of the zones stored in the name registry.
+pub struct LookupService {
+    zone_repository: DomainZoneRepository,
+    zone_metadata_repository: DomainZoneMetadataRepository,
+    soa_service: SoaService,
+    zone_history_service: ZoneHistoryService,
+}
+
+impl LookupService {
+    pub fn new() -> Self {
+        Self {
+            zone_repository: DomainZoneRepository::default(),
+            zone_metadata_repository: DomainZoneMetadataRepository::default(),
+            soa_service: SoaService::default(),
+            zone_history_service: ZoneHistoryService::default(),
+        }
+    }
+
+    /// Looks up the records of a domain name that match the given record type.
+    ///
+    /// The reserved "ANY" record type returns all the records of the domain name, unknown domains
+    /// result in an empty lookup.
+    ///
+    /// When the domain name has no records of the record type but is an alias, the chain of CNAME records is
+    /// followed inside the zone of the domain name, the records found at its end are the answers and the followed
+    /// CNAME records are the additionals.
+    ///
+    /// Domain names without records of their own are answered with the records of the closest wildcard name
+    /// of their zone, e.g. "*.example.icp." answers for "www.example.icp." but not for "example.icp." nor for
+    /// the names that have records below them, as defined in RFC 4592.
+    ///
+    /// Lookups without answers for a domain name that is delegated to another name server or naming canister
+    /// include the delegating NS and NC records in the authorities and the addresses of the name servers that
+    /// are inside the zone in the additionals. Other lookups without answers for a domain name of a zone include
+    /// the SOA record of the zone in the authorities, which tells resolvers how long the negative answer can be
+    /// cached.
+    pub fn lookup(&self, domain: &str, record_type: &str) -> DomainLookup {
+        let domain = canonical_name(domain);
+        let record_type = record_type.to_ascii_uppercase();
+        let Some((apex_domain, _)) = self.find_zone(&domain) else {
+            return DomainLookup::default();
+        };
+
+        let cname_record_type = DomainRecordTypes::CNAME.to_string();
+        let (answers, mut additionals) = match record_type.as_str() {
+            ANY_RECORD_TYPE => (self.resolve_records(&domain, None), Vec::new()),
+            record_type if record_type == cname_record_type => {
+                (self.resolve_records(&domain, Some(record_type)), Vec::new())
+            }
+            record_type => self.follow_cname_chain(&apex_domain, &domain, record_type),
+        };
+
+        let mut authorities = Vec::new();
+        let answered_name = additionals
+            .last()
+            .map_or(domain, |cname: &DomainRecord| cname.data.clone());
+        if let Some((apex_domain, record_name)) = self
+            .find_zone(&answered_name)
+            .filter(|_| answers.is_empty())
+        {
+            match self.find_delegation(&apex_domain, &record_name) {
+                Some((_, delegation_records)) => {
+                    additionals.extend(self.find_glue_records(&apex_domain, &delegation_records));
+                    authorities = delegation_records;
+                }
+                None => {
+                    authorities.extend(self.soa_service.negative_answer_authority(&apex_domain))
+                }
+            }
+        }
+
+        DomainLookup {
+            answers,
+            additionals,
+            authorities,
+            ..Default::default()
+        }
+    }
+
+    /// Looks up the records stored for a domain name as they were at the given time in nanoseconds since the
+    /// UNIX epoch, the reserved "ANY" record type returns all the records of the domain name.
+    ///
+    /// The records are rebuilt from the history of the zone that currently contains the domain name, neither
+    /// wildcards, aliases nor delegations are resolved and the answers are not certified. Fails if the history of
+    /// the zone no longer goes back to the given time.
+    pub fn lookup_at(
+        &self,
+        domain: &str,
+        record_type: &str,
+        timestamp: u64,
+    ) -> Result<DomainLookup, LookupAtError> {
+        let domain = canonical_name(domain);
+        let record_type = record_type.to_ascii_uppercase();
+        let Some((apex_domain, record_name)) = self.find_zone(&domain) else {
+            return Ok(DomainLookup::default());
+        };
+        let record_type =
+            Some(record_type.as_str()).filter(|record_type| *record_type != ANY_RECORD_TYPE);
+
+        let mut rrsets = BTreeMap::<String, Vec<DomainRecord>>::new();
+        for record in self.find_zone_records(&apex_domain, &record_name, record_type) {
+            rrsets
+                .entry(record.record_type.clone())
+                .or_default()
+                .push(record);
+        }
+        rrsets.extend(self.zone_history_service.changed_rrsets_at(
+            &apex_domain,
+            &record_name,
+            record_type,
+            timestamp,
+        )?);
+
+        Ok(DomainLookup {
+            answers: rrsets.into_values().flatten().collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the records that a lookup answers for the domain name in the most specific zone that contains it,
+    /// optionally filtered by record type.
+    ///
+    /// Names at or below a delegation point of the zone are answered by the name server or naming canister that
+    /// the name is delegated to, hence only the delegating NS and NC records of the delegation point are returned
+    /// for them.
+    ///
+    /// The domain name and record type are expected to be in their canonical lowercase and uppercase form.
+    pub fn find_records(&self, domain: &str, record_type: Option<&str>) -> Vec<DomainRecord> {
+        match self.find_zone(domain) {
+            Some((apex_domain, record_name)) => {
+                self.find_visible_records(&apex_domain, &record_name, record_type)
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the records that a lookup answers for the domain name, which are the records found by
+    /// [LookupService::find_records] or otherwise the records synthesized from the matching wildcard name.
+    pub fn resolve_records(&self, domain: &str, record_type: Option<&str>) -> Vec<DomainRecord> {
+        match self.find_zone(domain) {
+            Some((apex_domain, record_name)) => {
+                self.resolve_visible_records(&apex_domain, &record_name, record_type)
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the topmost delegation point of the zone at or above the record name together with its delegating
+    /// records, a delegation point is a name below the apex with NS or NC records.
+    pub fn find_delegation(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        record_name: &RecordName,
+    ) -> Option<(RecordName, Vec<DomainRecord>)> {
+        if record_name.is_apex() {
+            return None;
+        }
+
+        let labels = record_name.split('.').collect::<Vec<_>>();
+        (1..=labels.len()).find_map(|label_count| {
+            let name = RecordName::new(labels[labels.len() - label_count..].join("."), apex_domain)
+                .ok()?;
+            let delegation_records = self
+                .find_zone_records(apex_domain, &name, None)
+                .into_iter()
+                .filter(|record| Self::is_delegation_record(&record.record_type))
+                .collect::<Vec<_>>();
+
+            (!delegation_records.is_empty()).then_some((name, delegation_records))
+        })
+    }
+
+    /// Returns true if records of the record type delegate the name to another name server or naming canister.
+    pub fn is_delegation_record(record_type: &str) -> bool {
+        record_type == DomainRecordTypes::NS.to_string()
+            || record_type == DomainRecordTypes::NC.to_string()
+    }
+
+    /// Returns the records of the zone that a lookup answers for the record name, see [LookupService::find_records].
+    fn find_visible_records(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        record_name: &RecordName,
+        record_type: Option<&str>,
+    ) -> Vec<DomainRecord> {
+        match self.find_delegation(apex_domain, record_name) {
+            None => self.find_zone_records(apex_domain, record_name, record_type),
+            Some((delegation_name, delegation_records)) if delegation_name == *record_name => {
+                delegation_records
+                    .into_iter()
+                    .filter(|record| {
+                        record_type.map_or(true, |record_type| record.record_type == record_type)
+                    })
+                    .collect()
+            }
+            Some(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the records of the zone that a lookup answers for the record name, see
+    /// [LookupService::resolve_records].
+    fn resolve_visible_records(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        record_name: &RecordName,
+        record_type: Option<&str>,
+    ) -> Vec<DomainRecord> {
+        let records = self.find_visible_records(apex_domain, record_name, record_type);
+        if !records.is_empty() || self.find_delegation(apex_domain, record_name).is_some() {
+            return records;
+        }
+
+        self.synthesize_wildcard_records(apex_domain, record_name, record_type)
+    }
+
+    /// Returns the records of the wildcard name at the closest encloser of the record name, renamed after the
+    /// record name, when the record name does not exist in the zone.
+    ///
+    /// A name exists when it has records or when names below it have records, the closest encloser is the
+    /// nearest ancestor of the record name that exists and the apex is always one.
+    fn synthesize_wildcard_records(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        record_name: &RecordName,
+        record_type: Option<&str>,
+    ) -> Vec<DomainRecord> {
+        if record_name.is_apex() {
+            return Vec::new();
+        }
+
+        let name_exists = |name: &str| {
+            self.zone_repository
+                .has_subtree(apex_domain, &RecordName::new_unchecked(name.to_string()))
+        };
+        if name_exists(record_name.deref()) {
+            return Vec::new();
+        }
+
+        let labels = record_name.split('.').collect::<Vec<_>>();
+        let closest_encloser = (1..labels.len())
+            .map(|label_index| labels[label_index..].join("."))
+            .find(|name| name_exists(name));
+        let wildcard_name = match closest_encloser {
+            Some(closest_encloser) => format!("{}.{}", WILDCARD_LABEL, closest_encloser),
+            None => String::from(WILDCARD_LABEL),
+        };
+        let Ok(wildcard_name) = RecordName::new(wildcard_name, apex_domain) else {
+            return Vec::new();
+        };
+
+        let domain = record_name.to_fully_qualified_name(apex_domain);
+        self.find_zone_records(apex_domain, &wildcard_name, record_type)
+            .into_iter()
+            .map(|record| DomainRecord {
+                name: domain.clone(),
+                ..record
+            })
+            .collect()
+    }
+
+    fn find_zone_records(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        record_name: &RecordName,
+        record_type: Option<&str>,
+    ) -> Vec<DomainRecord> {
+        self.zone_repository
+            .search(&DomainZoneEntryInput::new(
+                DomainZoneInput {
+                    name: Some(apex_domain.deref().to_string()),
+                },
+                DomainRecordInput {
+                    name: Some(record_name.deref().to_string()),
+                    record_type: record_type.map(String::from),
+                    ..Default::default()
+                },
+            ))
+            .iter()
+            .map(DomainRecord::from)
+            .collect()
+    }
+
+    /// Returns the address records of the name servers of a delegation that are stored in the zone, which lets
+    /// resolvers reach name servers that are named after the delegated domain.
+    fn find_glue_records(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        delegation_records: &[DomainRecord],
+    ) -> Vec<DomainRecord> {
+        let ns_record_type = DomainRecordTypes::NS.to_string();
+        delegation_records
+            .iter()
+            .filter(|record| record.record_type == ns_record_type)
+            .filter_map(|record| {
+                RecordName::from_fully_qualified_name(&record.data, apex_domain).ok()
+            })
+            .flat_map(|name_server| {
+                [DomainRecordTypes::A, DomainRecordTypes::AAAA]
+                    .into_iter()
+                    .flat_map(move |record_type| {
+                        self.find_zone_records(
+                            apex_domain,
+                            &name_server,
+                            Some(&record_type.to_string()),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Follows the CNAME records that start at the domain name while their targets are in the given zone, and
+    /// returns the records of the record type at the end of the chain together with the followed CNAME records.
+    ///
+    /// The chain ends at the first name with records of the record type, at a name that is not an alias, at a
+    /// name outside of the zone, at a name that was already visited or after [MAX_CNAME_CHAIN_LENGTH] records.
+    fn follow_cname_chain(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        domain: &str,
+        record_type: &str,
+    ) -> (Vec<DomainRecord>, Vec<DomainRecord>) {
+        let mut name = domain.to_string();
+        let mut chain = Vec::new();
+        let mut visited_names = BTreeSet::new();
+
+        loop {
+            let answers = self.resolve_records(&name, Some(record_type));
+            if !answers.is_empty()
+                || chain.len() >= MAX_CNAME_CHAIN_LENGTH
+                || !visited_names.insert(name.clone())
+            {
+                return (answers, chain);
+            }
+
+            let cname = match self.find_zone(&name) {
+                Some((zone_apex_domain, record_name)) if zone_apex_domain == *apex_domain => self
+                    .resolve_visible_records(
+                        &zone_apex_domain,
+                        &record_name,
+                        Some(&DomainRecordTypes::CNAME.to_string()),
+                    )
+                    .into_iter()
+                    .next(),
+                _ => None,
+            };
+            let Some(cname) = cname else {
+                return (answers, chain);
+            };
+
+            name = cname.data.clone();
+            chain.push(cname);
+        }
+    }
+
+    /// Splits the domain name into the apex domain of the most specific zone stored in the
+    /// registry and the record name relative to it.
+    pub fn find_zone(&self, domain: &str) -> Option<(ZoneApexDomain, RecordName)> {
+        domain_name_splits(domain)
+            .into_iter()
+            .find_map(|(record_name, apex_domain)| {
+                let apex_domain = ZoneApexDomain::new(apex_domain).ok()?;
+                self.zone_metadata_repository.get(&apex_domain)?;
+
+                let record_name = RecordName::new(record_name, &apex_domain).ok()?;
+
+                Some((apex_domain, record_name))
+            })
+    }
+}
+
+impl Default for LookupService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::DEFAULT_SOA_MINIMUM,
+        repositories::Repository,
+        types::{
+            DomainRecord as ZoneRecord, DomainRecordTypes, DomainZone, DomainZoneEntry,
+            DomainZoneMetadata,
+        },
+    };
+    use candid::Nat;
+
+    fn insert_record(apex_domain: &str, name: &str, record_type: DomainRecordTypes, data: &str) {
+        let apex_domain = ZoneApexDomain::new(String::from(apex_domain)).unwrap();
+        DomainZoneMetadataRepository::default()
+            .insert(DomainZoneMetadata::new(apex_domain.clone(), vec![]));
+        DomainZoneRepository::default().insert(DomainZoneEntry::new(
+            DomainZone::new(apex_domain.clone()),
+            ZoneRecord::new(
+                RecordName::new(String::from(name), &apex_domain).unwrap(),
+                record_type.to_string(),
+                3600,
+                String::from(data),
+            ),
+        ));
+    }
+
+    #[test]
+    fn lookup_apex_record() {
+        insert_record(
+            "lookup.tld.",
+            "@",
+            DomainRecordTypes::CID,
+            "qoctq-giaaa-aaaaa-aaaea-cai",
+        );
+
+        let lookup = LookupService::default().lookup("lookup.tld.", "cid");
+
+        assert_eq!(
+            lookup.answers,
+            vec![DomainRecord {
+                name: String::from("lookup.tld."),
+                record_type: DomainRecordTypes::CID.to_string(),
+                ttl: Nat::from(3600u32),
+                data: String::from("qoctq-giaaa-aaaaa-aaaea-cai"),
+            }]
+        );
+        assert!(lookup.additionals.is_empty());
+        assert!(lookup.authorities.is_empty());
+    }
+
+    #[test]
+    fn lookup_subdomain_record_case_insensitive() {
+        insert_record(
+            "subdomain.tld.",
+            "wiki",
+            DomainRecordTypes::CID,
+            "qoctq-giaaa-aaaaa-aaaea-cai",
+        );
+        insert_record(
+            "subdomain.tld.",
+            "@",
+            DomainRecordTypes::CID,
+            "rrkah-fqaaa-aaaaa-aaaaq-cai",
+        );
+
+        let lookup = LookupService::default().lookup("Wiki.SubDomain.tld.", "CID");
+
+        assert_eq!(lookup.answers.len(), 1);
+        assert_eq!(lookup.answers[0].name, "wiki.subdomain.tld.");
+        assert_eq!(lookup.answers[0].data, "qoctq-giaaa-aaaaa-aaaea-cai");
+    }
+
+    #[test]
+    fn lookup_internationalized_domain() {
+        insert_record("café.tld.", "вики", DomainRecordTypes::TXT, "hello");
+
+        for domain in ["Вики.CAFÉ.tld.", "xn--b1amah.xn--caf-dma.tld."] {
+            let lookup = LookupService::default().lookup(domain, "TXT");
+
+            assert_eq!(lookup.answers.len(), 1);
+            assert_eq!(lookup.answers[0].name, "xn--b1amah.xn--caf-dma.tld.");
+        }
+    }
+
+    #[test]
+    fn lookup_any_returns_all_record_types() {
+        insert_record(
+            "any.tld.",
+            "@",
+            DomainRecordTypes::CID,
+            "qoctq-giaaa-aaaaa-aaaea-cai",
+        );
+        insert_record("any.tld.", "@", DomainRecordTypes::TXT, "hello");
+        insert_record("any.tld.", "other", DomainRecordTypes::TXT, "world");
+
+        let lookup = LookupService::default().lookup("any.tld.", "ANY");
+
+        assert_eq!(
+            lookup
+                .answers
+                .iter()
+                .map(|record| record.record_type.as_str())
+                .collect::<Vec<_>>(),
+            vec!["CID", "TXT"]
+        );
+    }
+
+    #[test]
+    fn lookup_unknown_domain_is_empty() {
+        insert_record(
+            "known.tld.",
+            "@",
+            DomainRecordTypes::CID,
+            "qoctq-giaaa-aaaaa-aaaea-cai",
+        );
+
+        assert_eq!(
+            LookupService::default().lookup("unknown.tld.", "CID"),
+            DomainLookup::default()
+        );
+        assert_eq!(
+            LookupService::default().lookup("known.tld.", "TXT"),
+            DomainLookup::default()
+        );
+        assert_eq!(
+            LookupService::default().lookup("invalid", "CID"),
+            DomainLookup::default()
+        );
+    }
+
+    #[test]
+    fn lookup_without_answers_includes_soa_authority() {
+        insert_record("negative.tld.", "wiki", DomainRecordTypes::TXT, "hello");
+        SoaService::default()
+            .create_soa(&ZoneApexDomain::new(String::from("negative.tld.")).unwrap());
+
+        let lookup = LookupService::default().lookup("wiki.negative.tld.", "CID");
+
+        assert!(lookup.answers.is_empty());
+        assert_eq!(lookup.authorities.len(), 1);
+        assert_eq!(lookup.authorities[0].name, "negative.tld.");
+        assert_eq!(lookup.authorities[0].record_type, "SOA");
+        assert_eq!(lookup.authorities[0].ttl, DEFAULT_SOA_MINIMUM);
+        assert!(LookupService::default()
+            .lookup("wiki.negative.tld.", "TXT")
+            .authorities
+            .is_empty());
+    }
+
+    #[test]
+    fn lookup_follows_cname_chain_inside_the_zone() {
+        insert_record(
+            "alias.tld.",
+            "www",
+            DomainRecordTypes::CNAME,
+            "web.alias.tld.",
+        );
+        insert_record("alias.tld.", "web", DomainRecordTypes::CNAME, "alias.tld.");
+        insert_record("alias.tld.", "@", DomainRecordTypes::TXT, "hello");
+
+        let lookup = LookupService::default().lookup("www.alias.tld.", "TXT");
+
+        assert_eq!(lookup.answers.len(), 1);
+        assert_eq!(lookup.answers[0].name, "alias.tld.");
+        assert_eq!(
+            lookup
+                .additionals
+                .iter()
+                .map(|record| record.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["www.alias.tld.", "web.alias.tld."]
+        );
+
+        let lookup = LookupService::default().lookup("www.alias.tld.", "CNAME");
+        assert_eq!(lookup.answers.len(), 1);
+        assert!(lookup.additionals.is_empty());
+    }
+
+    #[test]
+    fn lookup_stops_cname_chain() {
+        insert_record("loop.tld.", "a", DomainRecordTypes::CNAME, "b.loop.tld.");
+        insert_record("loop.tld.", "b", DomainRecordTypes::CNAME, "a.loop.tld.");
+        insert_record(
+            "loop.tld.",
+            "out",
+            DomainRecordTypes::CNAME,
+            "www.other.tld.",
+        );
+        insert_record("other.tld.", "www", DomainRecordTypes::CNAME, "other.tld.");
+        insert_record("other.tld.", "@", DomainRecordTypes::TXT, "other");
+        for index in 0..=MAX_CNAME_CHAIN_LENGTH {
+            insert_record(
+                "loop.tld.",
+                &format!("long{}", index),
+                DomainRecordTypes::CNAME,
+                &format!("long{}.loop.tld.", index + 1),
+            );
+        }
+        insert_record(
+            "loop.tld.",
+            &format!("long{}", MAX_CNAME_CHAIN_LENGTH + 1),
+            DomainRecordTypes::TXT,
+            "end",
+        );
+
+        let lookup = LookupService::default().lookup("a.loop.tld.", "TXT");
+        assert!(lookup.answers.is_empty());
+        assert_eq!(lookup.additionals.len(), 2);
+
+        // the target outside of the zone is answered but its CNAME records are not followed
+        let lookup = LookupService::default().lookup("out.loop.tld.", "TXT");
+        assert!(lookup.answers.is_empty());
+        assert_eq!(lookup.additionals.len(), 1);
+
+        let lookup = LookupService::default().lookup("long0.loop.tld.", "TXT");
+        assert!(lookup.answers.is_empty());
+        assert_eq!(lookup.additionals.len(), MAX_CNAME_CHAIN_LENGTH);
+    }
+
+    #[test]
+    fn lookup_synthesizes_wildcard_records() {
+        insert_record("wild.tld.", "*", DomainRecordTypes::TXT, "wildcard");
+        insert_record(
+            "wild.tld.",
+            "*.alias",
+            DomainRecordTypes::CNAME,
+            "web.wild.tld.",
+        );
+        insert_record("wild.tld.", "web", DomainRecordTypes::A, "192.0.2.1");
+        insert_record("wild.tld.", "app.preview", DomainRecordTypes::TXT, "app");
+        insert_record("wild.tld.", "*.preview", DomainRecordTypes::A, "192.0.2.2");
+        insert_record("wild.tld.", "team", DomainRecordTypes::NS, "ns.other.tld.");
+
+        let lookup = LookupService::default().lookup("www.wild.tld.", "TXT");
+        assert_eq!(
+            lookup.answers,
+            vec![DomainRecord {
+                name: String::from("www.wild.tld."),
+                record_type: DomainRecordTypes::TXT.to_string(),
+                ttl: Nat::from(3600u32),
+                data: String::from("wildcard"),
+            }]
+        );
+
+        // the synthesized CNAME record is followed like the stored ones
+        let lookup = LookupService::default().lookup("www.alias.wild.tld.", "A");
+        assert_eq!(lookup.answers.len(), 1);
+        assert_eq!(lookup.answers[0].name, "web.wild.tld.");
+        assert_eq!(lookup.additionals[0].name, "www.alias.wild.tld.");
+
+        // the closest wildcard answers and names with records below them are not synthesized
+        let lookup = LookupService::default().lookup("www.preview.wild.tld.", "A");
+        assert_eq!(lookup.answers[0].data, "192.0.2.2");
+        assert!(LookupService::default()
+            .lookup("www.preview.wild.tld.", "TXT")
+            .answers
+            .is_empty());
+        assert!(LookupService::default()
+            .lookup("preview.wild.tld.", "TXT")
+            .answers
+            .is_empty());
+        assert_eq!(
+            LookupService::default()
+                .lookup("app.preview.wild.tld.", "TXT")
+                .answers[0]
+                .data,
+            "app"
+        );
+
+        // the apex and the delegated names are not matched by wildcards
+        assert!(LookupService::default()
+            .lookup("wild.tld.", "TXT")
+            .answers
+            .is_empty());
+        let lookup = LookupService::default().lookup("www.team.wild.tld.", "TXT");
+        assert!(lookup.answers.is_empty());
+        assert_eq!(lookup.authorities[0].record_type, "NS");
+    }
+
+    #[test]
+    fn lookup_below_delegation_point_returns_referral() {
+        insert_record("corp.tld.", "@", DomainRecordTypes::NS, "ns1.corp.tld.");
+        insert_record(
+            "corp.tld.",
+            "team",
+            DomainRecordTypes::NS,
+            "ns1.team.corp.tld.",
+        );
+        insert_record(
+            "corp.tld.",
+            "team",
+            DomainRecordTypes::NC,
+            "qoctq-giaaa-aaaaa-aaaea-cai",
+        );
+        insert_record("corp.tld.", "ns1.team", DomainRecordTypes::A, "192.0.2.1");
+        insert_record("corp.tld.", "wiki.team", DomainRecordTypes::TXT, "occluded");
+        insert_record(
+            "corp.tld.",
+            "sub.wiki.team",
+            DomainRecordTypes::NS,
+            "ns.other.tld.",
+        );
+        insert_record("corp.tld.", "www", DomainRecordTypes::TXT, "www");
+
+        let lookup = LookupService::default().lookup("wiki.team.corp.tld.", "TXT");
+        assert!(lookup.answers.is_empty());
+        assert_eq!(
+            lookup
+                .authorities
+                .iter()
+                .map(|record| (record.name.as_str(), record.record_type.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("team.corp.tld.", "NC"), ("team.corp.tld.", "NS")]
+        );
+        assert_eq!(lookup.additionals.len(), 1);
+        assert_eq!(lookup.additionals[0].name, "ns1.team.corp.tld.");
+
+        // the topmost delegation point applies to the names below nested delegations
+        let lookup = LookupService::default().lookup("a.sub.wiki.team.corp.tld.", "TXT");
+        assert_eq!(lookup.authorities.len(), 2);
+        assert_eq!(lookup.authorities[0].name, "team.corp.tld.");
+
+        // the delegating records are answered at the delegation point
+        let lookup = LookupService::default().lookup("team.corp.tld.", "NS");
+        assert_eq!(lookup.answers.len(), 1);
+        assert!(lookup.authorities.is_empty());
+        let lookup = LookupService::default().lookup("team.corp.tld.", "ANY");
+        assert_eq!(lookup.answers.len(), 2);
+
+        // names outside of the delegation and the apex are answered by the zone
+        assert_eq!(
+            LookupService::default()
+                .lookup("www.corp.tld.", "TXT")
+                .answers
+                .len(),
+            1
+        );
+        assert_eq!(
+            LookupService::default()
+                .lookup("corp.tld.", "NS")
+                .answers
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use crate::{
+    common::SCHEMA_VERSION,
+    errors::MigrationError,
+    repositories::{DomainZoneRepository, StorageHeaderRepository},
+    types::{
+        DomainRecord, DomainZone, DomainZoneEntry, DomainZoneKey, DomainZoneValue, RecordName,
+        StorageHeader, ZoneApexDomain,
+    },
+};
+use ic_stable_structures::{BoundedStorable, Storable};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+
+/// A step that upgrades the data kept in stable memory from the previous schema version to its own.
+struct Migration {
+    /// The schema version of the data after the migration.
+    schema_version: u32,
+    /// Rewrites the stored data, e.g. with [crate::repositories::DomainZoneRepository::rewrite].
+    migrate: fn(),
+}
+
+/// The migrations of the stored data sorted by their schema version, the last one must match [SCHEMA_VERSION].
+const MIGRATIONS: [Migration; 3] = [
+    // The storage header was introduced without changing the layout of the stored data.
+    Migration {
+        schema_version: 1,
+        migrate: || {},
+    },
+    // The records were stored as Candid encoded keys without values, they are now stored with a binary key and
+    // their ttl and data in the value.
+    Migration {
+        schema_version: 2,
+        migrate: || {
+            DomainZoneRepository::default().rewrite(
+                |(zone, record): (DomainZone, DomainRecord), _: ()| {
+                    let entry = DomainZoneEntry::new(zone, record);
+
+                    (BinaryDomainZoneKey::from_entry(&entry), entry.value())
+                },
+            )
+        },
+    },
+    // The record names were stored as a single field, their labels are now stored from right to left to sort
+    // the records in the canonical order of RFC 4034.
+    Migration {
+        schema_version: 3,
+        migrate: || {
+            DomainZoneRepository::default().rewrite(
+                |key: BinaryDomainZoneKey, value: DomainZoneValue| {
+                    let entry = key.to_entry(value);
+
+                    (entry.key(), entry.value())
+                },
+            )
+        },
+    },
+];
+
+/// The key of the records written with schema version 2, it is made of the labels of the apex domain from right
+/// to left, the record name and the record type, each label and field ends with a zero byte and the apex domain
+/// with an empty label, followed by the SHA-256 digest of the record data.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct BinaryDomainZoneKey(Vec<u8>);
+
+impl BinaryDomainZoneKey {
+    fn from_entry(entry: &DomainZoneEntry) -> Self {
+        let mut bytes = Vec::new();
+        for label in entry
+            .zone()
+            .name
+            .split('.')
+            .rev()
+            .filter(|label| !label.is_empty())
+        {
+            bytes.extend_from_slice(label.as_bytes());
+            bytes.push(DomainZoneKey::TERMINATOR);
+        }
+        bytes.push(DomainZoneKey::TERMINATOR);
+        for field in [
+            entry.record().name.as_bytes(),
+            entry.record().record_type.as_bytes(),
+        ] {
+            bytes.extend_from_slice(field);
+            bytes.push(DomainZoneKey::TERMINATOR);
+        }
+        bytes.extend_from_slice(&Sha256::digest(entry.record().data.as_bytes()));
+
+        Self(bytes)
+    }
+
+    fn to_entry(&self, value: DomainZoneValue) -> DomainZoneEntry {
+        let mut fields = self.0.split(|byte| *byte == DomainZoneKey::TERMINATOR);
+        let mut apex_labels = fields
+            .by_ref()
+            .take_while(|label| !label.is_empty())
+            .map(|label| String::from_utf8_lossy(label).to_string())
+            .collect::<Vec<_>>();
+        apex_labels.reverse();
+        let mut next_field =
+            || String::from_utf8_lossy(fields.next().unwrap_or_default()).to_string();
+        let record_name = next_field();
+        let record_type = next_field();
+
+        DomainZoneEntry::new(
+            DomainZone::new(ZoneApexDomain::new_unchecked(format!(
+                "{}.",
+                apex_labels.join(".")
+            ))),
+            DomainRecord::new(
+                RecordName::new_unchecked(record_name),
+                record_type,
+                value.ttl,
+                value.data,
+            ),
+        )
+    }
+}
+
+impl Storable for BinaryDomainZoneKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(bytes.into_owned())
+    }
+}
+
+impl BoundedStorable for BinaryDomainZoneKey {
+    const MAX_SIZE: u32 = DomainZoneKey::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// A service that keeps the layout of the data kept in stable memory up to date across upgrades.
+pub struct MigrationService {
+    storage_header_repository: StorageHeaderRepository,
+}
+
+impl MigrationService {
+    pub fn new() -> Self {
+        Self {
+            storage_header_repository: StorageHeaderRepository::default(),
+        }
+    }
+
+    /// Marks the stored data as written with the current schema version, used when the canister is installed
+    /// and before it is upgraded.
+    pub fn save_schema_version(&self) {
+        self.set_schema_version(SCHEMA_VERSION);
+    }
+
+    /// Runs the migrations that are newer than the schema version of the stored data, in order, and returns the
+    /// schema version of the data before the migrations.
+    ///
+    /// It must run before the stored data is accessed by other services since the data might not be readable
+    /// with the current layout.
+    pub fn migrate(&self) -> Result<u32, MigrationError> {
+        let schema_version = self.storage_header_repository.get().schema_version;
+        if schema_version > SCHEMA_VERSION {
+            return Err(MigrationError::UnsupportedSchemaVersion {
+                schema_version,
+                supported_schema_version: SCHEMA_VERSION,
+            });
+        }
+
+        for migration in MIGRATIONS
+            .iter()
+            .filter(|migration| migration.schema_version > schema_version)
+        {
+            (migration.migrate)();
+            self.set_schema_version(migration.schema_version);
+        }
+
+        Ok(schema_version)
+    }
+
+    /// Updates the schema version of the stored header, keeping the rest of the header as it is.
+    fn set_schema_version(&self, schema_version: u32) {
+        self.storage_header_repository.set(StorageHeader {
+            schema_version,
+            ..self.storage_header_repository.get()
+        });
+    }
+}
+
+impl Default for MigrationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::LEGACY_SCHEMA_VERSION,
+        repositories::{with_memory_manager, Memory, DOMAIN_ZONES_MEMORY_ID},
+        types::{RecordName, ZoneApexDomain},
+    };
+    use ic_stable_structures::BTreeMap;
+
+    #[test]
+    fn migrations_end_at_the_current_schema_version() {
+        assert!(MIGRATIONS
+            .windows(2)
+            .all(|migrations| migrations[0].schema_version < migrations[1].schema_version));
+        assert_eq!(
+            MIGRATIONS.last().map(|migration| migration.schema_version),
+            Some(SCHEMA_VERSION)
+        );
+    }
+
+    #[test]
+    fn migrate_legacy_data() {
+        let service = MigrationService::default();
+
+        assert_eq!(service.migrate(), Ok(LEGACY_SCHEMA_VERSION));
+        assert_eq!(
+            StorageHeaderRepository::default().get(),
+            StorageHeader::new(SCHEMA_VERSION)
+        );
+        assert_eq!(service.migrate(), Ok(SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn migrate_candid_encoded_records() {
+        let apex_domain = ZoneApexDomain::new(String::from("legacy.tld.")).unwrap();
+        let legacy_entries = ["first", "second"].map(|data| {
+            (
+                DomainZone::new(apex_domain.clone()),
+                DomainRecord::new(
+                    RecordName::default(),
+                    String::from("TXT"),
+                    60,
+                    String::from(data),
+                ),
+            )
+        });
+        with_memory_manager(|memory_manager| {
+            let mut legacy_database = BTreeMap::<(DomainZone, DomainRecord), (), Memory>::init(
+                memory_manager.get(DOMAIN_ZONES_MEMORY_ID),
+            );
+            for legacy_entry in legacy_entries.clone() {
+                legacy_database.insert(legacy_entry, ());
+            }
+        });
+        StorageHeaderRepository::default().set(StorageHeader::new(1));
+
+        assert_eq!(MigrationService::default().migrate(), Ok(1));
+
+        let mut entries = Vec::new();
+        DomainZoneRepository::default().for_each(|entry| entries.push(entry));
+        let mut expected_entries =
+            legacy_entries.map(|(zone, record)| DomainZoneEntry::new(zone, record));
+        expected_entries.sort();
+        assert_eq!(entries, expected_entries);
+    }
+
+    #[test]
+    fn migrate_record_names_to_canonical_order() {
+        let apex_domain = ZoneApexDomain::new(String::from("legacy.tld.")).unwrap();
+        let legacy_entries = ["@", "a.b", "b", "*.b", "c"].map(|name| {
+            DomainZoneEntry::new(
+                DomainZone::new(apex_domain.clone()),
+                DomainRecord::new(
+                    RecordName::new(String::from(name), &apex_domain).unwrap(),
+                    String::from("TXT"),
+                    60,
+                    String::from(name),
+                ),
+            )
+        });
+        with_memory_manager(|memory_manager| {
+            let mut legacy_database =
+                BTreeMap::<BinaryDomainZoneKey, DomainZoneValue, Memory>::init(
+                    memory_manager.get(DOMAIN_ZONES_MEMORY_ID),
+                );
+            for legacy_entry in legacy_entries.iter() {
+                legacy_database.insert(
+                    BinaryDomainZoneKey::from_entry(legacy_entry),
+                    legacy_entry.value(),
+                );
+            }
+        });
+        StorageHeaderRepository::default().set(StorageHeader::new(2));
+
+        assert_eq!(MigrationService::default().migrate(), Ok(2));
+
+        let mut names = Vec::new();
+        DomainZoneRepository::default()
+            .for_each(|entry| names.push(entry.record().name.to_string()));
+        assert_eq!(names, vec!["@", "b", "*.b", "a.b", "c"]);
+        assert_eq!(
+            StorageHeaderRepository::default().get(),
+            StorageHeader {
+                schema_version: SCHEMA_VERSION,
+                domain_zones_swapped: Some(true),
+            }
+        );
+        with_memory_manager(|memory_manager| {
+            let legacy_database = BTreeMap::<BinaryDomainZoneKey, DomainZoneValue, Memory>::init(
+                memory_manager.get(DOMAIN_ZONES_MEMORY_ID),
+            );
+            assert!(legacy_database.is_empty());
+        });
+    }
+
+    #[test]
+    fn migrate_rejects_newer_schema_versions() {
+        StorageHeaderRepository::default().set(StorageHeader::new(SCHEMA_VERSION + 1));
+
+        assert_eq!(
+            MigrationService::default().migrate(),
+            Err(MigrationError::UnsupportedSchemaVersion {
+                schema_version: SCHEMA_VERSION + 1,
+                supported_schema_version: SCHEMA_VERSION,
+            })
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! Services that implement the business logic of the name registry canister.
+
+/// Service to keep the certified records tree in sync with the zones.
+mod certification;
+pub use certification::*;
+
+/// Service to resolve domain names to their records.
+mod lookup;
+pub use lookup::*;
+
+/// Service to upgrade the layout of the data kept in stable memory.
+mod migrations;
+pub use migrations::*;
+
+/// Service to manage the records of the zones.
+mod records;
+pub use records::*;
+
+/// Service to maintain the SOA record of the zones.
+mod soa;
+pub use soa::*;
+
+/// Service to provide information about the zones.
+mod zones;
+pub use zones::*;
+
+/// Service to keep the history of the changes made to the zones.
+mod zone_history;
+pub use zone_history::*;
+
+/// Service to export and import the records of the zones as zone files.
+mod zone_file;
+pub use zone_file::*;
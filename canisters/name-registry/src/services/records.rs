@@ -0,0 +1,1372 @@
+use crate::{
+    api::{
+        DomainRecord as DomainRecordResult, DomainRecordInput, GetRecordsInput, GetRecordsResult,
+        ManageRecordsInput, ManageRecordsOperation, PaginationInfo, RemoveRecordOperationInput,
+    },
+    common::ANY_RECORD_TYPE,
+    errors::{DomainRecordInputError, GetRecordsError, ManageRecordsError, PaginationError},
+    repositories::{
+        DomainZoneMetadataRepository, DomainZoneRepository, Repository, RepositorySearch,
+    },
+    services::{CertificationService, SoaService, ZoneHistoryService},
+    types::{
+        CallContext, DomainRecord, DomainRecordInput as DomainRecordSearchInput, DomainRecordTypes,
+        DomainZone, DomainZoneEntry, DomainZoneEntryInput, DomainZoneInput, DomainZoneMetadata,
+        RecordData, RecordName, ZoneApexDomain, ZoneHistoryOperation,
+    },
+    utils::{decode_cursor, encode_cursor},
+};
+use candid::Decode;
+use ic_stable_structures::Storable;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Deref,
+};
+
+/// A record operation that passed validation and can be applied to the zone without failing.
+enum ZoneOperation {
+    Append(Vec<DomainRecord>),
+    Override(Vec<DomainRecord>),
+    Remove(Vec<(RecordName, Option<String>)>),
+}
+
+impl ZoneOperation {
+    /// The names whose records can be changed by the operation.
+    fn names(&self) -> BTreeSet<RecordName> {
+        match self {
+            Self::Append(records) | Self::Override(records) => {
+                records.iter().map(|record| record.name.clone()).collect()
+            }
+            Self::Remove(records) => records.iter().map(|(name, _)| name.clone()).collect(),
+        }
+    }
+
+    fn history_operation(&self) -> ZoneHistoryOperation {
+        match self {
+            Self::Append(_) => ZoneHistoryOperation::Append,
+            Self::Override(_) => ZoneHistoryOperation::Override,
+            Self::Remove(_) => ZoneHistoryOperation::Remove,
+        }
+    }
+}
+
+/// The data of the records of a name indexed by their record type, used to validate the result of the operations
+/// before they are applied.
+#[derive(Default)]
+struct NameRRsets(BTreeMap<String, BTreeSet<String>>);
+
+impl NameRRsets {
+    /// Adds the record, a zone only has one SOA record so adding one replaces the existing one.
+    fn insert(&mut self, record: &DomainRecord) {
+        let rrset = self.0.entry(record.record_type.clone()).or_default();
+        if record.record_type == DomainRecordTypes::SOA.to_string() {
+            rrset.clear();
+        }
+
+        rrset.insert(record.data.clone());
+    }
+
+    /// Removes the RRset of the record type, or all the RRsets but the SOA record if no type is given.
+    fn remove(&mut self, record_type: Option<&str>) {
+        match record_type {
+            Some(record_type) => {
+                self.0.remove(record_type);
+            }
+            None => self
+                .0
+                .retain(|record_type, _| *record_type == DomainRecordTypes::SOA.to_string()),
+        }
+    }
+
+    fn has_cname_conflict(&self) -> bool {
+        self.0
+            .get(&DomainRecordTypes::CNAME.to_string())
+            .is_some_and(|cnames| cnames.len() > 1 || self.0.len() > 1)
+    }
+}
+
+/// A service that manages the records of the zones stored in the name registry.
+pub struct RecordsService {
+    zone_repository: DomainZoneRepository,
+    zone_metadata_repository: DomainZoneMetadataRepository,
+    certification_service: CertificationService,
+    soa_service: SoaService,
+    zone_history_service: ZoneHistoryService,
+}
+
+impl RecordsService {
+    pub fn new() -> Self {
+        Self {
+            zone_repository: DomainZoneRepository::default(),
+            zone_metadata_repository: DomainZoneMetadataRepository::default(),
+            certification_service: CertificationService::default(),
+            soa_service: SoaService::default(),
+            zone_history_service: ZoneHistoryService::default(),
+        }
+    }
+
+    /// Applies the list of operations to the records of the zone.
+    ///
+    /// All operations are validated before any change is made, if any of them fails the zone is left untouched
+    /// and the error contains the index of the operation that failed.
+    ///
+    /// Only canister controllers and zone controllers with a role that allows managing records can change
+    /// the zone, canister controllers implicitly create the zone if it does not exist yet.
+    ///
+    /// Every successful batch increments the serial of the SOA record of the zone, the SOA record can be replaced
+    /// to change its other values but not removed, and its serial is always managed by the registry.
+    ///
+    /// Every RRset changed by an operation, including the SOA record, is recorded in the history of the zone.
+    pub fn manage_records(
+        &self,
+        context: &CallContext,
+        input: &ManageRecordsInput,
+    ) -> Result<(), ManageRecordsError> {
+        if input.sign_with_tecdsa.unwrap_or(false) {
+            return Err(ManageRecordsError::SigningNotSupported);
+        }
+
+        let apex_domain = ZoneApexDomain::new(input.domain.to_ascii_lowercase())
+            .map_err(ManageRecordsError::InvalidDomain)?;
+        let metadata = self.zone_metadata_repository.get(&apex_domain);
+        let is_authorized = context.is_canister_controller
+            || metadata
+                .as_ref()
+                .is_some_and(|metadata| metadata.can_manage_records(&context.caller));
+
+        if !is_authorized {
+            return Err(ManageRecordsError::Unauthorized {
+                caller: context.caller.to_text(),
+                domain: apex_domain.deref().to_string(),
+            });
+        }
+
+        let operations = input
+            .operations
+            .iter()
+            .enumerate()
+            .map(|(index, operation)| {
+                Self::validate_operation(&apex_domain, operation)
+                    .map_err(|error| ManageRecordsError::InvalidOperation { index, error })
+            })
+            .collect::<Result<Vec<ZoneOperation>, ManageRecordsError>>()?;
+        self.validate_cname_exclusivity(&apex_domain, &operations)?;
+
+        if metadata.is_none() {
+            self.zone_metadata_repository
+                .insert(DomainZoneMetadata::new(apex_domain.clone(), vec![]));
+            self.certification_service.certify_zone(&apex_domain);
+        }
+
+        let previous_serial = self.soa_service.serial(&apex_domain);
+        let zone = DomainZone::new(apex_domain);
+        let mut changed_entries = Vec::new();
+        for operation in operations {
+            let names = operation.names();
+            let history_operation = operation.history_operation();
+            let records_before = self.find_names_records(&zone, &names);
+            changed_entries.extend(self.apply_operation(&zone, operation));
+
+            self.zone_history_service.record_changes(
+                context,
+                &zone.name,
+                history_operation,
+                &records_before,
+                &self.find_names_records(&zone, &names),
+            );
+        }
+
+        let apex_names = BTreeSet::from([RecordName::default()]);
+        let apex_records_before = self.find_names_records(&zone, &apex_names);
+        changed_entries.extend(
+            self.soa_service
+                .increment_serial(&zone.name, previous_serial),
+        );
+        self.zone_history_service.record_changes(
+            context,
+            &zone.name,
+            ZoneHistoryOperation::Override,
+            &apex_records_before,
+            &self.find_names_records(&zone, &apex_names),
+        );
+
+        self.certification_service.certify_entries(&changed_entries);
+
+        Ok(())
+    }
+
+    /// Returns a page of the records of the zone, sorted by their name and type.
+    pub fn get_records(
+        &self,
+        input: &GetRecordsInput,
+    ) -> Result<GetRecordsResult, GetRecordsError> {
+        let apex_domain = ZoneApexDomain::new(input.domain.to_ascii_lowercase())
+            .map_err(GetRecordsError::InvalidDomain)?;
+        let zone = DomainZone::new(apex_domain);
+        let options = input.options.clone().unwrap_or_default();
+        let after = match &options.cursor {
+            Some(cursor) => Some(DomainZoneEntry::new(
+                zone.clone(),
+                Self::decode_record_cursor(cursor).map_err(GetRecordsError::InvalidPagination)?,
+            )),
+            None => None,
+        };
+
+        let search_input = DomainZoneEntryInput::new(
+            DomainZoneInput {
+                name: Some(zone.name.deref().to_string()),
+            },
+            DomainRecordSearchInput::default(),
+        );
+        let limit = options.effective_limit() as usize;
+        // one more record than requested is loaded to know if there is a next page
+        let mut entries = self.zone_repository.search_after(
+            &search_input,
+            after.as_ref(),
+            options.start as usize,
+            limit + 1,
+        );
+        let has_next_page = entries.len() > limit;
+        entries.truncate(limit);
+
+        let next_cursor = match entries.last() {
+            Some(entry) if has_next_page => Some(encode_cursor(&entry.record().to_bytes())),
+            _ => None,
+        };
+
+        Ok(GetRecordsResult {
+            records: entries.iter().map(DomainRecordResult::from).collect(),
+            info: PaginationInfo {
+                total: options
+                    .cursor
+                    .is_none()
+                    .then(|| self.zone_repository.count(&search_input)),
+                limit: entries.len() as u64,
+                start: options.start,
+                next_cursor,
+            },
+        })
+    }
+
+    fn decode_record_cursor(cursor: &str) -> Result<DomainRecord, PaginationError> {
+        decode_cursor(cursor)
+            .and_then(|bytes| Decode!(&bytes, DomainRecord).ok())
+            .ok_or_else(|| PaginationError::InvalidCursor {
+                cursor: cursor.to_string(),
+            })
+    }
+
+    fn validate_operation(
+        apex_domain: &ZoneApexDomain,
+        operation: &ManageRecordsOperation,
+    ) -> Result<ZoneOperation, DomainRecordInputError> {
+        match operation {
+            ManageRecordsOperation::Append(records) => Ok(ZoneOperation::Append(
+                Self::validate_records(apex_domain, records)?,
+            )),
+            ManageRecordsOperation::Override(records) => Ok(ZoneOperation::Override(
+                Self::validate_records(apex_domain, records)?,
+            )),
+            ManageRecordsOperation::Remove(records) => Ok(ZoneOperation::Remove(
+                records
+                    .iter()
+                    .map(|record| Self::validate_remove_record(apex_domain, record))
+                    .collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+
+    /// Validates that after applying the operations no name of the zone has a CNAME record together with other
+    /// records, or more than one CNAME record, since an alias can't have records of its own.
+    ///
+    /// The operations are applied to a copy of the RRsets of the names they change, the zone is not modified.
+    fn validate_cname_exclusivity(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        operations: &[ZoneOperation],
+    ) -> Result<(), ManageRecordsError> {
+        let zone = DomainZone::new(apex_domain.clone());
+        let mut rrsets = BTreeMap::<&RecordName, NameRRsets>::new();
+
+        for operation in operations {
+            match operation {
+                ZoneOperation::Append(records) => {
+                    for record in records {
+                        rrsets
+                            .entry(&record.name)
+                            .or_insert_with(|| self.find_name_rrsets(&zone, &record.name))
+                            .insert(record);
+                    }
+                }
+                ZoneOperation::Override(records) => {
+                    for record in records {
+                        rrsets
+                            .entry(&record.name)
+                            .or_insert_with(|| self.find_name_rrsets(&zone, &record.name))
+                            .remove(Some(&record.record_type));
+                    }
+
+                    for record in records {
+                        rrsets.entry(&record.name).or_default().insert(record);
+                    }
+                }
+                ZoneOperation::Remove(records) => {
+                    for (name, record_type) in records {
+                        rrsets
+                            .entry(name)
+                            .or_insert_with(|| self.find_name_rrsets(&zone, name))
+                            .remove(record_type.as_deref());
+                    }
+                }
+            }
+        }
+
+        match rrsets
+            .into_iter()
+            .find(|(_, name_rrsets)| name_rrsets.has_cname_conflict())
+        {
+            Some((name, _)) => Err(ManageRecordsError::CnameConflict {
+                name: name.to_fully_qualified_name(apex_domain),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn find_name_rrsets(&self, zone: &DomainZone, name: &RecordName) -> NameRRsets {
+        let mut name_rrsets = NameRRsets::default();
+        for entry in self.find_records(zone, name, None) {
+            name_rrsets.insert(entry.record());
+        }
+
+        name_rrsets
+    }
+
+    fn validate_records(
+        apex_domain: &ZoneApexDomain,
+        records: &[DomainRecordInput],
+    ) -> Result<Vec<DomainRecord>, DomainRecordInputError> {
+        records
+            .iter()
+            .map(|record| Self::validate_record(apex_domain, record))
+            .collect()
+    }
+
+    fn validate_record(
+        apex_domain: &ZoneApexDomain,
+        record: &DomainRecordInput,
+    ) -> Result<DomainRecord, DomainRecordInputError> {
+        let name = Self::validate_record_name(apex_domain, &record.name)?;
+        let record_type = Self::validate_record_type(&record.record_type)?;
+        let ttl =
+            u32::try_from(&record.ttl.0).map_err(|_| DomainRecordInputError::TtlTooLarge {
+                ttl: record.ttl.to_string(),
+                max_ttl: DomainRecord::max_ttl_value(),
+            })?;
+
+        if record.data.len() > DomainRecord::FIELD_DATA_BYTE_SIZE as usize {
+            return Err(DomainRecordInputError::DataTooLong {
+                data_length: record.data.len(),
+                max_data_length: DomainRecord::FIELD_DATA_BYTE_SIZE as usize,
+            });
+        }
+
+        if record_type == DomainRecordTypes::SOA && !name.is_apex() {
+            return Err(DomainRecordInputError::SoaRecordNotAtApex {
+                name: record.name.clone(),
+            });
+        }
+
+        let data = RecordData::parse(record_type, &record.data)
+            .map_err(|error| DomainRecordInputError::InvalidRecordData {
+                record_type: record_type.to_string(),
+                error,
+            })?
+            .to_string();
+
+        Ok(DomainRecord::new(name, record_type.to_string(), ttl, data))
+    }
+
+    fn validate_remove_record(
+        apex_domain: &ZoneApexDomain,
+        record: &RemoveRecordOperationInput,
+    ) -> Result<(RecordName, Option<String>), DomainRecordInputError> {
+        let name = Self::validate_record_name(apex_domain, &record.name)?;
+        let record_type = match &record.record_type {
+            Some(record_type) => match Self::validate_record_type(record_type)? {
+                DomainRecordTypes::SOA => {
+                    return Err(DomainRecordInputError::RequiredRecordType {
+                        record_type: DomainRecordTypes::SOA.to_string(),
+                    })
+                }
+                record_type => Some(record_type.to_string()),
+            },
+            None => None,
+        };
+
+        Ok((name, record_type))
+    }
+
+    fn validate_record_name(
+        apex_domain: &ZoneApexDomain,
+        name: &str,
+    ) -> Result<RecordName, DomainRecordInputError> {
+        RecordName::from_fully_qualified_name(name, apex_domain).map_err(|error| {
+            DomainRecordInputError::InvalidRecordName {
+                name: name.to_string(),
+                error,
+            }
+        })
+    }
+
+    /// Validates that the record type is one of the supported record types, ignoring the case.
+    fn validate_record_type(
+        record_type: &str,
+    ) -> Result<DomainRecordTypes, DomainRecordInputError> {
+        let record_type = record_type.to_ascii_uppercase();
+
+        if record_type.is_empty() {
+            return Err(DomainRecordInputError::NonEmptyRecordType);
+        }
+
+        if record_type.len() > DomainRecord::FIELD_RECORD_TYPE_BYTE_SIZE as usize {
+            return Err(DomainRecordInputError::RecordTypeTooLong {
+                record_type_length: record_type.len(),
+                max_record_type_length: DomainRecord::FIELD_RECORD_TYPE_BYTE_SIZE as usize,
+            });
+        }
+
+        if !record_type.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(DomainRecordInputError::InvalidRecordType { record_type });
+        }
+
+        if record_type == ANY_RECORD_TYPE {
+            return Err(DomainRecordInputError::ReservedRecordType { record_type });
+        }
+
+        record_type
+            .parse()
+            .map_err(DomainRecordInputError::UnsupportedRecordType)
+    }
+
+    /// Applies the operation to the zone and returns the entries that were inserted or removed.
+    fn apply_operation(&self, zone: &DomainZone, operation: ZoneOperation) -> Vec<DomainZoneEntry> {
+        let mut changed_entries = Vec::new();
+        match operation {
+            ZoneOperation::Append(records) => {
+                for record in records {
+                    // records with the same data are replaced to avoid duplicates that only differ in the ttl
+                    for entry in self.find_records(zone, &record.name, Some(&record.record_type)) {
+                        if entry.record().data == record.data {
+                            self.zone_repository.remove(&entry);
+                            changed_entries.push(entry);
+                        }
+                    }
+
+                    changed_entries.extend(self.insert_record(zone, record));
+                }
+            }
+            ZoneOperation::Override(records) => {
+                let rrsets = records
+                    .iter()
+                    .map(|record| (record.name.clone(), record.record_type.clone()))
+                    .collect::<BTreeSet<_>>();
+
+                for (name, record_type) in rrsets {
+                    changed_entries.extend(self.remove_records(zone, &name, Some(&record_type)));
+                }
+
+                for record in records {
+                    changed_entries.extend(self.insert_record(zone, record));
+                }
+            }
+            ZoneOperation::Remove(records) => {
+                for (name, record_type) in records {
+                    // the SOA record is required, removing all the records of the apex keeps it in place
+                    let entries = self.find_records(zone, &name, record_type.as_deref());
+                    for entry in entries.into_iter().filter(|entry| {
+                        entry.record().record_type != DomainRecordTypes::SOA.to_string()
+                    }) {
+                        self.zone_repository.remove(&entry);
+                        changed_entries.push(entry);
+                    }
+                }
+            }
+        }
+
+        changed_entries
+    }
+
+    /// Inserts the record and returns the entries that were inserted or removed, a zone only has one SOA record
+    /// so inserting one replaces the existing one.
+    fn insert_record(&self, zone: &DomainZone, record: DomainRecord) -> Vec<DomainZoneEntry> {
+        let mut changed_entries = Vec::new();
+        if record.record_type == DomainRecordTypes::SOA.to_string() {
+            changed_entries.extend(self.remove_records(
+                zone,
+                &record.name,
+                Some(&record.record_type),
+            ));
+        }
+
+        let entry = DomainZoneEntry::new(zone.clone(), record);
+        self.zone_repository.insert(entry.clone());
+        changed_entries.push(entry);
+
+        changed_entries
+    }
+
+    fn remove_records(
+        &self,
+        zone: &DomainZone,
+        name: &RecordName,
+        record_type: Option<&str>,
+    ) -> Vec<DomainZoneEntry> {
+        let entries = self.find_records(zone, name, record_type);
+        for entry in &entries {
+            self.zone_repository.remove(entry);
+        }
+
+        entries
+    }
+
+    /// Returns all the records of the names.
+    fn find_names_records(
+        &self,
+        zone: &DomainZone,
+        names: &BTreeSet<RecordName>,
+    ) -> Vec<DomainRecord> {
+        names
+            .iter()
+            .flat_map(|name| self.find_records(zone, name, None))
+            .map(|entry| entry.record().clone())
+            .collect()
+    }
+
+    fn find_records(
+        &self,
+        zone: &DomainZone,
+        name: &RecordName,
+        record_type: Option<&str>,
+    ) -> Vec<DomainZoneEntry> {
+        self.zone_repository.search(&DomainZoneEntryInput::new(
+            DomainZoneInput {
+                name: Some(zone.name.deref().to_string()),
+            },
+            DomainRecordSearchInput {
+                name: Some(name.deref().to_string()),
+                record_type: record_type.map(String::from),
+                ..Default::default()
+            },
+        ))
+    }
+}
+
+impl Default for RecordsService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{CreateZoneInput, PaginationOptions},
+        errors::{RecordDataError, RecordNameError, RecordTypeError},
+        repositories::CertifiedRecordsRepository,
+        services::{LookupService, ZonesService},
+        types::{RegistrationController, RegistrationControllerRole},
+        utils::rrset_hash,
+    };
+    use candid::{Nat, Principal};
+    use rstest::*;
+
+    fn record_input(name: &str, record_type: &str, ttl: u32, data: &str) -> DomainRecordInput {
+        DomainRecordInput {
+            name: String::from(name),
+            record_type: String::from(record_type),
+            ttl: Nat::from(ttl),
+            data: String::from(data),
+        }
+    }
+
+    fn manage_records(
+        domain: &str,
+        operations: Vec<ManageRecordsOperation>,
+    ) -> Result<(), ManageRecordsError> {
+        manage_records_as(&CallContext::canister_controller(0), domain, operations)
+    }
+
+    fn manage_records_as(
+        context: &CallContext,
+        domain: &str,
+        operations: Vec<ManageRecordsOperation>,
+    ) -> Result<(), ManageRecordsError> {
+        RecordsService::default().manage_records(
+            context,
+            &ManageRecordsInput {
+                domain: String::from(domain),
+                operations,
+                sign_with_tecdsa: None,
+            },
+        )
+    }
+
+    /// Returns the sorted data of the answers, the records of an RRset are not stored in the order of their data.
+    fn lookup_data(domain: &str, record_type: &str) -> Vec<String> {
+        let mut data = LookupService::default()
+            .lookup(domain, record_type)
+            .answers
+            .into_iter()
+            .map(|record| record.data)
+            .collect::<Vec<_>>();
+        data.sort();
+
+        data
+    }
+
+    #[test]
+    fn append_records() {
+        manage_records(
+            "records.tld.",
+            vec![ManageRecordsOperation::Append(vec![
+                record_input("records.tld.", "txt", 60, "first"),
+                record_input("wiki.records.tld.", "TXT", 60, "wiki"),
+            ])],
+        )
+        .unwrap();
+        manage_records(
+            "records.tld.",
+            vec![ManageRecordsOperation::Append(vec![
+                record_input("records.tld.", "TXT", 60, "second"),
+                record_input("records.tld.", "TXT", 120, "first"),
+            ])],
+        )
+        .unwrap();
+
+        let answers = LookupService::default()
+            .lookup("records.tld.", "TXT")
+            .answers;
+        assert_eq!(answers.len(), 2);
+        assert!(answers
+            .iter()
+            .any(|record| record.data == "first" && record.ttl == 120u32));
+        assert!(answers
+            .iter()
+            .any(|record| record.data == "second" && record.ttl == 60u32));
+        assert_eq!(lookup_data("wiki.records.tld.", "TXT"), vec!["wiki"]);
+    }
+
+    #[test]
+    fn override_records() {
+        manage_records(
+            "records.tld.",
+            vec![
+                ManageRecordsOperation::Append(vec![
+                    record_input("records.tld.", "TXT", 60, "first"),
+                    record_input("records.tld.", "TXT", 60, "second"),
+                    record_input("records.tld.", "CID", 60, "qoctq-giaaa-aaaaa-aaaea-cai"),
+                ]),
+                ManageRecordsOperation::Override(vec![
+                    record_input("records.tld.", "TXT", 60, "third"),
+                    record_input("records.tld.", "TXT", 60, "fourth"),
+                ]),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(lookup_data("records.tld.", "TXT"), vec!["fourth", "third"]);
+        assert_eq!(
+            lookup_data("records.tld.", "CID"),
+            vec!["qoctq-giaaa-aaaaa-aaaea-cai"]
+        );
+    }
+
+    #[test]
+    fn remove_records() {
+        manage_records(
+            "records.tld.",
+            vec![ManageRecordsOperation::Append(vec![
+                record_input("records.tld.", "TXT", 60, "first"),
+                record_input("records.tld.", "CID", 60, "qoctq-giaaa-aaaaa-aaaea-cai"),
+                record_input("wiki.records.tld.", "TXT", 60, "wiki"),
+                record_input(
+                    "wiki.records.tld.",
+                    "CID",
+                    60,
+                    "qoctq-giaaa-aaaaa-aaaea-cai",
+                ),
+            ])],
+        )
+        .unwrap();
+        manage_records(
+            "records.tld.",
+            vec![ManageRecordsOperation::Remove(vec![
+                RemoveRecordOperationInput {
+                    name: String::from("records.tld."),
+                    record_type: Some(String::from("txt")),
+                },
+                RemoveRecordOperationInput {
+                    name: String::from("wiki.records.tld."),
+                    record_type: None,
+                },
+            ])],
+        )
+        .unwrap();
+
+        assert!(lookup_data("records.tld.", "TXT").is_empty());
+        assert_eq!(
+            lookup_data("records.tld.", "CID"),
+            vec!["qoctq-giaaa-aaaaa-aaaea-cai"]
+        );
+        assert!(lookup_data("wiki.records.tld.", "ANY").is_empty());
+    }
+
+    #[test]
+    fn failed_operation_does_not_apply_any_change() {
+        let result = manage_records(
+            "records.tld.",
+            vec![
+                ManageRecordsOperation::Append(vec![record_input(
+                    "records.tld.",
+                    "TXT",
+                    60,
+                    "first",
+                )]),
+                ManageRecordsOperation::Append(vec![record_input(
+                    "wiki.other.tld.",
+                    "TXT",
+                    60,
+                    "wiki",
+                )]),
+            ],
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            ManageRecordsError::InvalidOperation {
+                index: 1,
+                error: DomainRecordInputError::InvalidRecordName {
+                    name: String::from("wiki.other.tld."),
+                    error: RecordNameError::NotInZone {
+                        name: String::from("wiki.other.tld."),
+                        apex_domain: String::from("records.tld."),
+                    },
+                },
+            }
+        );
+        assert!(lookup_data("records.tld.", "TXT").is_empty());
+    }
+
+    #[test]
+    fn invalid_record_inputs_are_rejected() {
+        let cases = vec![
+            (
+                record_input("records.tld.", "", 60, "data"),
+                DomainRecordInputError::NonEmptyRecordType,
+            ),
+            (
+                record_input("records.tld.", "ANY", 60, "data"),
+                DomainRecordInputError::ReservedRecordType {
+                    record_type: String::from("ANY"),
+                },
+            ),
+            (
+                record_input("records.tld.", "C-ID", 60, "data"),
+                DomainRecordInputError::InvalidRecordType {
+                    record_type: String::from("C-ID"),
+                },
+            ),
+            (
+                record_input("records.tld.", "HINFO", 60, "data"),
+                DomainRecordInputError::UnsupportedRecordType(
+                    RecordTypeError::UnsupportedRecordType {
+                        record_type: String::from("HINFO"),
+                    },
+                ),
+            ),
+            (
+                record_input("records.tld.", "TOOLONGRECORDTYPE", 60, "data"),
+                DomainRecordInputError::RecordTypeTooLong {
+                    record_type_length: 17,
+                    max_record_type_length: 12,
+                },
+            ),
+            (
+                record_input("records.tld.", "TXT", 60, &"a".repeat(2551)),
+                DomainRecordInputError::DataTooLong {
+                    data_length: 2551,
+                    max_data_length: 2550,
+                },
+            ),
+            (
+                record_input("records.tld.", "A", 60, "hello"),
+                DomainRecordInputError::InvalidRecordData {
+                    record_type: String::from("A"),
+                    error: RecordDataError::InvalidIpv4Address {
+                        data: String::from("hello"),
+                    },
+                },
+            ),
+            (
+                record_input("records.tld.", "cid", 60, "not-a-principal"),
+                DomainRecordInputError::InvalidRecordData {
+                    record_type: String::from("CID"),
+                    error: RecordDataError::InvalidPrincipal {
+                        data: String::from("not-a-principal"),
+                    },
+                },
+            ),
+        ];
+
+        for (record, expected_error) in cases {
+            let result = manage_records(
+                "records.tld.",
+                vec![ManageRecordsOperation::Append(vec![record])],
+            );
+
+            assert_eq!(
+                result.unwrap_err(),
+                ManageRecordsError::InvalidOperation {
+                    index: 0,
+                    error: expected_error,
+                }
+            );
+        }
+
+        let mut record = record_input("records.tld.", "TXT", 0, "data");
+        record.ttl = Nat::from(u64::MAX);
+        assert_eq!(
+            manage_records(
+                "records.tld.",
+                vec![ManageRecordsOperation::Append(vec![record])]
+            )
+            .unwrap_err(),
+            ManageRecordsError::InvalidOperation {
+                index: 0,
+                error: DomainRecordInputError::TtlTooLarge {
+                    ttl: Nat::from(u64::MAX).to_string(),
+                    max_ttl: u32::MAX,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn record_data_is_stored_in_canonical_form() {
+        manage_records(
+            "canonical.tld.",
+            vec![ManageRecordsOperation::Append(vec![
+                record_input("canonical.tld.", "AAAA", 60, "2001:0DB8:0000::0001"),
+                record_input("canonical.tld.", "MX", 60, "10   Mail.Canonical.tld."),
+                record_input("canonical.tld.", "TXT", 60, r#""hello world""#),
+            ])],
+        )
+        .unwrap();
+
+        assert_eq!(lookup_data("canonical.tld.", "AAAA"), vec!["2001:db8::1"]);
+        assert_eq!(
+            lookup_data("canonical.tld.", "MX"),
+            vec!["10 mail.canonical.tld."]
+        );
+        assert_eq!(
+            lookup_data("canonical.tld.", "TXT"),
+            vec![r#""hello world""#]
+        );
+    }
+
+    #[test]
+    fn get_records_pages_with_cursor() {
+        manage_records(
+            "pages.tld.",
+            vec![ManageRecordsOperation::Append(
+                ["a", "b", "c", "d", "e"]
+                    .iter()
+                    .map(|name| record_input(&format!("{}.pages.tld.", name), "TXT", 60, name))
+                    .collect(),
+            )],
+        )
+        .unwrap();
+        let service = RecordsService::default();
+        let first_page = service
+            .get_records(&GetRecordsInput {
+                domain: String::from("pages.tld."),
+                options: Some(PaginationOptions {
+                    start: 0,
+                    limit: 2,
+                    cursor: None,
+                }),
+            })
+            .unwrap();
+
+        // records added between two pages don't shift the next page
+        manage_records(
+            "pages.tld.",
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "0.pages.tld.",
+                "TXT",
+                60,
+                "0",
+            )])],
+        )
+        .unwrap();
+        let second_page = service
+            .get_records(&GetRecordsInput {
+                domain: String::from("pages.tld."),
+                options: Some(PaginationOptions {
+                    start: 0,
+                    limit: 4,
+                    cursor: first_page.info.next_cursor.clone(),
+                }),
+            })
+            .unwrap();
+
+        // the SOA record of the zone is part of the records
+        assert_eq!(first_page.info.total, Some(6));
+        assert_eq!(first_page.info.limit, 2);
+        assert_eq!(
+            first_page
+                .records
+                .iter()
+                .chain(second_page.records.iter())
+                .map(|record| record.record_type.as_str())
+                .collect::<Vec<_>>(),
+            vec!["SOA", "TXT", "TXT", "TXT", "TXT", "TXT"]
+        );
+        assert_eq!(
+            first_page
+                .records
+                .iter()
+                .chain(second_page.records.iter())
+                .skip(1)
+                .map(|record| record.data.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c", "d", "e"]
+        );
+        assert_eq!(second_page.info.total, None);
+        assert_eq!(second_page.info.next_cursor, None);
+    }
+
+    #[test]
+    fn get_records_uses_default_pagination() {
+        manage_records(
+            "pages.tld.",
+            vec![ManageRecordsOperation::Append(
+                (0..150)
+                    .map(|index| record_input("pages.tld.", "TXT", 60, &index.to_string()))
+                    .collect(),
+            )],
+        )
+        .unwrap();
+
+        let page = RecordsService::default()
+            .get_records(&GetRecordsInput {
+                domain: String::from("pages.tld."),
+                options: None,
+            })
+            .unwrap();
+
+        assert_eq!(page.records.len(), 100);
+        assert_eq!(page.info.start, 0);
+        assert_eq!(page.info.total, Some(151));
+        assert!(page.info.next_cursor.is_some());
+    }
+
+    #[test]
+    fn get_records_rejects_invalid_cursor() {
+        let result = RecordsService::default().get_records(&GetRecordsInput {
+            domain: String::from("pages.tld."),
+            options: Some(PaginationOptions {
+                cursor: Some(String::from("not-a-cursor")),
+                ..Default::default()
+            }),
+        });
+
+        assert_eq!(
+            result.unwrap_err(),
+            GetRecordsError::InvalidPagination(PaginationError::InvalidCursor {
+                cursor: String::from("not-a-cursor"),
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_domain_is_rejected() {
+        assert!(matches!(
+            manage_records("records", vec![]),
+            Err(ManageRecordsError::InvalidDomain(_))
+        ));
+    }
+
+    #[rstest]
+    #[case::registrant(RegistrationControllerRole::Registrant, true)]
+    #[case::registrar(RegistrationControllerRole::Registrar, true)]
+    #[case::technical(RegistrationControllerRole::Technical, true)]
+    #[case::administrative(RegistrationControllerRole::Administrative, false)]
+    fn zone_controller_roles_are_authorized(
+        #[case] role: RegistrationControllerRole,
+        #[case] is_authorized: bool,
+    ) {
+        let controller_id = Principal::from_slice(&[1; 29]);
+        ZonesService::default()
+            .create_zone(
+                &CallContext::canister_controller(0),
+                &CreateZoneInput {
+                    domain: String::from("owned.tld."),
+                    controllers: vec![RegistrationController::new(controller_id, vec![role])],
+                },
+            )
+            .unwrap();
+
+        let result = manage_records_as(
+            &CallContext::new(controller_id, false, 0),
+            "owned.tld.",
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "owned.tld.",
+                "TXT",
+                60,
+                "owned",
+            )])],
+        );
+
+        if is_authorized {
+            assert!(result.is_ok());
+            assert_eq!(lookup_data("owned.tld.", "TXT"), vec!["owned"]);
+        } else {
+            assert_eq!(
+                result.unwrap_err(),
+                ManageRecordsError::Unauthorized {
+                    caller: controller_id.to_text(),
+                    domain: String::from("owned.tld."),
+                }
+            );
+            assert!(lookup_data("owned.tld.", "TXT").is_empty());
+        }
+    }
+
+    #[test]
+    fn unknown_caller_is_unauthorized() {
+        manage_records("owned.tld.", vec![]).unwrap();
+
+        assert_eq!(
+            manage_records_as(
+                &CallContext::new(Principal::anonymous(), false, 0),
+                "owned.tld.",
+                vec![ManageRecordsOperation::Remove(vec![
+                    RemoveRecordOperationInput {
+                        name: String::from("owned.tld."),
+                        record_type: None,
+                    }
+                ])],
+            )
+            .unwrap_err(),
+            ManageRecordsError::Unauthorized {
+                caller: Principal::anonymous().to_text(),
+                domain: String::from("owned.tld."),
+            }
+        );
+        assert_eq!(
+            manage_records_as(
+                &CallContext::new(Principal::anonymous(), false, 0),
+                "missing.tld.",
+                vec![],
+            )
+            .unwrap_err(),
+            ManageRecordsError::Unauthorized {
+                caller: Principal::anonymous().to_text(),
+                domain: String::from("missing.tld."),
+            }
+        );
+    }
+
+    #[test]
+    fn manage_records_certifies_changed_rrsets() {
+        let certified_records = CertifiedRecordsRepository::default();
+        manage_records(
+            "certified.tld.",
+            vec![ManageRecordsOperation::Append(vec![
+                record_input("certified.tld.", "TXT", 60, "first"),
+                record_input("wiki.certified.tld.", "TXT", 60, "wiki"),
+            ])],
+        )
+        .unwrap();
+        let certified_data = certified_records.certified_data();
+
+        manage_records(
+            "certified.tld.",
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "certified.tld.",
+                "TXT",
+                60,
+                "second",
+            )])],
+        )
+        .unwrap();
+
+        assert_ne!(certified_records.certified_data(), certified_data);
+        assert_eq!(
+            certified_records.get("certified.tld.", "TXT"),
+            Some(rrset_hash(
+                &LookupService::default()
+                    .lookup("certified.tld.", "TXT")
+                    .answers
+            ))
+        );
+
+        manage_records(
+            "certified.tld.",
+            vec![ManageRecordsOperation::Remove(vec![
+                RemoveRecordOperationInput {
+                    name: String::from("wiki.certified.tld."),
+                    record_type: None,
+                },
+            ])],
+        )
+        .unwrap();
+
+        assert_eq!(
+            certified_records.rrsets(),
+            vec![
+                (String::from("certified.tld."), String::from("SOA")),
+                (String::from("certified.tld."), String::from("TXT"))
+            ]
+        );
+    }
+
+    fn soa_serial(domain: &str) -> Option<u32> {
+        SoaService::default().serial(&ZoneApexDomain::new(String::from(domain)).unwrap())
+    }
+
+    #[test]
+    fn manage_records_increments_soa_serial() {
+        manage_records(
+            "serial.tld.",
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "serial.tld.",
+                "TXT",
+                60,
+                "first",
+            )])],
+        )
+        .unwrap();
+        assert_eq!(soa_serial("serial.tld."), Some(1));
+
+        manage_records(
+            "serial.tld.",
+            vec![ManageRecordsOperation::Remove(vec![
+                RemoveRecordOperationInput {
+                    name: String::from("serial.tld."),
+                    record_type: None,
+                },
+            ])],
+        )
+        .unwrap();
+        assert_eq!(soa_serial("serial.tld."), Some(2));
+        // removing all the records of the apex keeps the SOA record
+        assert_eq!(lookup_data("serial.tld.", "ANY").len(), 1);
+
+        let result = manage_records(
+            "serial.tld.",
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "serial.tld.",
+                "A",
+                60,
+                "invalid",
+            )])],
+        );
+        assert!(result.is_err());
+        assert_eq!(soa_serial("serial.tld."), Some(2));
+    }
+
+    #[test]
+    fn soa_record_can_be_replaced_with_managed_serial() {
+        manage_records("soa.tld.", vec![]).unwrap();
+        manage_records(
+            "soa.tld.",
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "soa.tld.",
+                "SOA",
+                300,
+                "ns1.soa.tld. admin.soa.tld. 100 600 300 86400 60",
+            )])],
+        )
+        .unwrap();
+
+        let records = LookupService::default().lookup("soa.tld.", "SOA").answers;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].ttl, 300u32);
+        assert_eq!(
+            records[0].data,
+            "ns1.soa.tld. admin.soa.tld. 2 600 300 86400 60"
+        );
+    }
+
+    #[test]
+    fn soa_record_inputs_are_rejected() {
+        assert_eq!(
+            manage_records(
+                "soa.tld.",
+                vec![ManageRecordsOperation::Append(vec![record_input(
+                    "wiki.soa.tld.",
+                    "SOA",
+                    60,
+                    "ns1.soa.tld. admin.soa.tld. 1 600 300 86400 60",
+                )])],
+            )
+            .unwrap_err(),
+            ManageRecordsError::InvalidOperation {
+                index: 0,
+                error: DomainRecordInputError::SoaRecordNotAtApex {
+                    name: String::from("wiki.soa.tld."),
+                },
+            }
+        );
+        assert_eq!(
+            manage_records(
+                "soa.tld.",
+                vec![ManageRecordsOperation::Remove(vec![
+                    RemoveRecordOperationInput {
+                        name: String::from("soa.tld."),
+                        record_type: Some(String::from("soa")),
+                    }
+                ])],
+            )
+            .unwrap_err(),
+            ManageRecordsError::InvalidOperation {
+                index: 0,
+                error: DomainRecordInputError::RequiredRecordType {
+                    record_type: String::from("SOA"),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn cname_records_cannot_coexist_with_other_records() {
+        manage_records(
+            "alias.tld.",
+            vec![ManageRecordsOperation::Append(vec![
+                record_input("www.alias.tld.", "CNAME", 60, "alias.tld."),
+                record_input("wiki.alias.tld.", "TXT", 60, "wiki"),
+            ])],
+        )
+        .unwrap();
+
+        for operations in [
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "www.alias.tld.",
+                "TXT",
+                60,
+                "www",
+            )])],
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "www.alias.tld.",
+                "CNAME",
+                60,
+                "wiki.alias.tld.",
+            )])],
+            vec![ManageRecordsOperation::Override(vec![record_input(
+                "wiki.alias.tld.",
+                "CNAME",
+                60,
+                "alias.tld.",
+            )])],
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "alias.tld.",
+                "CNAME",
+                60,
+                "other.tld.",
+            )])],
+        ] {
+            let name = match &operations[0] {
+                ManageRecordsOperation::Append(records)
+                | ManageRecordsOperation::Override(records) => records[0].name.clone(),
+                ManageRecordsOperation::Remove(_) => unreachable!(),
+            };
+
+            assert_eq!(
+                manage_records("alias.tld.", operations).unwrap_err(),
+                ManageRecordsError::CnameConflict { name }
+            );
+        }
+
+        // the conflict is resolved by a later operation of the same batch
+        manage_records(
+            "alias.tld.",
+            vec![
+                ManageRecordsOperation::Override(vec![record_input(
+                    "wiki.alias.tld.",
+                    "CNAME",
+                    60,
+                    "alias.tld.",
+                )]),
+                ManageRecordsOperation::Remove(vec![RemoveRecordOperationInput {
+                    name: String::from("wiki.alias.tld."),
+                    record_type: Some(String::from("TXT")),
+                }]),
+                ManageRecordsOperation::Override(vec![record_input(
+                    "www.alias.tld.",
+                    "CNAME",
+                    60,
+                    "wiki.alias.tld.",
+                )]),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(lookup_data("wiki.alias.tld.", "ANY"), vec!["alias.tld."]);
+        assert_eq!(
+            lookup_data("www.alias.tld.", "CNAME"),
+            vec!["wiki.alias.tld."]
+        );
+    }
+
+    #[test]
+    fn delegating_a_name_certifies_the_names_below_it() {
+        manage_records(
+            "delegated.tld.",
+            vec![ManageRecordsOperation::Append(vec![
+                record_input("wiki.team.delegated.tld.", "TXT", 60, "wiki"),
+                record_input("www.delegated.tld.", "TXT", 60, "www"),
+            ])],
+        )
+        .unwrap();
+        let certified_records = CertifiedRecordsRepository::default();
+        let wiki_rrset = (
+            String::from("wiki.team.delegated.tld."),
+            String::from("TXT"),
+        );
+        assert!(certified_records.rrsets().contains(&wiki_rrset));
+
+        manage_records(
+            "delegated.tld.",
+            vec![ManageRecordsOperation::Append(vec![record_input(
+                "team.delegated.tld.",
+                "NC",
+                60,
+                "qoctq-giaaa-aaaaa-aaaea-cai",
+            )])],
+        )
+        .unwrap();
+
+        assert!(!certified_records.rrsets().contains(&wiki_rrset));
+        assert!(certified_records
+            .rrsets()
+            .contains(&(String::from("team.delegated.tld."), String::from("NC"))));
+        assert!(lookup_data("wiki.team.delegated.tld.", "TXT").is_empty());
+
+        manage_records(
+            "delegated.tld.",
+            vec![ManageRecordsOperation::Remove(vec![
+                RemoveRecordOperationInput {
+                    name: String::from("team.delegated.tld."),
+                    record_type: Some(String::from("NC")),
+                },
+            ])],
+        )
+        .unwrap();
+
+        assert!(certified_records.rrsets().contains(&wiki_rrset));
+        assert_eq!(lookup_data("wiki.team.delegated.tld.", "TXT"), vec!["wiki"]);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{
+    api::DomainRecord as DomainRecordResult,
+    common::{
+        DEFAULT_SOA_EXPIRE, DEFAULT_SOA_MAILBOX, DEFAULT_SOA_MINIMUM, DEFAULT_SOA_REFRESH,
+        DEFAULT_SOA_RETRY, DEFAULT_SOA_TTL,
+    },
+    repositories::{DomainZoneRepository, Repository, RepositorySearch},
+    types::{
+        DomainRecord, DomainRecordInput, DomainRecordTypes, DomainZone, DomainZoneEntry,
+        DomainZoneEntryInput, DomainZoneInput, RecordData, RecordName, ZoneApexDomain,
+    },
+};
+use std::ops::Deref;
+
+/// A service that maintains the SOA record that every zone has at its apex.
+///
+/// The serial of the SOA record is the version of the zone, it is increased on every change to the records of the
+/// zone so that caches and secondary name servers can cheaply detect that the zone changed.
+pub struct SoaService {
+    zone_repository: DomainZoneRepository,
+}
+
+impl SoaService {
+    pub fn new() -> Self {
+        Self {
+            zone_repository: DomainZoneRepository::default(),
+        }
+    }
+
+    /// Creates the SOA record of a new zone with the default values and returns the inserted entry.
+    pub fn create_soa(&self, apex_domain: &ZoneApexDomain) -> DomainZoneEntry {
+        let entry = DomainZoneEntry::new(
+            DomainZone::new(apex_domain.clone()),
+            Self::default_soa_record(apex_domain),
+        );
+        self.zone_repository.insert(entry.clone());
+
+        entry
+    }
+
+    /// Returns the SOA record of the zone, if the zone has one.
+    pub fn find_soa(&self, apex_domain: &ZoneApexDomain) -> Option<DomainZoneEntry> {
+        self.zone_repository
+            .search(&DomainZoneEntryInput::new(
+                DomainZoneInput {
+                    name: Some(apex_domain.deref().to_string()),
+                },
+                DomainRecordInput {
+                    name: Some(RecordName::default().deref().to_string()),
+                    record_type: Some(DomainRecordTypes::SOA.to_string()),
+                    ..Default::default()
+                },
+            ))
+            .into_iter()
+            .next()
+    }
+
+    /// Returns the serial of the SOA record of the zone, if the zone has one.
+    pub fn serial(&self, apex_domain: &ZoneApexDomain) -> Option<u32> {
+        self.find_soa(apex_domain)
+            .and_then(|entry| match Self::soa_data(entry.record()) {
+                Some(RecordData::SOA { serial, .. }) => Some(serial),
+                _ => None,
+            })
+    }
+
+    /// Sets the serial of the SOA record of the zone to the next version after the previous serial, the SOA
+    /// record is created with the default values if the zone does not have one.
+    ///
+    /// Returns the entries that were removed and inserted.
+    pub fn increment_serial(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        previous_serial: Option<u32>,
+    ) -> Vec<DomainZoneEntry> {
+        let mut changed_entries = Vec::new();
+        let mut record = match self.find_soa(apex_domain) {
+            Some(entry) => {
+                self.zone_repository.remove(&entry);
+                changed_entries.push(entry.clone());
+                entry.record().clone()
+            }
+            None => Self::default_soa_record(apex_domain),
+        };
+
+        if let Some(RecordData::SOA {
+            mname,
+            rname,
+            refresh,
+            retry,
+            expire,
+            minimum,
+            ..
+        }) = Self::soa_data(&record)
+        {
+            // serials use sequence space arithmetic, hence they wrap around after the max value
+            let serial = previous_serial.map_or(1, |serial| serial.wrapping_add(1));
+            record.data = RecordData::SOA {
+                mname,
+                rname,
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum,
+            }
+            .to_string();
+        }
+
+        let entry = DomainZoneEntry::new(DomainZone::new(apex_domain.clone()), record);
+        self.zone_repository.insert(entry.clone());
+        changed_entries.push(entry);
+
+        changed_entries
+    }
+
+    /// Returns the SOA record of the zone to include in the authorities of negative lookups, its ttl is the
+    /// minimum of the record ttl and the negative ttl of the zone as defined in RFC 2308.
+    pub fn negative_answer_authority(
+        &self,
+        apex_domain: &ZoneApexDomain,
+    ) -> Option<DomainRecordResult> {
+        let entry = self.find_soa(apex_domain)?;
+        let mut record = DomainRecordResult::from(&entry);
+        if let Some(RecordData::SOA { minimum, .. }) = Self::soa_data(entry.record()) {
+            record.ttl = entry.record().ttl.min(minimum).into();
+        }
+
+        Some(record)
+    }
+
+    fn soa_data(record: &DomainRecord) -> Option<RecordData> {
+        RecordData::parse(DomainRecordTypes::SOA, &record.data).ok()
+    }
+
+    fn default_soa_record(apex_domain: &ZoneApexDomain) -> DomainRecord {
+        let rname = ZoneApexDomain::new(format!("{}.{}", DEFAULT_SOA_MAILBOX, apex_domain.deref()))
+            .unwrap_or_else(|_| apex_domain.clone());
+
+        DomainRecord::new(
+            RecordName::default(),
+            DomainRecordTypes::SOA.to_string(),
+            DEFAULT_SOA_TTL,
+            RecordData::SOA {
+                mname: apex_domain.clone(),
+                rname,
+                serial: 1,
+                refresh: DEFAULT_SOA_REFRESH,
+                retry: DEFAULT_SOA_RETRY,
+                expire: DEFAULT_SOA_EXPIRE,
+                minimum: DEFAULT_SOA_MINIMUM,
+            }
+            .to_string(),
+        )
+    }
+}
+
+impl Default for SoaService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
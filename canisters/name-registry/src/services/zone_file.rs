@@ -0,0 +1,318 @@
+use crate::{
+    api::{
+        DomainRecord as DomainRecordResult, ExportZoneInput, ExportZoneResult, ImportZoneInput,
+        ManageRecordsInput, ManageRecordsOperation, RemoveRecordOperationInput,
+    },
+    errors::{ExportZoneError, ImportZoneError},
+    repositories::{DomainZoneMetadataRepository, DomainZoneRepository},
+    services::RecordsService,
+    types::{CallContext, DomainRecordTypes, DomainZone, RecordName, ZoneApexDomain},
+    utils::{format_zone_file, parse_zone_file},
+};
+use std::{collections::BTreeSet, ops::Deref};
+
+/// A service that exports and imports the records of the zones as RFC 1035 master files.
+pub struct ZoneFileService {
+    zone_repository: DomainZoneRepository,
+    zone_metadata_repository: DomainZoneMetadataRepository,
+    records_service: RecordsService,
+}
+
+impl ZoneFileService {
+    pub fn new() -> Self {
+        Self {
+            zone_repository: DomainZoneRepository::default(),
+            zone_metadata_repository: DomainZoneMetadataRepository::default(),
+            records_service: RecordsService::default(),
+        }
+    }
+
+    /// Returns the records of the zone as a zone file, the records are public hence anyone can export them.
+    pub fn export_zone(
+        &self,
+        input: &ExportZoneInput,
+    ) -> Result<ExportZoneResult, ExportZoneError> {
+        let apex_domain = ZoneApexDomain::new(input.domain.to_ascii_lowercase())
+            .map_err(ExportZoneError::InvalidDomain)?;
+        if self.zone_metadata_repository.get(&apex_domain).is_none() {
+            return Err(ExportZoneError::ZoneNotFound {
+                domain: apex_domain.deref().to_string(),
+            });
+        }
+
+        let records = self
+            .zone_repository
+            .search_subtree(&apex_domain, &RecordName::default())
+            .into_iter()
+            .map(|entry| entry.record().clone())
+            .collect::<Vec<_>>();
+
+        Ok(ExportZoneResult {
+            zone_file: format_zone_file(&DomainZone::new(apex_domain), &records),
+        })
+    }
+
+    /// Replaces the records of the zone with the records of the zone file.
+    ///
+    /// The RRsets of the file override the ones of the zone and the other RRsets of the zone are removed, except
+    /// for the SOA record which is only replaced if the file has one. The changes are applied with `manage_records`,
+    /// hence the same authorization applies, the serial of the SOA record is incremented, the changes are recorded
+    /// in the history of the zone and either all of them or none are applied.
+    pub fn import_zone(
+        &self,
+        context: &CallContext,
+        input: &ImportZoneInput,
+    ) -> Result<(), ImportZoneError> {
+        let apex_domain = ZoneApexDomain::new(input.domain.to_ascii_lowercase())
+            .map_err(ImportZoneError::InvalidDomain)?;
+        let records = parse_zone_file(&apex_domain, &input.zone_file)
+            .map_err(ImportZoneError::InvalidZoneFile)?;
+
+        let imported_rrsets = records
+            .iter()
+            .map(|record| (record.name.clone(), record.record_type.clone()))
+            .collect::<BTreeSet<_>>();
+        let removed_rrsets = self
+            .zone_repository
+            .search_subtree(&apex_domain, &RecordName::default())
+            .iter()
+            .map(DomainRecordResult::from)
+            .filter(|record| record.record_type != DomainRecordTypes::SOA.to_string())
+            .map(|record| (record.name, record.record_type))
+            .filter(|rrset| !imported_rrsets.contains(rrset))
+            .collect::<BTreeSet<_>>();
+
+        let mut operations = Vec::new();
+        if !removed_rrsets.is_empty() {
+            operations.push(ManageRecordsOperation::Remove(
+                removed_rrsets
+                    .into_iter()
+                    .map(|(name, record_type)| RemoveRecordOperationInput {
+                        name,
+                        record_type: Some(record_type),
+                    })
+                    .collect(),
+            ));
+        }
+        if !records.is_empty() {
+            operations.push(ManageRecordsOperation::Override(records));
+        }
+
+        self.records_service
+            .manage_records(
+                context,
+                &ManageRecordsInput {
+                    domain: apex_domain.deref().to_string(),
+                    operations,
+                    sign_with_tecdsa: None,
+                },
+            )
+            .map_err(ImportZoneError::ManageRecords)
+    }
+}
+
+impl Default for ZoneFileService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{CreateZoneInput, DomainRecordInput, GetRecordsInput},
+        errors::{ManageRecordsError, ZoneFileError},
+        services::ZonesService,
+        types::{RegistrationController, RegistrationControllerRole},
+    };
+    use candid::{Nat, Principal};
+
+    fn import_zone(
+        context: &CallContext,
+        domain: &str,
+        zone_file: &str,
+    ) -> Result<(), ImportZoneError> {
+        ZoneFileService::default().import_zone(
+            context,
+            &ImportZoneInput {
+                domain: domain.to_string(),
+                zone_file: zone_file.to_string(),
+            },
+        )
+    }
+
+    fn records(domain: &str) -> Vec<(String, String, String)> {
+        RecordsService::default()
+            .get_records(&GetRecordsInput {
+                domain: domain.to_string(),
+                options: None,
+            })
+            .unwrap()
+            .records
+            .into_iter()
+            .map(|record| (record.name, record.record_type, record.data))
+            .collect()
+    }
+
+    #[test]
+    fn import_zone_replaces_records() {
+        let domain = "imported.tld.";
+        RecordsService::default()
+            .manage_records(
+                &CallContext::canister_controller(0),
+                &ManageRecordsInput {
+                    domain: domain.to_string(),
+                    operations: vec![ManageRecordsOperation::Append(vec![
+                        DomainRecordInput {
+                            name: String::from("old.imported.tld."),
+                            record_type: String::from("TXT"),
+                            ttl: Nat::from(60u32),
+                            data: String::from("stale"),
+                        },
+                        DomainRecordInput {
+                            name: String::from("www.imported.tld."),
+                            record_type: String::from("A"),
+                            ttl: Nat::from(60u32),
+                            data: String::from("192.0.2.1"),
+                        },
+                    ])],
+                    sign_with_tecdsa: None,
+                },
+            )
+            .unwrap();
+
+        import_zone(
+            &CallContext::canister_controller(0),
+            domain,
+            "$TTL 300\nwww A 192.0.2.2\n    A 192.0.2.3\nwiki CID qoctq-giaaa-aaaaa-aaaea-cai\n",
+        )
+        .unwrap();
+
+        let records = records(domain);
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].1, "SOA");
+        assert!(records[0].2.contains(" 2 "));
+        assert_eq!(
+            records[1..],
+            [
+                (
+                    String::from("wiki.imported.tld."),
+                    String::from("CID"),
+                    String::from("qoctq-giaaa-aaaaa-aaaea-cai")
+                ),
+                (
+                    String::from("www.imported.tld."),
+                    String::from("A"),
+                    String::from("192.0.2.2")
+                ),
+                (
+                    String::from("www.imported.tld."),
+                    String::from("A"),
+                    String::from("192.0.2.3")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn export_zone_round_trip() {
+        let domain = "exported.tld.";
+        let zone_file = "$ORIGIN exported.tld.\n\
+                         @ 3600 IN SOA ns1 hostmaster 1 7200 3600 1209600 300\n\
+                         @ 600 IN NS ns1\n\
+                         ns1 600 IN A 192.0.2.1\n\
+                         txt.eu 60 IN TXT \"hello world\" a;comment\n";
+        import_zone(&CallContext::canister_controller(0), domain, zone_file).unwrap();
+
+        let exported = ZoneFileService::default()
+            .export_zone(&ExportZoneInput {
+                domain: domain.to_string(),
+            })
+            .unwrap()
+            .zone_file;
+
+        assert_eq!(
+            exported,
+            "$ORIGIN exported.tld.\n\
+             $TTL 3600\n\
+             @\t3600\tIN\tSOA\tns1.exported.tld. hostmaster.exported.tld. 1 7200 3600 1209600 300\n\
+             @\t600\tIN\tNS\tns1.exported.tld.\n\
+             txt.eu\t60\tIN\tTXT\t\"hello world\" a\n\
+             ns1\t600\tIN\tA\t192.0.2.1\n"
+        );
+
+        // only the serial of the SOA record changes when the exported zone is imported again
+        let records_before = records(domain);
+        import_zone(&CallContext::canister_controller(0), domain, &exported).unwrap();
+        let records_after = records(domain);
+        assert_eq!(
+            records_before
+                .iter()
+                .filter(|record| record.1 != "SOA")
+                .collect::<Vec<_>>(),
+            records_after
+                .iter()
+                .filter(|record| record.1 != "SOA")
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn export_unknown_zone_fails() {
+        assert_eq!(
+            ZoneFileService::default().export_zone(&ExportZoneInput {
+                domain: String::from("unknown.tld."),
+            }),
+            Err(ExportZoneError::ZoneNotFound {
+                domain: String::from("unknown.tld.")
+            })
+        );
+    }
+
+    #[test]
+    fn import_zone_requires_authorization_and_valid_file() {
+        let domain = "guarded.tld.";
+        let administrative = Principal::from_slice(&[2; 29]);
+        ZonesService::default()
+            .create_zone(
+                &CallContext::canister_controller(0),
+                &CreateZoneInput {
+                    domain: domain.to_string(),
+                    controllers: vec![RegistrationController {
+                        controller_id: administrative,
+                        roles: vec![RegistrationControllerRole::Administrative],
+                    }],
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            import_zone(
+                &CallContext::new(administrative, false, 0),
+                domain,
+                "www A 192.0.2.1"
+            ),
+            Err(ImportZoneError::ManageRecords(
+                ManageRecordsError::Unauthorized {
+                    caller: administrative.to_text(),
+                    domain: domain.to_string(),
+                }
+            ))
+        );
+        assert_eq!(
+            import_zone(
+                &CallContext::canister_controller(0),
+                domain,
+                "$INCLUDE other.zone"
+            ),
+            Err(ImportZoneError::InvalidZoneFile(
+                ZoneFileError::UnsupportedDirective {
+                    line: 1,
+                    directive: String::from("$INCLUDE"),
+                }
+            ))
+        );
+        assert_eq!(records(domain).len(), 1);
+    }
+}
@@ -0,0 +1,454 @@
+use crate::{
+    api::{
+        DomainRecord as DomainRecordResult, GetZoneHistoryInput, GetZoneHistoryResult,
+        PaginationInfo, ZoneHistoryChange,
+    },
+    common::MAX_ZONE_HISTORY_LENGTH,
+    errors::{GetZoneHistoryError, LookupAtError, PaginationError},
+    repositories::{DomainZoneMetadataRepository, Repository, ZoneHistoryRepository},
+    types::{
+        CallContext, DomainRecord, RecordName, ZoneApexDomain, ZoneHistoryEntry,
+        ZoneHistoryOperation,
+    },
+    utils::{decode_cursor, encode_cursor},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Deref,
+};
+
+/// A service that keeps the history of the changes made to the RRsets of the zones, which tells what a name
+/// resolved to at any point in time covered by the history.
+///
+/// The history of each zone is bounded, the oldest changes are removed once a zone has more than
+/// [MAX_ZONE_HISTORY_LENGTH] changes.
+pub struct ZoneHistoryService {
+    zone_history_repository: ZoneHistoryRepository,
+    zone_metadata_repository: DomainZoneMetadataRepository,
+}
+
+impl ZoneHistoryService {
+    pub fn new() -> Self {
+        Self {
+            zone_history_repository: ZoneHistoryRepository::default(),
+            zone_metadata_repository: DomainZoneMetadataRepository::default(),
+        }
+    }
+
+    /// Records the changes that the operation made to the RRsets of the zone, given the records of the names it
+    /// changed before and after it was applied. RRsets that are the same before and after are not recorded.
+    pub fn record_changes(
+        &self,
+        context: &CallContext,
+        apex_domain: &ZoneApexDomain,
+        operation: ZoneHistoryOperation,
+        before: &[DomainRecord],
+        after: &[DomainRecord],
+    ) {
+        let (before, after) = (Self::rrsets(before), Self::rrsets(after));
+        let mut sequence = self
+            .zone_history_repository
+            .last_sequence(apex_domain)
+            .map_or(0, |sequence| sequence + 1);
+
+        for rrset in before.keys().chain(after.keys()).collect::<BTreeSet<_>>() {
+            let before = before.get(rrset).cloned().unwrap_or_default();
+            let after = after.get(rrset).cloned().unwrap_or_default();
+            if before == after {
+                continue;
+            }
+
+            self.zone_history_repository.insert(ZoneHistoryEntry {
+                zone: apex_domain.clone(),
+                sequence,
+                timestamp: context.time,
+                caller: context.caller,
+                operation,
+                name: rrset.0.clone(),
+                record_type: rrset.1.clone(),
+                before,
+                after,
+            });
+            sequence += 1;
+        }
+
+        if sequence > MAX_ZONE_HISTORY_LENGTH {
+            self.zone_history_repository
+                .remove_before(apex_domain, sequence - MAX_ZONE_HISTORY_LENGTH);
+        }
+    }
+
+    /// Returns a page of the changes of the zone in the order they were made.
+    ///
+    /// Only canister controllers and controllers of the zone can get its history.
+    pub fn get_zone_history(
+        &self,
+        context: &CallContext,
+        input: &GetZoneHistoryInput,
+    ) -> Result<GetZoneHistoryResult, GetZoneHistoryError> {
+        let apex_domain = ZoneApexDomain::new(input.domain.to_ascii_lowercase())
+            .map_err(GetZoneHistoryError::InvalidDomain)?;
+        let is_authorized = context.is_canister_controller
+            || self
+                .zone_metadata_repository
+                .get(&apex_domain)
+                .is_some_and(|metadata| metadata.is_controller(&context.caller));
+
+        if !is_authorized {
+            return Err(GetZoneHistoryError::Unauthorized {
+                caller: context.caller.to_text(),
+                domain: apex_domain.deref().to_string(),
+            });
+        }
+
+        let options = input.options.clone().unwrap_or_default();
+        let after = match &options.cursor {
+            Some(cursor) => Some(
+                Self::decode_sequence_cursor(cursor)
+                    .map_err(GetZoneHistoryError::InvalidPagination)?,
+            ),
+            None => None,
+        };
+
+        let limit = options.effective_limit() as usize;
+        // one more change than requested is loaded to know if there is a next page
+        let mut entries = self.zone_history_repository.find_after(
+            &apex_domain,
+            after,
+            |_| true,
+            options.start as usize,
+            limit + 1,
+        );
+        let has_next_page = entries.len() > limit;
+        entries.truncate(limit);
+
+        let next_cursor = match entries.last() {
+            Some(entry) if has_next_page => Some(encode_cursor(&entry.sequence.to_be_bytes())),
+            _ => None,
+        };
+
+        Ok(GetZoneHistoryResult {
+            items: entries.iter().map(ZoneHistoryChange::from).collect(),
+            info: PaginationInfo {
+                total: Some(self.zone_history_repository.count(&apex_domain)),
+                limit: entries.len() as u64,
+                start: options.start,
+                next_cursor,
+            },
+        })
+    }
+
+    /// Returns the RRsets of the name that changed after the given time as they were at that time, indexed by
+    /// their record type and optionally filtered by record type. The RRsets that did not change since are not
+    /// included.
+    ///
+    /// Fails if the history of the zone no longer goes back to the given time.
+    pub fn changed_rrsets_at(
+        &self,
+        apex_domain: &ZoneApexDomain,
+        record_name: &RecordName,
+        record_type: Option<&str>,
+        timestamp: u64,
+    ) -> Result<BTreeMap<String, Vec<DomainRecordResult>>, LookupAtError> {
+        let oldest_entry = self
+            .zone_history_repository
+            .find_after(apex_domain, None, |_| true, 0, 1)
+            .into_iter()
+            .next();
+        if let Some(oldest_entry) =
+            oldest_entry.filter(|entry| entry.sequence > 0 && entry.timestamp > timestamp)
+        {
+            return Err(LookupAtError::HistoryUnavailable {
+                domain: apex_domain.deref().to_string(),
+                timestamp,
+                oldest_timestamp: oldest_entry.timestamp,
+            });
+        }
+
+        let mut rrsets = BTreeMap::new();
+        for entry in self.zone_history_repository.find_after(
+            apex_domain,
+            None,
+            |entry| {
+                entry.timestamp > timestamp
+                    && &entry.name == record_name
+                    && record_type.map_or(true, |record_type| entry.record_type == record_type)
+            },
+            0,
+            usize::MAX,
+        ) {
+            // the oldest change after the given time has the RRset as it was at that time
+            rrsets
+                .entry(entry.record_type.clone())
+                .or_insert_with(|| ZoneHistoryChange::from(&entry).before);
+        }
+
+        Ok(rrsets)
+    }
+
+    /// Groups the records by their name and record type.
+    fn rrsets(records: &[DomainRecord]) -> BTreeMap<(RecordName, String), Vec<DomainRecord>> {
+        let mut rrsets = BTreeMap::<_, Vec<_>>::new();
+        for record in records {
+            rrsets
+                .entry((record.name.clone(), record.record_type.clone()))
+                .or_default()
+                .push(record.clone());
+        }
+
+        rrsets
+    }
+
+    fn decode_sequence_cursor(cursor: &str) -> Result<u64, PaginationError> {
+        decode_cursor(cursor)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_be_bytes)
+            .ok_or_else(|| PaginationError::InvalidCursor {
+                cursor: cursor.to_string(),
+            })
+    }
+}
+
+impl Default for ZoneHistoryService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            DomainRecordInput, ManageRecordOperationType, ManageRecordsInput,
+            ManageRecordsOperation, PaginationOptions,
+        },
+        services::{LookupService, RecordsService},
+    };
+    use candid::{Nat, Principal};
+
+    fn controller_at(time: u64) -> CallContext {
+        CallContext::new(Principal::from_slice(&[9; 29]), true, time)
+    }
+
+    fn manage_txt_record(
+        time: u64,
+        name: &str,
+        operation: fn(Vec<DomainRecordInput>) -> ManageRecordsOperation,
+        data: &str,
+    ) {
+        let domain = name.split_once('.').map_or(name, |(_, domain)| domain);
+        RecordsService::default()
+            .manage_records(
+                &controller_at(time),
+                &ManageRecordsInput {
+                    domain: String::from(domain),
+                    operations: vec![operation(vec![DomainRecordInput {
+                        name: String::from(name),
+                        record_type: String::from("TXT"),
+                        ttl: Nat::from(60u32),
+                        data: String::from(data),
+                    }])],
+                    sign_with_tecdsa: None,
+                },
+            )
+            .unwrap();
+    }
+
+    fn get_zone_history(
+        context: &CallContext,
+        domain: &str,
+        cursor: Option<String>,
+    ) -> Result<GetZoneHistoryResult, GetZoneHistoryError> {
+        ZoneHistoryService::default().get_zone_history(
+            context,
+            &GetZoneHistoryInput {
+                domain: String::from(domain),
+                options: Some(PaginationOptions {
+                    start: 0,
+                    limit: 2,
+                    cursor,
+                }),
+            },
+        )
+    }
+
+    #[test]
+    fn manage_records_keeps_zone_history() {
+        manage_txt_record(
+            100,
+            "wiki.history.tld.",
+            ManageRecordsOperation::Append,
+            "first",
+        );
+        manage_txt_record(
+            200,
+            "wiki.history.tld.",
+            ManageRecordsOperation::Override,
+            "second",
+        );
+
+        let first_page = get_zone_history(&controller_at(0), "history.tld.", None).unwrap();
+        let second_page = get_zone_history(
+            &controller_at(0),
+            "history.tld.",
+            first_page.info.next_cursor.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(first_page.info.total, Some(4));
+        assert_eq!(second_page.info.next_cursor, None);
+        let changes = first_page
+            .items
+            .iter()
+            .chain(second_page.items.iter())
+            .map(|change| {
+                (
+                    change.sequence,
+                    change.timestamp,
+                    change.operation,
+                    change.name.as_str(),
+                    change.record_type.as_str(),
+                    change.before.len(),
+                    change.after.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    0,
+                    100,
+                    ManageRecordOperationType::Append,
+                    "wiki.history.tld.",
+                    "TXT",
+                    0,
+                    1
+                ),
+                (
+                    1,
+                    100,
+                    ManageRecordOperationType::Override,
+                    "history.tld.",
+                    "SOA",
+                    0,
+                    1
+                ),
+                (
+                    2,
+                    200,
+                    ManageRecordOperationType::Override,
+                    "wiki.history.tld.",
+                    "TXT",
+                    1,
+                    1
+                ),
+                (
+                    3,
+                    200,
+                    ManageRecordOperationType::Override,
+                    "history.tld.",
+                    "SOA",
+                    1,
+                    1
+                ),
+            ]
+        );
+        assert_eq!(first_page.items[0].caller, controller_at(0).caller);
+        assert_eq!(second_page.items[0].before[0].data, "first");
+        assert_eq!(second_page.items[0].after[0].data, "second");
+    }
+
+    #[test]
+    fn get_zone_history_requires_zone_controller() {
+        manage_txt_record(
+            100,
+            "wiki.private.tld.",
+            ManageRecordsOperation::Append,
+            "first",
+        );
+
+        assert_eq!(
+            get_zone_history(
+                &CallContext::new(Principal::anonymous(), false, 0),
+                "private.tld.",
+                None
+            ),
+            Err(GetZoneHistoryError::Unauthorized {
+                caller: Principal::anonymous().to_text(),
+                domain: String::from("private.tld."),
+            })
+        );
+    }
+
+    #[test]
+    fn lookup_at_returns_past_records() {
+        manage_txt_record(
+            100,
+            "wiki.past.tld.",
+            ManageRecordsOperation::Append,
+            "first",
+        );
+        manage_txt_record(
+            200,
+            "wiki.past.tld.",
+            ManageRecordsOperation::Override,
+            "second",
+        );
+        let lookup_at = |record_type: &str, timestamp: u64| {
+            LookupService::default()
+                .lookup_at("wiki.past.tld.", record_type, timestamp)
+                .unwrap()
+                .answers
+                .into_iter()
+                .map(|record| record.data)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(lookup_at("TXT", 50).is_empty());
+        assert_eq!(lookup_at("TXT", 100), vec!["first"]);
+        assert_eq!(lookup_at("txt", 199), vec!["first"]);
+        assert_eq!(lookup_at("ANY", 150), vec!["first"]);
+        assert_eq!(lookup_at("TXT", 200), vec!["second"]);
+        assert!(lookup_at("CID", 150).is_empty());
+    }
+
+    #[test]
+    fn zone_history_is_bounded() {
+        let service = ZoneHistoryService::default();
+        let apex_domain = ZoneApexDomain::new(String::from("bounded.tld.")).unwrap();
+        let record = |data: u64| {
+            DomainRecord::new(
+                RecordName::default(),
+                String::from("TXT"),
+                60,
+                data.to_string(),
+            )
+        };
+        for time in 0..=MAX_ZONE_HISTORY_LENGTH {
+            service.record_changes(
+                &controller_at(time),
+                &apex_domain,
+                ZoneHistoryOperation::Override,
+                &[record(time)],
+                &[record(time + 1)],
+            );
+        }
+
+        let repository = ZoneHistoryRepository::default();
+        assert_eq!(repository.count(&apex_domain), MAX_ZONE_HISTORY_LENGTH);
+        assert_eq!(repository.first_sequence(&apex_domain), Some(1));
+        assert!(service
+            .changed_rrsets_at(&apex_domain, &RecordName::default(), None, 1)
+            .is_ok());
+        assert_eq!(
+            service.changed_rrsets_at(&apex_domain, &RecordName::default(), None, 0),
+            Err(LookupAtError::HistoryUnavailable {
+                domain: String::from("bounded.tld."),
+                timestamp: 0,
+                oldest_timestamp: 1,
+            })
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use crate::{
+    api::{CreateZoneInput, GetDomainsInput, GetDomainsItem, GetDomainsResult, PaginationInfo},
+    errors::{CreateZoneError, PaginationError},
+    repositories::{DomainZoneMetadataRepository, Repository},
+    services::{CertificationService, SoaService},
+    types::{CallContext, DomainZoneMetadata, ZoneApexDomain},
+    utils::{decode_cursor, encode_cursor},
+};
+use candid::Decode;
+use ic_stable_structures::Storable;
+use std::{collections::BTreeSet, ops::Deref};
+
+/// A service that manages the zones of the name registry and who controls them.
+pub struct ZonesService {
+    zone_metadata_repository: DomainZoneMetadataRepository,
+    certification_service: CertificationService,
+    soa_service: SoaService,
+}
+
+impl ZonesService {
+    pub fn new() -> Self {
+        Self {
+            zone_metadata_repository: DomainZoneMetadataRepository::default(),
+            certification_service: CertificationService::default(),
+            soa_service: SoaService::default(),
+        }
+    }
+
+    /// Creates a new zone controlled by the given controllers, only canister controllers can create zones.
+    ///
+    /// The zone starts with an SOA record with the default values at its apex.
+    pub fn create_zone(
+        &self,
+        context: &CallContext,
+        input: &CreateZoneInput,
+    ) -> Result<(), CreateZoneError> {
+        if !context.is_canister_controller {
+            return Err(CreateZoneError::Unauthorized {
+                caller: context.caller.to_text(),
+            });
+        }
+
+        let apex_domain = ZoneApexDomain::new(input.domain.to_ascii_lowercase())
+            .map_err(CreateZoneError::InvalidDomain)?;
+
+        if self.zone_metadata_repository.get(&apex_domain).is_some() {
+            return Err(CreateZoneError::ZoneAlreadyExists {
+                domain: apex_domain.deref().to_string(),
+            });
+        }
+
+        if input.controllers.len() > DomainZoneMetadata::MAX_CONTROLLERS {
+            return Err(CreateZoneError::TooManyControllers {
+                controllers: input.controllers.len(),
+                max_controllers: DomainZoneMetadata::MAX_CONTROLLERS,
+            });
+        }
+
+        let mut controller_ids = BTreeSet::new();
+        for controller in &input.controllers {
+            if !controller_ids.insert(controller.controller_id) {
+                return Err(CreateZoneError::DuplicateController {
+                    controller_id: controller.controller_id.to_text(),
+                });
+            }
+
+            if controller.roles.is_empty() {
+                return Err(CreateZoneError::MissingControllerRoles {
+                    controller_id: controller.controller_id.to_text(),
+                });
+            }
+        }
+
+        self.zone_metadata_repository
+            .insert(DomainZoneMetadata::new(
+                apex_domain.clone(),
+                input.controllers.clone(),
+            ));
+        let soa_entry = self.soa_service.create_soa(&apex_domain);
+        // the new zone answers for its names from now on, even if they were answered by its parent zone before
+        self.certification_service.certify_zone(&apex_domain);
+        self.certification_service.certify_entries(&[soa_entry]);
+
+        Ok(())
+    }
+
+    /// Returns a page of the apex domains of the zones the caller has access to, sorted by their name.
+    ///
+    /// Canister controllers have access to all zones, other callers to the zones they are a controller of.
+    pub fn get_domains(
+        &self,
+        context: &CallContext,
+        input: &GetDomainsInput,
+    ) -> Result<GetDomainsResult, PaginationError> {
+        let options = input.options.clone().unwrap_or_default();
+        let after = match &options.cursor {
+            Some(cursor) => Some(Self::decode_domain_cursor(cursor)?),
+            None => None,
+        };
+        let has_access = |metadata: &DomainZoneMetadata| {
+            context.is_canister_controller || metadata.is_controller(&context.caller)
+        };
+
+        let limit = options.effective_limit() as usize;
+        // one more zone than requested is loaded to know if there is a next page
+        let mut zones = self.zone_metadata_repository.find_after(
+            after.as_ref(),
+            has_access,
+            options.start as usize,
+            limit + 1,
+        );
+        let has_next_page = zones.len() > limit;
+        zones.truncate(limit);
+
+        let next_cursor = match zones.last() {
+            Some(metadata) if has_next_page => Some(encode_cursor(&metadata.name.to_bytes())),
+            _ => None,
+        };
+
+        Ok(GetDomainsResult {
+            info: PaginationInfo {
+                total: options
+                    .cursor
+                    .is_none()
+                    .then(|| self.zone_metadata_repository.count(has_access)),
+                limit: zones.len() as u64,
+                start: options.start,
+                next_cursor,
+            },
+            items: zones
+                .iter()
+                .map(|metadata| GetDomainsItem {
+                    domain: metadata.name.deref().to_string(),
+                })
+                .collect(),
+        })
+    }
+
+    fn decode_domain_cursor(cursor: &str) -> Result<ZoneApexDomain, PaginationError> {
+        decode_cursor(cursor)
+            .and_then(|bytes| Decode!(&bytes, ZoneApexDomain).ok())
+            .ok_or_else(|| PaginationError::InvalidCursor {
+                cursor: cursor.to_string(),
+            })
+    }
+}
+
+impl Default for ZonesService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::PaginationOptions,
+        repositories::CertifiedRecordsRepository,
+        services::LookupService,
+        types::{RegistrationController, RegistrationControllerRole},
+    };
+    use candid::Principal;
+
+    fn owner() -> Principal {
+        Principal::from_slice(&[1; 29])
+    }
+
+    fn create_zone(domain: &str, controllers: Vec<RegistrationController>) {
+        ZonesService::default()
+            .create_zone(
+                &CallContext::canister_controller(0),
+                &CreateZoneInput {
+                    domain: String::from(domain),
+                    controllers,
+                },
+            )
+            .unwrap();
+    }
+
+    fn registrant(controller_id: Principal) -> Vec<RegistrationController> {
+        vec![RegistrationController::new(
+            controller_id,
+            vec![RegistrationControllerRole::Registrant],
+        )]
+    }
+
+    fn domains(result: &GetDomainsResult) -> Vec<&str> {
+        result
+            .items
+            .iter()
+            .map(|item| item.domain.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn get_domains_pages_with_cursor() {
+        create_zone("a.tld.", vec![]);
+        create_zone("b.tld.", vec![]);
+        create_zone("c.tld.", vec![]);
+        let service = ZonesService::default();
+
+        let first_page = service
+            .get_domains(
+                &CallContext::canister_controller(0),
+                &GetDomainsInput {
+                    options: Some(PaginationOptions {
+                        start: 0,
+                        limit: 2,
+                        cursor: None,
+                    }),
+                },
+            )
+            .unwrap();
+        let second_page = service
+            .get_domains(
+                &CallContext::canister_controller(0),
+                &GetDomainsInput {
+                    options: Some(PaginationOptions {
+                        start: 0,
+                        limit: 2,
+                        cursor: first_page.info.next_cursor.clone(),
+                    }),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(domains(&first_page), vec!["a.tld.", "b.tld."]);
+        assert_eq!(first_page.info.total, Some(3));
+        assert_eq!(second_page.info.total, None);
+        assert_eq!(domains(&second_page), vec!["c.tld."]);
+        assert_eq!(second_page.info.next_cursor, None);
+    }
+
+    #[test]
+    fn get_domains_skips_start_items() {
+        create_zone("a.tld.", vec![]);
+        create_zone("b.tld.", vec![]);
+
+        let result = ZonesService::default()
+            .get_domains(
+                &CallContext::canister_controller(0),
+                &GetDomainsInput {
+                    options: Some(PaginationOptions {
+                        start: 1,
+                        ..Default::default()
+                    }),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(domains(&result), vec!["b.tld."]);
+        assert_eq!(result.info.start, 1);
+        assert_eq!(result.info.limit, 1);
+    }
+
+    #[test]
+    fn get_domains_only_returns_zones_of_the_caller() {
+        create_zone("a.tld.", registrant(owner()));
+        create_zone("b.tld.", registrant(Principal::anonymous()));
+        create_zone("c.tld.", registrant(owner()));
+
+        let result = ZonesService::default()
+            .get_domains(
+                &CallContext::new(owner(), false, 0),
+                &GetDomainsInput::default(),
+            )
+            .unwrap();
+
+        assert_eq!(domains(&result), vec!["a.tld.", "c.tld."]);
+        assert_eq!(result.info.total, Some(2));
+    }
+
+    #[test]
+    fn get_domains_rejects_invalid_cursor() {
+        let result = ZonesService::default().get_domains(
+            &CallContext::canister_controller(0),
+            &GetDomainsInput {
+                options: Some(PaginationOptions {
+                    cursor: Some(String::from("00")),
+                    ..Default::default()
+                }),
+            },
+        );
+
+        assert!(matches!(result, Err(PaginationError::InvalidCursor { .. })));
+    }
+
+    #[test]
+    fn create_zone_requires_canister_controller() {
+        let result = ZonesService::default().create_zone(
+            &CallContext::new(owner(), false, 0),
+            &CreateZoneInput {
+                domain: String::from("a.tld."),
+                controllers: registrant(owner()),
+            },
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            CreateZoneError::Unauthorized {
+                caller: owner().to_text(),
+            }
+        );
+    }
+
+    #[test]
+    fn create_zone_validates_controllers() {
+        let service = ZonesService::default();
+        let create = |domain: &str, controllers: Vec<RegistrationController>| {
+            service.create_zone(
+                &CallContext::canister_controller(0),
+                &CreateZoneInput {
+                    domain: String::from(domain),
+                    controllers,
+                },
+            )
+        };
+        create("a.tld.", vec![]).unwrap();
+
+        assert_eq!(
+            create("A.tld.", vec![]).unwrap_err(),
+            CreateZoneError::ZoneAlreadyExists {
+                domain: String::from("a.tld."),
+            }
+        );
+        assert_eq!(
+            create(
+                "b.tld.",
+                [registrant(owner()), registrant(owner())].concat()
+            )
+            .unwrap_err(),
+            CreateZoneError::DuplicateController {
+                controller_id: owner().to_text(),
+            }
+        );
+        assert_eq!(
+            create("b.tld.", vec![RegistrationController::new(owner(), vec![])]).unwrap_err(),
+            CreateZoneError::MissingControllerRoles {
+                controller_id: owner().to_text(),
+            }
+        );
+        assert_eq!(
+            create(
+                "b.tld.",
+                (0..=DomainZoneMetadata::MAX_CONTROLLERS)
+                    .flat_map(|index| registrant(Principal::from_slice(&[index as u8; 29])))
+                    .collect()
+            )
+            .unwrap_err(),
+            CreateZoneError::TooManyControllers {
+                controllers: DomainZoneMetadata::MAX_CONTROLLERS + 1,
+                max_controllers: DomainZoneMetadata::MAX_CONTROLLERS,
+            }
+        );
+    }
+
+    #[test]
+    fn create_zone_creates_soa_record() {
+        create_zone("soa.tld.", vec![]);
+
+        let soa = LookupService::default().lookup("soa.tld.", "SOA").answers;
+        assert_eq!(soa.len(), 1);
+        assert_eq!(
+            soa[0].data,
+            "soa.tld. hostmaster.soa.tld. 1 7200 3600 1209600 3600"
+        );
+        assert_eq!(
+            CertifiedRecordsRepository::default().rrsets(),
+            vec![(String::from("soa.tld."), String::from("SOA"))]
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use candid::Principal;
+
+/// The context of a canister call, used to authorize the caller of an operation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallContext {
+    /// The principal that made the call.
+    pub caller: Principal,
+    /// Wether the caller is a controller of the canister, these have access to all zones.
+    pub is_canister_controller: bool,
+    /// The time of the call in nanoseconds since the UNIX epoch.
+    pub time: u64,
+}
+
+impl CallContext {
+    pub fn new(caller: Principal, is_canister_controller: bool, time: u64) -> Self {
+        Self {
+            caller,
+            is_canister_controller,
+            time,
+        }
+    }
+
+    /// Creates the context of a call made by a controller of the canister, used by tests.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn canister_controller(time: u64) -> Self {
+        Self::new(Principal::from_slice(&[9; 29]), true, time)
+    }
+}
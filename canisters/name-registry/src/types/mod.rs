@@ -1,9 +1,17 @@
 //! Public types used for domain names.
 
+/// Types to represent the context of a canister call.
+mod call_context;
+pub use call_context::*;
+
 /// Types to represent a domain name record.
 mod record;
 pub use record::*;
 
+/// Types to represent the typed data of a domain record.
+mod record_data;
+pub use record_data::*;
+
 /// Types to represent a record name.
 mod record_name;
 pub use record_name::*;
@@ -12,10 +20,22 @@ pub use record_name::*;
 mod record_type;
 pub use record_type::*;
 
+/// Types to represent the controllers of a domain and their roles.
+mod registration_controller;
+pub use registration_controller::*;
+
+/// Types to represent the header of the data kept in stable memory.
+mod storage_header;
+pub use storage_header::*;
+
 /// Types to represent a domain name zone.
 mod zone;
 pub use zone::*;
 
+/// Types to represent the metadata of a domain name zone.
+mod zone_metadata;
+pub use zone_metadata::*;
+
 /// Types to represent the zone apex name, the main domain name of a zone.
 mod zone_apex_domain;
 pub use zone_apex_domain::*;
@@ -23,3 +43,7 @@ pub use zone_apex_domain::*;
 /// Types to represent a domain name zone entry.
 mod zone_entry;
 pub use zone_entry::*;
+
+/// Types to represent the history of the changes made to a domain name zone.
+mod zone_history;
+pub use zone_history::*;
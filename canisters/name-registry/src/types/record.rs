@@ -38,18 +38,6 @@ pub struct DomainRecord {
     pub data: String,
 }
 
-#[derive(CandidType, Deserialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
-pub struct DomainLookup {
-    // The list of answers that match the lookup, the answers section is the most important part of
-    // the lookup result as it contains the actual data that the client is looking for.
-    pub answers: Vec<DomainRecord>,
-    // Additionals are records that are not a direct match with the lookuped up record type but facilitate the process,
-    // e.g. returning the CID records from a NC lookup to prevent the client from having to perform another lookup.
-    pub additionals: Vec<DomainRecord>,
-    // Authorities contains records that point toward the authoritative naming canister/server for the domain.
-    pub authorities: Vec<DomainRecord>,
-}
-
 #[derive(CandidType, Deserialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct OperationResult {
     pub success: bool,
@@ -110,11 +98,10 @@ impl DomainRecord {
         )
     }
 
+    /// Record data is free-form text, the max value uses the highest unicode scalar value to make sure
+    /// it sorts after any data that can be stored.
     pub fn max_data_value() -> String {
-        repeat_char(
-            MAX_DOMAIN_ASCII_CHAR_VALUE as char,
-            Self::FIELD_DATA_BYTE_SIZE as usize,
-        )
+        repeat_char(char::MAX, Self::FIELD_DATA_BYTE_SIZE as usize)
     }
 
     pub fn max_ttl_value() -> u32 {
@@ -0,0 +1,484 @@
+use crate::{
+    errors::RecordDataError,
+    types::{DomainRecordTypes, ZoneApexDomain},
+};
+use candid::Principal;
+use std::{
+    fmt::Display,
+    net::{Ipv4Addr, Ipv6Addr},
+    ops::Deref,
+    str::FromStr,
+};
+
+/// The typed data of a domain record, parsed from the textual representation of the data for the record type.
+///
+/// Formatting the data results in its canonical textual representation, which is what gets stored, and parsing
+/// it back results in the same data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecordData {
+    /// An IPv4 address, e.g. "192.0.2.1".
+    A(Ipv4Addr),
+    /// An IPv6 address, e.g. "2001:db8::1".
+    AAAA(Ipv6Addr),
+    /// The certification authorities allowed to issue certificates for the domain, e.g. "0 issue \"ca.example\"".
+    CAA {
+        /// The flags of the property, e.g. 128 marks the property as critical.
+        flags: u8,
+        /// The property tag, e.g. "issue", "issuewild" or "iodef".
+        tag: String,
+        /// The property value.
+        value: String,
+    },
+    /// The principal of a canister, e.g. "qoctq-giaaa-aaaaa-aaaea-cai".
+    CID(Principal),
+    /// The domain name that the record name is an alias of, e.g. "mydomain.tld.".
+    CNAME(ZoneApexDomain),
+    /// The mail exchange of the domain, e.g. "10 mail.mydomain.tld.".
+    MX {
+        /// Mail exchanges with lower values are preferred.
+        preference: u16,
+        /// The domain name of the mail exchange.
+        exchange: ZoneApexDomain,
+    },
+    /// The principal of the naming canister that is authoritative for the domain, e.g. "qoctq-giaaa-aaaaa-aaaea-cai".
+    NC(Principal),
+    /// The domain name of the name server that is authoritative for the domain, e.g. "ns1.mydomain.tld.".
+    NS(ZoneApexDomain),
+    /// The domain name that the record name points to, e.g. "mydomain.tld.".
+    PTR(ZoneApexDomain),
+    /// The principal of a subnet, e.g. "tdb26-jop6k-aogll-7ltgs-eruif-6kk7m-qpktf-gdiqx-mxtrf-vb5e6-eqe".
+    SID(Principal),
+    /// The start of authority of the zone, e.g. "ns1.mydomain.tld. hostmaster.mydomain.tld. 1 7200 3600 1209600 3600".
+    SOA {
+        /// The domain name of the primary name server of the zone.
+        mname: ZoneApexDomain,
+        /// The mailbox of the person responsible for the zone, encoded as a domain name.
+        rname: ZoneApexDomain,
+        /// The version of the zone.
+        serial: u32,
+        /// The seconds after which secondary name servers should refresh the zone.
+        refresh: u32,
+        /// The seconds after which a failed refresh should be retried.
+        retry: u32,
+        /// The seconds after which secondary name servers stop answering for the zone if it can't be refreshed.
+        expire: u32,
+        /// The ttl of negative responses.
+        minimum: u32,
+    },
+    /// The location of a service, e.g. "10 5 443 service.mydomain.tld.".
+    SRV {
+        /// Targets with lower values are preferred.
+        priority: u16,
+        /// The relative weight of targets with the same priority.
+        weight: u16,
+        /// The port on which the service is provided.
+        port: u16,
+        /// The domain name of the host providing the service.
+        target: ZoneApexDomain,
+    },
+    /// One or more character strings, e.g. "hello" or "\"hello world\" \"second string\"".
+    TXT(Vec<String>),
+    /// The data of a record type without a typed representation, e.g. DNSKEY and NSEC, which is kept as is.
+    Other(String),
+}
+
+impl RecordData {
+    /// The maximum length of a character string of a TXT record.
+    pub const MAX_CHARACTER_STRING_LENGTH: usize = 255;
+
+    /// Parses the textual representation of the data of a record with the given record type.
+    pub fn parse(record_type: DomainRecordTypes, data: &str) -> Result<Self, RecordDataError> {
+        match record_type {
+            DomainRecordTypes::A => {
+                data.parse()
+                    .map(RecordData::A)
+                    .map_err(|_| RecordDataError::InvalidIpv4Address {
+                        data: data.to_string(),
+                    })
+            }
+            DomainRecordTypes::AAAA => data.parse().map(RecordData::AAAA).map_err(|_| {
+                RecordDataError::InvalidIpv6Address {
+                    data: data.to_string(),
+                }
+            }),
+            DomainRecordTypes::CAA => parse_certification_authority_authorization(data),
+            DomainRecordTypes::CID => parse_principal(data).map(RecordData::CID),
+            DomainRecordTypes::CNAME => parse_domain_name(data).map(RecordData::CNAME),
+            DomainRecordTypes::MX => parse_mail_exchange(data),
+            DomainRecordTypes::NC => parse_principal(data).map(RecordData::NC),
+            DomainRecordTypes::NS => parse_domain_name(data).map(RecordData::NS),
+            DomainRecordTypes::PTR => parse_domain_name(data).map(RecordData::PTR),
+            DomainRecordTypes::SID => parse_principal(data).map(RecordData::SID),
+            DomainRecordTypes::SOA => parse_start_of_authority(data),
+            DomainRecordTypes::SRV => parse_service_location(data),
+            DomainRecordTypes::TXT => parse_character_strings(data).map(RecordData::TXT),
+            DomainRecordTypes::DNSKEY | DomainRecordTypes::NSEC => {
+                Ok(RecordData::Other(data.to_string()))
+            }
+        }
+    }
+}
+
+/// Formats the data to its canonical textual representation.
+impl Display for RecordData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordData::A(address) => write!(f, "{}", address),
+            RecordData::AAAA(address) => write!(f, "{}", address),
+            RecordData::CAA { flags, tag, value } => {
+                write!(f, "{} {} {}", flags, tag, quote_character_string(value))
+            }
+            RecordData::CID(principal) | RecordData::NC(principal) | RecordData::SID(principal) => {
+                write!(f, "{}", principal.to_text())
+            }
+            RecordData::CNAME(domain) | RecordData::NS(domain) | RecordData::PTR(domain) => {
+                write!(f, "{}", domain.deref())
+            }
+            RecordData::MX {
+                preference,
+                exchange,
+            } => write!(f, "{} {}", preference, exchange.deref()),
+            RecordData::SOA {
+                mname,
+                rname,
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum,
+            } => write!(
+                f,
+                "{} {} {} {} {} {} {}",
+                mname.deref(),
+                rname.deref(),
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum
+            ),
+            RecordData::SRV {
+                priority,
+                weight,
+                port,
+                target,
+            } => write!(f, "{} {} {} {}", priority, weight, port, target.deref()),
+            RecordData::TXT(strings) => write!(
+                f,
+                "{}",
+                strings
+                    .iter()
+                    .map(|string| format_character_string(string))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            RecordData::Other(data) => write!(f, "{}", data),
+        }
+    }
+}
+
+fn parse_principal(data: &str) -> Result<Principal, RecordDataError> {
+    Principal::from_text(data).map_err(|_| RecordDataError::InvalidPrincipal {
+        data: data.to_string(),
+    })
+}
+
+fn parse_domain_name(data: &str) -> Result<ZoneApexDomain, RecordDataError> {
+    ZoneApexDomain::new(data.to_ascii_lowercase()).map_err(|error| {
+        RecordDataError::InvalidDomainName {
+            data: data.to_string(),
+            error,
+        }
+    })
+}
+
+fn parse_mail_exchange(data: &str) -> Result<RecordData, RecordDataError> {
+    let [preference, exchange] = data.split_whitespace().collect::<Vec<_>>()[..] else {
+        return Err(RecordDataError::InvalidMailExchange {
+            data: data.to_string(),
+        });
+    };
+
+    Ok(RecordData::MX {
+        preference: parse_number("preference", preference)?,
+        exchange: parse_domain_name(exchange)?,
+    })
+}
+
+fn parse_service_location(data: &str) -> Result<RecordData, RecordDataError> {
+    let [priority, weight, port, target] = data.split_whitespace().collect::<Vec<_>>()[..] else {
+        return Err(RecordDataError::InvalidServiceLocation {
+            data: data.to_string(),
+        });
+    };
+
+    Ok(RecordData::SRV {
+        priority: parse_number("priority", priority)?,
+        weight: parse_number("weight", weight)?,
+        port: parse_number("port", port)?,
+        target: parse_domain_name(target)?,
+    })
+}
+
+fn parse_certification_authority_authorization(data: &str) -> Result<RecordData, RecordDataError> {
+    let invalid_data = || RecordDataError::InvalidCertificationAuthorityAuthorization {
+        data: data.to_string(),
+    };
+    let mut fields = data.trim_start().splitn(2, char::is_whitespace);
+    let flags = fields
+        .next()
+        .filter(|flags| !flags.is_empty())
+        .ok_or_else(invalid_data)?;
+    let mut fields = fields
+        .next()
+        .ok_or_else(invalid_data)?
+        .trim_start()
+        .splitn(2, char::is_whitespace);
+    let tag = fields
+        .next()
+        .filter(|tag| !tag.is_empty())
+        .ok_or_else(invalid_data)?;
+    let [value] = &parse_character_strings(fields.next().ok_or_else(invalid_data)?)?[..] else {
+        return Err(invalid_data());
+    };
+
+    if !tag.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(RecordDataError::InvalidTag {
+            tag: tag.to_string(),
+        });
+    }
+
+    Ok(RecordData::CAA {
+        flags: parse_number("flags", flags)?,
+        tag: tag.to_ascii_lowercase(),
+        value: value.clone(),
+    })
+}
+
+fn parse_start_of_authority(data: &str) -> Result<RecordData, RecordDataError> {
+    let [mname, rname, serial, refresh, retry, expire, minimum] =
+        data.split_whitespace().collect::<Vec<_>>()[..]
+    else {
+        return Err(RecordDataError::InvalidStartOfAuthority {
+            data: data.to_string(),
+        });
+    };
+
+    Ok(RecordData::SOA {
+        mname: parse_domain_name(mname)?,
+        rname: parse_domain_name(rname)?,
+        serial: parse_number("serial", serial)?,
+        refresh: parse_number("refresh", refresh)?,
+        retry: parse_number("retry", retry)?,
+        expire: parse_number("expire", expire)?,
+        minimum: parse_number("minimum", minimum)?,
+    })
+}
+
+/// Parses an unsigned number that must fit in the numeric type of the field.
+fn parse_number<T>(field: &str, value: &str) -> Result<T, RecordDataError>
+where
+    T: FromStr + Bounded,
+{
+    value.parse().map_err(|_| RecordDataError::InvalidNumber {
+        field: field.to_string(),
+        value: value.to_string(),
+        max_value: T::MAX_VALUE,
+    })
+}
+
+/// The numeric types used by the fields of the record data.
+trait Bounded {
+    const MAX_VALUE: u32;
+}
+
+impl Bounded for u8 {
+    const MAX_VALUE: u32 = u8::MAX as u32;
+}
+
+impl Bounded for u16 {
+    const MAX_VALUE: u32 = u16::MAX as u32;
+}
+
+impl Bounded for u32 {
+    const MAX_VALUE: u32 = u32::MAX;
+}
+
+/// Parses the character strings of a TXT record, strings are separated by whitespace and can be quoted to include
+/// whitespace, within quotes a backslash escapes the next character.
+///
+/// Empty data results in a single empty string.
+fn parse_character_strings(data: &str) -> Result<Vec<String>, RecordDataError> {
+    let mut strings = Vec::new();
+    let mut chars = data.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let string = match chars.next() {
+            None => break,
+            Some('"') => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => string.push(c),
+                            None => {
+                                return Err(RecordDataError::UnterminatedString {
+                                    data: data.to_string(),
+                                })
+                            }
+                        },
+                        Some(c) => string.push(c),
+                        None => {
+                            return Err(RecordDataError::UnterminatedString {
+                                data: data.to_string(),
+                            })
+                        }
+                    }
+                }
+
+                string
+            }
+            Some(c) => {
+                let mut string = String::from(c);
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    string.push(c);
+                }
+
+                string
+            }
+        };
+
+        if string.len() > RecordData::MAX_CHARACTER_STRING_LENGTH {
+            return Err(RecordDataError::StringTooLong {
+                string_length: string.len(),
+                max_string_length: RecordData::MAX_CHARACTER_STRING_LENGTH,
+            });
+        }
+
+        strings.push(string);
+    }
+
+    if strings.is_empty() {
+        strings.push(String::new());
+    }
+
+    Ok(strings)
+}
+
+/// Formats a character string of a TXT record, strings are only quoted if they would not be parsed back otherwise,
+/// which includes the comments and parentheses of zone files.
+fn format_character_string(string: &str) -> String {
+    let needs_quotes = string.is_empty()
+        || string
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\\' | ';' | '(' | ')'));
+
+    if !needs_quotes {
+        return string.to_string();
+    }
+
+    quote_character_string(string)
+}
+
+/// Quotes a character string, escaping the quotes and backslashes that it contains.
+fn quote_character_string(string: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in string.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::ipv4("A", "192.0.2.1", "192.0.2.1")]
+    #[case::ipv6("AAAA", "2001:0DB8:0000::0001", "2001:db8::1")]
+    #[case::canister_id("CID", "qoctq-giaaa-aaaaa-aaaea-cai", "qoctq-giaaa-aaaaa-aaaea-cai")]
+    #[case::alias("CNAME", "MyDomain.tld.", "mydomain.tld.")]
+    #[case::mail_exchange("MX", " 10   Mail.mydomain.tld. ", "10 mail.mydomain.tld.")]
+    #[case::naming_canister("NC", "qoctq-giaaa-aaaaa-aaaea-cai", "qoctq-giaaa-aaaaa-aaaea-cai")]
+    #[case::name_server("NS", "ns1.mydomain.tld.", "ns1.mydomain.tld.")]
+    #[case::subnet_id(
+        "SID",
+        "tdb26-jop6k-aogll-7ltgs-eruif-6kk7m-qpktf-gdiqx-mxtrf-vb5e6-eqe",
+        "tdb26-jop6k-aogll-7ltgs-eruif-6kk7m-qpktf-gdiqx-mxtrf-vb5e6-eqe"
+    )]
+    #[case::text("TXT", "hello", "hello")]
+    #[case::text_strings("TXT", "hello  world", "hello world")]
+    #[case::text_quoted("TXT", r#""hello world" "second""#, r#""hello world" second"#)]
+    #[case::text_escaped("TXT", r#""say \"hi\"" back\slash"#, r#""say \"hi\"" "back\\slash""#)]
+    #[case::text_empty("TXT", "", r#""""#)]
+    #[case::text_comment("TXT", "v=spf1;a (b)", r#""v=spf1;a" "(b)""#)]
+    #[case::certification_authority("CAA", "0 ISSUE ca.example", r#"0 issue "ca.example""#)]
+    #[case::certification_authority_quoted(
+        "CAA",
+        r#"128 iodef "mailto:security@mydomain.tld""#,
+        r#"128 iodef "mailto:security@mydomain.tld""#
+    )]
+    #[case::pointer("PTR", "MyDomain.tld.", "mydomain.tld.")]
+    #[case::start_of_authority(
+        "SOA",
+        "ns1.mydomain.tld.  hostmaster.mydomain.tld. 1 7200 3600 1209600 3600",
+        "ns1.mydomain.tld. hostmaster.mydomain.tld. 1 7200 3600 1209600 3600"
+    )]
+    #[case::service_location(
+        "SRV",
+        "10 5 443 Service.mydomain.tld.",
+        "10 5 443 service.mydomain.tld."
+    )]
+    #[case::other("DNSKEY", "free form data", "free form data")]
+    fn record_data_round_trip(
+        #[case] record_type: &str,
+        #[case] data: &str,
+        #[case] canonical_data: &str,
+    ) {
+        let record_data = RecordData::parse(record_type.parse().unwrap(), data).unwrap();
+
+        assert_eq!(record_data.to_string(), canonical_data);
+        assert_eq!(
+            RecordData::parse(record_type.parse().unwrap(), canonical_data).unwrap(),
+            record_data
+        );
+    }
+
+    #[rstest]
+    #[case::ipv4("A", "hello", RecordDataError::InvalidIpv4Address { data: String::from("hello") })]
+    #[case::ipv4_with_ipv6("A", "2001:db8::1", RecordDataError::InvalidIpv4Address { data: String::from("2001:db8::1") })]
+    #[case::ipv6("AAAA", "192.0.2.1", RecordDataError::InvalidIpv6Address { data: String::from("192.0.2.1") })]
+    #[case::principal("CID", "not-a-principal", RecordDataError::InvalidPrincipal { data: String::from("not-a-principal") })]
+    #[case::domain_name("CNAME", "mydomain.tld", RecordDataError::InvalidDomainName { data: String::from("mydomain.tld"), error: crate::errors::ZoneApexDomainError::MissingEndWithDot })]
+    #[case::mail_exchange("MX", "mail.mydomain.tld.", RecordDataError::InvalidMailExchange { data: String::from("mail.mydomain.tld.") })]
+    #[case::preference("MX", "70000 mail.mydomain.tld.", RecordDataError::InvalidNumber { field: String::from("preference"), value: String::from("70000"), max_value: 65535 })]
+    #[case::service_location("SRV", "10 5 service.mydomain.tld.", RecordDataError::InvalidServiceLocation { data: String::from("10 5 service.mydomain.tld.") })]
+    #[case::port("SRV", "10 5 -1 service.mydomain.tld.", RecordDataError::InvalidNumber { field: String::from("port"), value: String::from("-1"), max_value: 65535 })]
+    #[case::certification_authority("CAA", "0 issue", RecordDataError::InvalidCertificationAuthorityAuthorization { data: String::from("0 issue") })]
+    #[case::certification_authority_values("CAA", "0 issue first second", RecordDataError::InvalidCertificationAuthorityAuthorization { data: String::from("0 issue first second") })]
+    #[case::tag("CAA", "0 is-sue ca.example", RecordDataError::InvalidTag { tag: String::from("is-sue") })]
+    #[case::flags("CAA", "256 issue ca.example", RecordDataError::InvalidNumber { field: String::from("flags"), value: String::from("256"), max_value: 255 })]
+    #[case::start_of_authority("SOA", "ns1.mydomain.tld. 1 7200 3600 1209600 3600", RecordDataError::InvalidStartOfAuthority { data: String::from("ns1.mydomain.tld. 1 7200 3600 1209600 3600") })]
+    #[case::serial("SOA", "ns1.mydomain.tld. hostmaster.mydomain.tld. 4294967296 7200 3600 1209600 3600", RecordDataError::InvalidNumber { field: String::from("serial"), value: String::from("4294967296"), max_value: u32::MAX })]
+    #[case::unterminated_string("TXT", r#""hello"#, RecordDataError::UnterminatedString { data: String::from(r#""hello"#) })]
+    #[case::string_too_long("TXT", &"a".repeat(256), RecordDataError::StringTooLong { string_length: 256, max_string_length: 255 })]
+    fn record_data_validation_fail(
+        #[case] record_type: &str,
+        #[case] data: &str,
+        #[case] expected_err: RecordDataError,
+    ) {
+        assert_eq!(
+            RecordData::parse(record_type.parse().unwrap(), data).unwrap_err(),
+            expected_err
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
-    common::{MAX_DOMAIN_ASCII_CHAR_VALUE, MAX_DOMAIN_LABEL_LENGTH},
+    common::{MAX_DOMAIN_ASCII_CHAR_VALUE, MAX_DOMAIN_LABEL_LENGTH, WILDCARD_LABEL},
     errors::RecordNameError,
     types::ZoneApexDomain,
-    utils::repeat_char,
+    utils::{name_to_ascii, name_to_unicode, repeat_char},
 };
 use candid::{CandidType, Decode, Deserialize, Encode};
 use ic_stable_structures::{BoundedStorable, Storable};
@@ -14,7 +14,8 @@ use std::{borrow::Cow, cmp, ops::Deref};
 /// name must be under 255 bytes.
 ///
 /// Names are encoded in ascii, have alphanumeric characters and are case insensitive, but
-/// the canonical form is lowercase.
+/// the canonical form is lowercase. Internationalized names are accepted in their Unicode form
+/// and stored as punycode A-labels, e.g. "café" is stored as "xn--caf-dma".
 #[derive(CandidType, Deserialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct RecordName(String);
 
@@ -24,14 +25,44 @@ impl RecordName {
 
     /// Creates a new record name and returns an error if the name is invalid.
     ///
-    /// The name will be converted to lowercase as the canonical form of any domain name is lowercase.
+    /// The name will be converted to lowercase as the canonical form of any domain name is lowercase, and its
+    /// Unicode labels are normalized and converted to A-labels.
     pub fn new(name: String, apex_domain: &ZoneApexDomain) -> Result<Self, RecordNameError> {
-        let lowercased_name = name.to_ascii_lowercase();
+        let lowercased_name = name_to_ascii(&name)
+            .map_err(RecordNameError::InvalidInternationalizedName)?
+            .to_ascii_lowercase();
         Self::validate(&lowercased_name, apex_domain)?;
 
         Ok(Self(lowercased_name))
     }
 
+    /// Creates a new record name from a fully qualified domain name that is part of the zone of the apex domain,
+    /// e.g. "wiki.mydomain.tld." results in "wiki" and "mydomain.tld." in "@" for the apex domain "mydomain.tld.".
+    pub fn from_fully_qualified_name(
+        name: &str,
+        apex_domain: &ZoneApexDomain,
+    ) -> Result<Self, RecordNameError> {
+        let lowercased_name = name_to_ascii(name)
+            .map_err(RecordNameError::InvalidInternationalizedName)?
+            .to_ascii_lowercase();
+        if lowercased_name == apex_domain.deref() {
+            return Ok(Self::default());
+        }
+
+        match lowercased_name.strip_suffix(&format!(".{}", apex_domain.deref())) {
+            Some(record_name) => Self::new(record_name.to_string(), apex_domain),
+            None => Err(RecordNameError::NotInZone {
+                name: name.to_string(),
+                apex_domain: apex_domain.deref().to_string(),
+            }),
+        }
+    }
+
+    /// Creates the name without validating it, used for names that were validated before being stored.
+    pub(crate) fn new_unchecked(name: String) -> Self {
+        Self(name)
+    }
+
     /// Validates the apex domain name and returns an error if it is invalid.
     fn validate(record_name: &str, apex_domain: &ZoneApexDomain) -> Result<(), RecordNameError> {
         if record_name.is_empty() {
@@ -47,11 +78,20 @@ impl RecordName {
             return Ok(());
         }
 
-        for label in record_name.split('.').collect::<Vec<&str>>() {
+        for (index, label) in record_name.split('.').enumerate() {
             if label.is_empty() {
                 return Err(RecordNameError::NonEmptyLabel);
             }
 
+            // a single * as the leftmost label makes the record a wildcard, as defined in RFC 4592
+            if label == WILDCARD_LABEL && index == 0 {
+                continue;
+            }
+
+            if label.contains(WILDCARD_LABEL) {
+                return Err(RecordNameError::MisplacedWildcard);
+            }
+
             if label.len() > MAX_DOMAIN_LABEL_LENGTH {
                 return Err(RecordNameError::LabelTooLong {
                     label_length: label.len(),
@@ -116,8 +156,34 @@ impl RecordName {
     }
 
     /// Generates the minimum value of a record name.
+    ///
+    /// Labels can start with digits which sort before "@", hence the empty name is used as the lower bound.
     pub fn min_value() -> RecordName {
-        Self("@".to_string())
+        Self(String::new())
+    }
+
+    /// Returns the fully qualified domain name of the record within the given apex domain, e.g. "wiki.mydomain.tld.".
+    pub fn to_fully_qualified_name(&self, apex_domain: &ZoneApexDomain) -> String {
+        if self.is_apex() {
+            return apex_domain.deref().to_string();
+        }
+
+        format!("{}.{}", self.0, apex_domain.deref())
+    }
+
+    /// Returns the record name with its A-labels converted to Unicode, which is meant for display purposes.
+    pub fn to_unicode(&self) -> String {
+        name_to_unicode(&self.0)
+    }
+
+    /// Returns `true` if the record name refers to the apex domain of the zone.
+    pub fn is_apex(&self) -> bool {
+        self.0 == "@"
+    }
+
+    /// Returns `true` if the leftmost label of the record name is the wildcard label, e.g. "*.preview".
+    pub fn is_wildcard(&self) -> bool {
+        self.0.split('.').next() == Some(WILDCARD_LABEL)
     }
 }
 
@@ -157,6 +223,7 @@ impl BoundedStorable for RecordName {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::InternationalizedNameError;
     use rstest::rstest;
 
     #[rstest]
@@ -165,6 +232,10 @@ mod tests {
     #[case::multiple_subdomain_record(&"internetcomputer.tld.", &"subdomain.wiki")]
     #[case::valid_middle_hyphen(&"internetcomputer.tld.", &"subdomain-wiki")]
     #[case::valid_begin_with_underscore(&"internetcomputer.tld.", &"_canister")]
+    #[case::wildcard(&"internetcomputer.tld.", &"*")]
+    #[case::subdomain_wildcard(&"internetcomputer.tld.", &"*.preview")]
+    #[case::unicode_name(&"internetcomputer.tld.", &"café.wiki")]
+    #[case::unicode_name_in_unicode_zone(&"café.tld.", &"вики")]
     fn record_name_validation_pass(#[case] apex_domain: &str, #[case] domain_name: &str) {
         let apex_domain = ZoneApexDomain::new(String::from(apex_domain)).unwrap();
         let record_name = RecordName::new(String::from(domain_name), &apex_domain);
@@ -180,6 +251,9 @@ mod tests {
     #[case::misplaced_underscore_end(&"internetcomputer.tld.", &"domain_", RecordNameError::MisplacedUnderscore)]
     #[case::misplaced_underscore_middle(&"internetcomputer.tld.", &"domain_another", RecordNameError::MisplacedUnderscore)]
     #[case::misplaced_underscore_other_subdomain(&"internetcomputer.tld.", &"domain._another", RecordNameError::MisplacedUnderscore)]
+    #[case::misplaced_wildcard(&"internetcomputer.tld.", &"preview.*", RecordNameError::MisplacedWildcard)]
+    #[case::partial_wildcard_label(&"internetcomputer.tld.", &"preview*", RecordNameError::MisplacedWildcard)]
+    #[case::disallowed_code_point(&"internetcomputer.tld.", &"wiki.a⌘b", RecordNameError::InvalidInternationalizedName(InternationalizedNameError::DisallowedCodePoint { label: String::from("a⌘b"), code_point: '⌘' }))]
     fn record_name_validation_fail(
         #[case] apex_domain: &str,
         #[case] domain_name: &str,
@@ -191,4 +265,60 @@ mod tests {
         assert!(record_name.is_err());
         assert_eq!(record_name.unwrap_err(), expected_err);
     }
+
+    #[rstest]
+    #[case::apex_record(&"internetcomputer.tld.", &"internetcomputer.tld.", &"@")]
+    #[case::subdomain_record(&"internetcomputer.tld.", &"Wiki.InternetComputer.tld.", &"wiki")]
+    #[case::multiple_subdomain_record(&"internetcomputer.tld.", &"a.wiki.internetcomputer.tld.", &"a.wiki")]
+    #[case::wildcard_record(&"internetcomputer.tld.", &"*.preview.internetcomputer.tld.", &"*.preview")]
+    fn record_name_from_fully_qualified_name(
+        #[case] apex_domain: &str,
+        #[case] name: &str,
+        #[case] expected_name: &str,
+    ) {
+        let apex_domain = ZoneApexDomain::new(String::from(apex_domain)).unwrap();
+        let record_name = RecordName::from_fully_qualified_name(name, &apex_domain).unwrap();
+
+        assert_eq!(record_name.deref(), expected_name);
+        assert_eq!(
+            record_name.to_fully_qualified_name(&apex_domain),
+            name.to_ascii_lowercase()
+        );
+    }
+
+    #[test]
+    fn record_name_from_internationalized_name() {
+        let apex_domain = ZoneApexDomain::new(String::from("café.tld.")).unwrap();
+        let record_name =
+            RecordName::from_fully_qualified_name("Вики.xn--caf-dma.tld.", &apex_domain).unwrap();
+
+        assert_eq!(record_name.deref(), "xn--b1amah");
+        assert_eq!(record_name.to_unicode(), "вики");
+        assert_eq!(
+            RecordName::from_fully_qualified_name("wiki.CAFÉ.tld.", &apex_domain)
+                .unwrap()
+                .deref(),
+            "wiki"
+        );
+    }
+
+    #[rstest]
+    #[case::other_zone(&"internetcomputer.tld.", &"wiki.other.tld.")]
+    #[case::missing_end_with_dot(&"internetcomputer.tld.", &"wiki.internetcomputer.tld")]
+    #[case::partial_label(&"internetcomputer.tld.", &"wikiinternetcomputer.tld.")]
+    fn record_name_from_fully_qualified_name_not_in_zone(
+        #[case] apex_domain: &str,
+        #[case] name: &str,
+    ) {
+        let apex_domain = ZoneApexDomain::new(String::from(apex_domain)).unwrap();
+        let record_name = RecordName::from_fully_qualified_name(name, &apex_domain);
+
+        assert_eq!(
+            record_name.unwrap_err(),
+            RecordNameError::NotInZone {
+                name: String::from(name),
+                apex_domain: apex_domain.deref().to_string(),
+            }
+        );
+    }
 }
@@ -1,46 +1,139 @@
-use std::fmt::Display;
+use crate::errors::RecordTypeError;
+use std::{fmt::Display, str::FromStr};
 
 /// Represents the existing record types that can be used in a domain record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum DomainRecordTypes {
     A,
     AAAA,
+    CAA,
     CID,
     CNAME,
+    DNSKEY,
     MX,
     NC,
     NS,
+    NSEC,
+    PTR,
+    SID,
+    SOA,
+    SRV,
     TXT,
 }
 
+impl DomainRecordTypes {
+    /// All the record types that can be used in a domain record.
+    pub const ALL: [DomainRecordTypes; 15] = [
+        DomainRecordTypes::A,
+        DomainRecordTypes::AAAA,
+        DomainRecordTypes::CAA,
+        DomainRecordTypes::CID,
+        DomainRecordTypes::CNAME,
+        DomainRecordTypes::DNSKEY,
+        DomainRecordTypes::MX,
+        DomainRecordTypes::NC,
+        DomainRecordTypes::NS,
+        DomainRecordTypes::NSEC,
+        DomainRecordTypes::PTR,
+        DomainRecordTypes::SID,
+        DomainRecordTypes::SOA,
+        DomainRecordTypes::SRV,
+        DomainRecordTypes::TXT,
+    ];
+}
+
 /// Adds the equivalent string representation for each DomainRecordType.
 impl Display for DomainRecordTypes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             DomainRecordTypes::A => write!(f, "A"),
             DomainRecordTypes::AAAA => write!(f, "AAAA"),
+            DomainRecordTypes::CAA => write!(f, "CAA"),
             DomainRecordTypes::CID => write!(f, "CID"),
             DomainRecordTypes::CNAME => write!(f, "CNAME"),
+            DomainRecordTypes::DNSKEY => write!(f, "DNSKEY"),
             DomainRecordTypes::MX => write!(f, "MX"),
             DomainRecordTypes::NC => write!(f, "NC"),
             DomainRecordTypes::NS => write!(f, "NS"),
+            DomainRecordTypes::NSEC => write!(f, "NSEC"),
+            DomainRecordTypes::PTR => write!(f, "PTR"),
+            DomainRecordTypes::SID => write!(f, "SID"),
+            DomainRecordTypes::SOA => write!(f, "SOA"),
+            DomainRecordTypes::SRV => write!(f, "SRV"),
             DomainRecordTypes::TXT => write!(f, "TXT"),
         }
     }
 }
 
+/// Parses the record type from its string representation, ignoring the case.
+impl FromStr for DomainRecordTypes {
+    type Err = RecordTypeError;
+
+    fn from_str(record_type: &str) -> Result<Self, Self::Err> {
+        DomainRecordTypes::ALL
+            .into_iter()
+            .find(|known_type| known_type.to_string().eq_ignore_ascii_case(record_type))
+            .ok_or_else(|| RecordTypeError::UnsupportedRecordType {
+                record_type: record_type.to_string(),
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn record_types_match_string_representation() {
         assert_eq!(DomainRecordTypes::A.to_string(), "A");
         assert_eq!(DomainRecordTypes::AAAA.to_string(), "AAAA");
+        assert_eq!(DomainRecordTypes::CAA.to_string(), "CAA");
         assert_eq!(DomainRecordTypes::CID.to_string(), "CID");
         assert_eq!(DomainRecordTypes::CNAME.to_string(), "CNAME");
+        assert_eq!(DomainRecordTypes::DNSKEY.to_string(), "DNSKEY");
         assert_eq!(DomainRecordTypes::MX.to_string(), "MX");
         assert_eq!(DomainRecordTypes::NC.to_string(), "NC");
         assert_eq!(DomainRecordTypes::NS.to_string(), "NS");
+        assert_eq!(DomainRecordTypes::NSEC.to_string(), "NSEC");
+        assert_eq!(DomainRecordTypes::PTR.to_string(), "PTR");
+        assert_eq!(DomainRecordTypes::SID.to_string(), "SID");
+        assert_eq!(DomainRecordTypes::SOA.to_string(), "SOA");
+        assert_eq!(DomainRecordTypes::SRV.to_string(), "SRV");
         assert_eq!(DomainRecordTypes::TXT.to_string(), "TXT");
     }
+
+    #[test]
+    fn record_types_round_trip() {
+        for record_type in DomainRecordTypes::ALL {
+            assert_eq!(
+                record_type.to_string().parse::<DomainRecordTypes>(),
+                Ok(record_type)
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::uppercase("SRV", DomainRecordTypes::SRV)]
+    #[case::lowercase("ptr", DomainRecordTypes::PTR)]
+    #[case::mixed_case("DnsKey", DomainRecordTypes::DNSKEY)]
+    fn record_types_are_parsed_ignoring_case(
+        #[case] record_type: &str,
+        #[case] expected: DomainRecordTypes,
+    ) {
+        assert_eq!(record_type.parse::<DomainRecordTypes>(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case::unknown("HINFO")]
+    #[case::reserved("ANY")]
+    #[case::empty("")]
+    fn unsupported_record_types_are_rejected(#[case] record_type: &str) {
+        assert_eq!(
+            record_type.parse::<DomainRecordTypes>(),
+            Err(RecordTypeError::UnsupportedRecordType {
+                record_type: record_type.to_string(),
+            })
+        );
+    }
 }
@@ -0,0 +1,121 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// The roles that a controller can have over a domain.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RegistrationControllerRole {
+    /// Registers and manages domains on behalf of their registrants.
+    #[serde(rename = "registrar")]
+    Registrar,
+    /// The owner of the domain.
+    #[serde(rename = "registrant")]
+    Registrant,
+    /// Responsible for the technical operation of the domain, such as its records.
+    #[serde(rename = "technical")]
+    Technical,
+    /// Responsible for the administration of the domain.
+    #[serde(rename = "administrative")]
+    Administrative,
+}
+
+impl RegistrationControllerRole {
+    /// Returns `true` if the role allows changing the records of the domain.
+    pub fn can_manage_records(&self) -> bool {
+        matches!(
+            self,
+            RegistrationControllerRole::Registrar
+                | RegistrationControllerRole::Registrant
+                | RegistrationControllerRole::Technical
+        )
+    }
+
+    /// Returns `true` if the role allows adding and removing the controllers of the domain.
+    pub fn can_manage_controllers(&self) -> bool {
+        matches!(
+            self,
+            RegistrationControllerRole::Registrar
+                | RegistrationControllerRole::Registrant
+                | RegistrationControllerRole::Administrative
+        )
+    }
+
+    /// Returns `true` if the role allows transferring and renewing the registration of the domain, which is left to
+    /// its owner and the registrars acting on their behalf.
+    pub fn can_manage_registration(&self) -> bool {
+        matches!(
+            self,
+            RegistrationControllerRole::Registrar | RegistrationControllerRole::Registrant
+        )
+    }
+}
+
+/// A principal that controls a domain with the given roles.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct RegistrationController {
+    /// The principal of the controller.
+    pub controller_id: Principal,
+    /// The roles the controller has over the domain.
+    pub roles: Vec<RegistrationControllerRole>,
+}
+
+impl RegistrationController {
+    /// Creates a new controller with the given roles.
+    pub fn new(controller_id: Principal, roles: Vec<RegistrationControllerRole>) -> Self {
+        Self {
+            controller_id,
+            roles,
+        }
+    }
+
+    /// Returns `true` if the controller has the given role.
+    pub fn has_role(&self, role: &RegistrationControllerRole) -> bool {
+        self.roles.contains(role)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::{CandidType, Decode, Encode};
+
+    #[test]
+    fn roles_match_candid_variant_names() {
+        let roles = vec![
+            RegistrationControllerRole::Registrar,
+            RegistrationControllerRole::Registrant,
+            RegistrationControllerRole::Technical,
+            RegistrationControllerRole::Administrative,
+        ];
+        let bytes = Encode!(&roles).unwrap();
+
+        assert_eq!(
+            Decode!(&bytes, Vec<RegistrationControllerRole>).unwrap(),
+            roles
+        );
+
+        let candid_type = RegistrationControllerRole::ty().to_string();
+        for variant in ["registrar", "registrant", "technical", "administrative"] {
+            assert!(candid_type.contains(variant), "{}", candid_type);
+        }
+    }
+
+    #[test]
+    fn only_operational_roles_can_manage_records() {
+        assert!(RegistrationControllerRole::Registrar.can_manage_records());
+        assert!(RegistrationControllerRole::Registrant.can_manage_records());
+        assert!(RegistrationControllerRole::Technical.can_manage_records());
+        assert!(!RegistrationControllerRole::Administrative.can_manage_records());
+    }
+
+    #[test]
+    fn technical_role_cannot_manage_controllers_or_registration() {
+        assert!(RegistrationControllerRole::Registrar.can_manage_controllers());
+        assert!(RegistrationControllerRole::Registrant.can_manage_controllers());
+        assert!(!RegistrationControllerRole::Technical.can_manage_controllers());
+        assert!(RegistrationControllerRole::Administrative.can_manage_controllers());
+
+        assert!(RegistrationControllerRole::Registrar.can_manage_registration());
+        assert!(RegistrationControllerRole::Registrant.can_manage_registration());
+        assert!(!RegistrationControllerRole::Technical.can_manage_registration());
+        assert!(!RegistrationControllerRole::Administrative.can_manage_registration());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::common::LEGACY_SCHEMA_VERSION;
+use candid::{CandidType, Decode, Deserialize, Encode};
+use ic_stable_structures::Storable;
+use std::borrow::Cow;
+
+/// Describes the data kept in stable memory, which lets a new version of the canister know how to read the data
+/// written by the previous one.
+///
+/// New fields must be optional to keep the header readable by every version of the canister.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct StorageHeader {
+    /// The version of the layout of the stored data, see [crate::common::SCHEMA_VERSION].
+    pub schema_version: u32,
+    /// Whether the domain zones are stored in their swap memory instead of their default one, the zones alternate
+    /// between the two memories when they are rewritten, see [crate::repositories::DomainZoneRepository::rewrite].
+    pub domain_zones_swapped: Option<bool>,
+}
+
+impl StorageHeader {
+    pub fn new(schema_version: u32) -> Self {
+        Self {
+            schema_version,
+            domain_zones_swapped: None,
+        }
+    }
+}
+
+impl Default for StorageHeader {
+    /// The default header describes the data written before the header was introduced.
+    fn default() -> Self {
+        Self::new(LEGACY_SCHEMA_VERSION)
+    }
+}
+
+/// Adds serialization and deserialization support to StorageHeader to stable memory.
+impl Storable for StorageHeader {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialization_for_storage_header_match() {
+        let header = StorageHeader::new(1);
+
+        assert_eq!(StorageHeader::from_bytes(header.to_bytes()), header);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     common::{MAX_DOMAIN_LABEL_LENGTH, MAX_DOMAIN_NAME_LENGTH},
     errors::ZoneApexDomainError,
+    utils::{name_to_ascii, name_to_unicode},
 };
 use candid::{CandidType, Decode, Deserialize, Encode};
 use ic_stable_structures::{BoundedStorable, Storable};
@@ -12,7 +13,8 @@ use std::{borrow::Cow, ops::Deref};
 /// name must be under 255 bytes.
 ///
 /// Names are encoded in ascii, have alphanumeric characters and are case insensitive, but
-/// the canonical form is lowercase.
+/// the canonical form is lowercase. Internationalized names are accepted in their Unicode form
+/// and stored as punycode A-labels, e.g. "café.tld." is stored as "xn--caf-dma.tld.".
 #[derive(CandidType, Deserialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct ZoneApexDomain(String);
 
@@ -21,12 +23,26 @@ impl ZoneApexDomain {
     pub const MAX_SIZE: usize = MAX_DOMAIN_NAME_LENGTH;
 
     /// Creates a new apex domain name and returns an error if it is invalid.
+    ///
+    /// Unicode labels are normalized and converted to A-labels before the name is validated.
     pub fn new(name: String) -> Result<Self, ZoneApexDomainError> {
+        let name =
+            name_to_ascii(&name).map_err(ZoneApexDomainError::InvalidInternationalizedName)?;
         Self::validate(&name)?;
 
         Ok(Self(name))
     }
 
+    /// Returns the domain name with its A-labels converted to Unicode, which is meant for display purposes.
+    pub fn to_unicode(&self) -> String {
+        name_to_unicode(&self.0)
+    }
+
+    /// Creates the name without validating it, used for names that were validated before being stored.
+    pub(crate) fn new_unchecked(name: String) -> Self {
+        Self(name)
+    }
+
     /// Validates the apex domain name and returns an error if it is invalid.
     fn validate(domain_name: &str) -> Result<(), ZoneApexDomainError> {
         if domain_name.is_empty() {
@@ -108,7 +124,7 @@ impl BoundedStorable for ZoneApexDomain {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::max_domain_name;
+    use crate::{errors::InternationalizedNameError, utils::max_domain_name};
     use rstest::rstest;
 
     #[rstest]
@@ -122,6 +138,17 @@ mod tests {
         assert!(apex_domain.is_ok());
     }
 
+    #[rstest]
+    #[case::unicode_domain_name(&"café.tld.", &"xn--caf-dma.tld.")]
+    #[case::uppercase_unicode_domain_name(&"CAFÉ.Tld.", &"xn--caf-dma.tld.")]
+    #[case::a_label_domain_name(&"xn--caf-dma.tld.", &"xn--caf-dma.tld.")]
+    fn apex_domain_internationalized_name(#[case] domain_name: &str, #[case] ascii_name: &str) {
+        let apex_domain = ZoneApexDomain::new(String::from(domain_name)).unwrap();
+
+        assert_eq!(apex_domain.deref(), ascii_name);
+        assert_eq!(apex_domain.to_unicode(), "café.tld.");
+    }
+
     #[rstest]
     #[case::empty_domain(&"", ZoneApexDomainError::NonEmptyDomain)]
     #[case::empty_label(&".tld.", ZoneApexDomainError::NonEmptyLabel)]
@@ -130,6 +157,8 @@ mod tests {
     #[case::invalid_hyphen_start(&"-test.tld.", ZoneApexDomainError::MisplacedHyphen)]
     #[case::invalid_hyphen_end(&"test-.tld.", ZoneApexDomainError::MisplacedHyphen)]
     #[case::invalid_name_start_with_underscore(&"_test.tld.", ZoneApexDomainError::InvalidDomainNameLabel { label: String::from("_test") })]
+    #[case::disallowed_code_point(&"i❤.tld.", ZoneApexDomainError::InvalidInternationalizedName(InternationalizedNameError::DisallowedCodePoint { label: String::from("i❤"), code_point: '❤' }))]
+    #[case::bidi_rule(&"aשלום.tld.", ZoneApexDomainError::InvalidInternationalizedName(InternationalizedNameError::BidiRule { label: String::from("aשלום") }))]
     // add test for valid domain name but with length > 255
     fn apex_domain_validation_fail(
         #[case] domain_name: &str,
@@ -5,33 +5,239 @@ use crate::{
     },
 };
 use ic_stable_structures::{BoundedStorable, Storable};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 
-/// In order to store a DomainZone in stable memory, we need to store the DomainZone and the DomainRecord as
-/// a tuple, this enables more efficient lookup and update operations when accessing stable memory since
-/// the BTreeMap is transformed into a Set and the tuple is used as a composite key.
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+/// A record of a zone, which is stored in stable memory as a [DomainZoneKey] that identifies the record and a
+/// [DomainZoneValue] with the rest of its fields.
+///
+/// Entries are sorted in the order of their keys, which is the canonical order of RFC 4034.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DomainZoneEntry((DomainZone, DomainRecord));
 
 impl DomainZoneEntry {
     pub fn new(zone: DomainZone, record: DomainRecord) -> Self {
         Self((zone, record))
     }
+
+    /// The zone that the record belongs to.
+    pub fn zone(&self) -> &DomainZone {
+        &self.0 .0
+    }
+
+    /// The record that is part of the zone.
+    pub fn record(&self) -> &DomainRecord {
+        &self.0 .1
+    }
+
+    /// Returns the key that identifies the record in stable memory.
+    pub fn key(&self) -> DomainZoneKey {
+        let record = self.record();
+
+        DomainZoneKey::from_parts(
+            &self.zone().name,
+            Some(&record.name),
+            Some(&record.record_type),
+            Some(&record.data),
+        )
+    }
+
+    /// Returns the fields of the record that are stored next to its key.
+    pub fn value(&self) -> DomainZoneValue {
+        DomainZoneValue {
+            ttl: self.record().ttl,
+            data: self.record().data.clone(),
+        }
+    }
+
+    /// Rebuilds the entry from the key and value stored in stable memory.
+    pub fn from_key_value(key: &DomainZoneKey, value: DomainZoneValue) -> Self {
+        let (apex_domain, record_name, record_type) = key.decode();
+
+        Self::new(
+            DomainZone::new(apex_domain),
+            DomainRecord::new(record_name, record_type, value.ttl, value.data),
+        )
+    }
+}
+
+impl Ord for DomainZoneEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key()
+            .cmp(&other.key())
+            .then_with(|| self.record().ttl.cmp(&other.record().ttl))
+    }
+}
+
+impl PartialOrd for DomainZoneEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The order preserving binary key of a record in stable memory, the byte order of the keys is the sort order of
+/// the records, which makes every search a range scan over the keys.
+///
+/// The key is made of the labels of the apex domain and of the record name from right to left and the record
+/// type, each label and field ends with a zero byte and the names with an empty label, e.g. "mydomain.tld." and
+/// "a.wiki" result in `tld\0mydomain\0\0wiki\0a\0\0TXT\0`. As names are stored lowercased, the records are
+/// sorted in the canonical order of RFC 4034 with the names below a name right after it. The key ends with the
+/// SHA-256 digest of the record data to tell apart the records of an RRset without storing their data twice.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DomainZoneKey(Vec<u8>);
+
+impl DomainZoneKey {
+    /// The byte that ends each label and field of the key.
+    pub(crate) const TERMINATOR: u8 = 0;
+
+    /// The size of the digest of the record data.
+    const DATA_DIGEST_SIZE: u32 = 32;
+
+    /// The maximum byte size of a key, each field takes at most one byte more than its maximum size since the
+    /// record name is at most 253 bytes long to fit in a fully qualified name.
+    pub const MAX_SIZE: u32 = (DomainZone::FIELD_NAME_BYTE_SIZE + 1)
+        + (DomainRecord::FIELD_NAME_BYTE_SIZE + 1)
+        + (DomainRecord::FIELD_RECORD_TYPE_BYTE_SIZE + 1)
+        + Self::DATA_DIGEST_SIZE;
+
+    /// Builds the key of a record, or the prefix shared by the keys of the records that match the given fields
+    /// when the trailing ones are missing.
+    pub fn from_parts(
+        apex_domain: &ZoneApexDomain,
+        record_name: Option<&RecordName>,
+        record_type: Option<&str>,
+        data: Option<&str>,
+    ) -> Self {
+        let mut key = Self::subtree_prefix(apex_domain, None);
+        let Some(record_name) = record_name else {
+            return key;
+        };
+        key = Self::subtree_prefix(apex_domain, Some(record_name));
+        key.0.push(Self::TERMINATOR);
+
+        let Some(record_type) = record_type else {
+            return key;
+        };
+        key.0.extend_from_slice(record_type.as_bytes());
+        key.0.push(Self::TERMINATOR);
+
+        if let Some(data) = data {
+            key.0.extend_from_slice(&Sha256::digest(data.as_bytes()));
+        }
+
+        key
+    }
+
+    /// Builds the prefix shared by the keys of the records at or below the record name, or of all the records of
+    /// the zone when no record name is given.
+    pub fn subtree_prefix(apex_domain: &ZoneApexDomain, record_name: Option<&RecordName>) -> Self {
+        let mut bytes = Vec::new();
+        Self::push_labels(&mut bytes, apex_domain);
+        bytes.push(Self::TERMINATOR);
+
+        if let Some(record_name) = record_name.filter(|record_name| !record_name.is_apex()) {
+            Self::push_labels(&mut bytes, record_name);
+        }
+
+        Self(bytes)
+    }
+
+    /// Returns the greatest key that starts with this key, used as the upper bound of a range scan.
+    pub fn prefix_upper_bound(&self) -> Self {
+        let mut bytes = self.0.clone();
+        bytes.extend_from_slice(&[u8::MAX; Self::DATA_DIGEST_SIZE as usize]);
+
+        Self(bytes)
+    }
+
+    /// Appends the labels of the name from right to left, each followed by the terminator, which sorts the names
+    /// in the canonical order of RFC 4034 and keeps the names below a name next to it.
+    fn push_labels(bytes: &mut Vec<u8>, name: &str) {
+        for label in name.split('.').rev().filter(|label| !label.is_empty()) {
+            bytes.extend_from_slice(label.as_bytes());
+            bytes.push(Self::TERMINATOR);
+        }
+    }
+
+    /// Reads the labels of a name up to the empty label that ends it and returns them from left to right.
+    fn read_labels<'a>(fields: &mut impl Iterator<Item = &'a [u8]>) -> Vec<String> {
+        let mut labels = fields
+            .take_while(|label| !label.is_empty())
+            .map(|label| String::from_utf8_lossy(label).to_string())
+            .collect::<Vec<_>>();
+        labels.reverse();
+
+        labels
+    }
+
+    /// Decodes the apex domain, record name and record type of the key.
+    fn decode(&self) -> (ZoneApexDomain, RecordName, String) {
+        let mut fields = self.0.split(|byte| *byte == Self::TERMINATOR);
+        let apex_domain =
+            ZoneApexDomain::new_unchecked(format!("{}.", Self::read_labels(&mut fields).join(".")));
+        let record_name = match Self::read_labels(&mut fields) {
+            labels if labels.is_empty() => RecordName::default(),
+            labels => RecordName::new_unchecked(labels.join(".")),
+        };
+        let record_type = String::from_utf8_lossy(fields.next().unwrap_or_default()).to_string();
+
+        (apex_domain, record_name, record_type)
+    }
+}
+
+/// Adds serialization and deserialization support to DomainZoneKey to stable memory, the key is stored as is.
+impl Storable for DomainZoneKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Self(bytes.into_owned())
+    }
+}
+
+/// Represents the memory required to store a DomainZoneKey in stable memory.
+impl BoundedStorable for DomainZoneKey {
+    const MAX_SIZE: u32 = DomainZoneKey::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// The fields of a record that are not part of its key.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DomainZoneValue {
+    /// The time to live of the record.
+    pub ttl: u32,
+    /// The data of the record.
+    pub data: String,
+}
+
+impl DomainZoneValue {
+    /// The maximum byte size of a value, the ttl takes 4 bytes followed by the data.
+    pub const MAX_SIZE: u32 =
+        DomainRecord::FIELD_TTL_BYTE_SIZE + DomainRecord::FIELD_DATA_BYTE_SIZE;
 }
 
-// Adds serialization and deserialization support to DomainZone to stable memory.
-impl Storable for DomainZoneEntry {
+/// Adds serialization and deserialization support to DomainZoneValue to stable memory, the ttl is stored as
+/// big endian bytes followed by the data.
+impl Storable for DomainZoneValue {
     fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
-        self.0.to_bytes()
+        Cow::Owned([self.ttl.to_be_bytes().as_slice(), self.data.as_bytes()].concat())
     }
 
     fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
-        Self(<(DomainZone, DomainRecord)>::from_bytes(bytes))
+        let (ttl, data) = bytes.split_at(DomainRecord::FIELD_TTL_BYTE_SIZE as usize);
+
+        Self {
+            ttl: u32::from_be_bytes(ttl.try_into().unwrap()),
+            data: String::from_utf8_lossy(data).to_string(),
+        }
     }
 }
 
-/// Represents the memory required to store a DomainZone in stable memory.
-impl BoundedStorable for DomainZoneEntry {
-    const MAX_SIZE: u32 = <(DomainZone, DomainRecord)>::MAX_SIZE;
+/// Represents the memory required to store a DomainZoneValue in stable memory.
+impl BoundedStorable for DomainZoneValue {
+    const MAX_SIZE: u32 = DomainZoneValue::MAX_SIZE;
 
     const IS_FIXED_SIZE: bool = false;
 }
@@ -45,58 +251,39 @@ impl DomainZoneEntryInput {
     pub fn new(zone: DomainZoneInput, record: DomainRecordInput) -> Self {
         Self(zone, record)
     }
-}
 
-impl RepositorySearchInto<DomainZoneEntry> for DomainZoneEntryInput {
-    fn map_to_lower_range_key(&self) -> Result<DomainZoneEntry, String> {
-        let zone = self.0.clone();
-        let record = self.1.clone();
-        let apex_domain = match zone.name {
-            Some(name) => ZoneApexDomain::new(name),
-            _ => Ok(ZoneApexDomain::default()),
-        }
-        .map_err(|e| e.to_string())?;
+    /// Returns the key prefix shared by the records that match the input, the fields after the first missing
+    /// one are ignored and the ttl is not part of the key.
+    fn key_prefix(&self) -> Result<Option<DomainZoneKey>, String> {
+        let Some(apex_domain) = &self.0.name else {
+            return Ok(None);
+        };
+        let apex_domain = ZoneApexDomain::new(apex_domain.clone()).map_err(|e| e.to_string())?;
+        let record_name = match &self.1.name {
+            Some(name) => {
+                Some(RecordName::new(name.clone(), &apex_domain).map_err(|e| e.to_string())?)
+            }
+            None => None,
+        };
+        let record_type = record_name.as_ref().and(self.1.record_type.as_deref());
+        let data = record_type.and(self.1.data.as_deref());
 
-        Ok(DomainZoneEntry((
-            DomainZone {
-                name: apex_domain.clone(),
-            },
-            DomainRecord {
-                name: match record.name {
-                    Some(name) => RecordName::new(name, &apex_domain).unwrap(),
-                    _ => RecordName::default(),
-                },
-                record_type: record.record_type.unwrap_or_default(),
-                ttl: record.ttl.unwrap_or(0),
-                data: record.data.unwrap_or_default(),
-            },
+        Ok(Some(DomainZoneKey::from_parts(
+            &apex_domain,
+            record_name.as_ref(),
+            record_type,
+            data,
         )))
     }
+}
 
-    fn map_to_upper_range_key(&self) -> Result<DomainZoneEntry, String> {
-        let zone = self.0.clone();
-        let record = self.1.clone();
-        let apex_domain = match zone.name {
-            Some(name) => ZoneApexDomain::new(name).map_err(|e| e.to_string())?,
-            _ => ZoneApexDomain::new(".".to_string()).map_err(|e| e.to_string())?,
-        };
+impl RepositorySearchInto<DomainZoneKey> for DomainZoneEntryInput {
+    fn map_to_lower_range_key(&self) -> Result<DomainZoneKey, String> {
+        Ok(self.key_prefix()?.unwrap_or_default())
+    }
 
-        Ok(DomainZoneEntry((
-            DomainZone {
-                name: apex_domain.clone(),
-            },
-            DomainRecord {
-                name: match record.name {
-                    Some(name) => RecordName::new(name, &apex_domain).unwrap(),
-                    _ => DomainRecord::max_record_name_value(Some(apex_domain)),
-                },
-                record_type: record
-                    .record_type
-                    .unwrap_or(DomainRecord::max_record_type_value()),
-                ttl: record.ttl.unwrap_or(DomainRecord::max_ttl_value()),
-                data: record.data.unwrap_or(DomainRecord::max_data_value()),
-            },
-        )))
+    fn map_to_upper_range_key(&self) -> Result<DomainZoneKey, String> {
+        Ok(self.key_prefix()?.unwrap_or_default().prefix_upper_bound())
     }
 }
 
@@ -105,33 +292,90 @@ mod tests {
     use super::*;
     use crate::types::{RecordName, ZoneApexDomain};
 
+    fn entry(apex_domain: &str, name: &str, record_type: &str, data: &str) -> DomainZoneEntry {
+        let apex_domain = ZoneApexDomain::new(String::from(apex_domain)).unwrap();
+        let record_name = RecordName::new(String::from(name), &apex_domain).unwrap();
+
+        DomainZoneEntry::new(
+            DomainZone::new(apex_domain),
+            DomainRecord::new(
+                record_name,
+                String::from(record_type),
+                3600,
+                String::from(data),
+            ),
+        )
+    }
+
     #[test]
     fn deserialization_for_domain_zone_entry_match() {
-        let zone_apex_domain = ZoneApexDomain::new(String::from("internetcomputer.icp.")).unwrap();
-        let record_name = RecordName::new(String::from("@"), &zone_apex_domain).unwrap();
-        let domain_zone_entry = DomainZoneEntry::new(
-            DomainZone {
-                name: zone_apex_domain,
-            },
-            DomainRecord {
-                name: record_name,
-                record_type: "A".to_string(),
-                ttl: 3600,
-                data: String::default(),
-            },
-        )
-        .0;
+        let domain_zone_entry = entry("internetcomputer.icp.", "@", "A", "192.0.2.1");
 
-        let serialized_domain_zone = domain_zone_entry.to_bytes();
-        let deserialized_domain_zone = DomainZoneEntry::from_bytes(serialized_domain_zone).0;
+        let key = DomainZoneKey::from_bytes(domain_zone_entry.key().to_bytes());
+        let value = DomainZoneValue::from_bytes(domain_zone_entry.value().to_bytes());
 
-        assert_eq!(domain_zone_entry.0.name, deserialized_domain_zone.0.name);
-        assert_eq!(domain_zone_entry.1.name, deserialized_domain_zone.1.name);
+        assert_eq!(key, domain_zone_entry.key());
         assert_eq!(
-            domain_zone_entry.1.record_type,
-            deserialized_domain_zone.1.record_type
+            DomainZoneEntry::from_key_value(&key, value),
+            domain_zone_entry
         );
-        assert_eq!(domain_zone_entry.1.ttl, deserialized_domain_zone.1.ttl);
-        assert_eq!(domain_zone_entry.1.data, deserialized_domain_zone.1.data);
+    }
+
+    #[test]
+    fn domain_zone_keys_are_order_preserving() {
+        let keys = [
+            entry("a.other.", "@", "TXT", "other"),
+            entry("tld.", "@", "TXT", "apex"),
+            entry("tld.", "wiki", "TXT", "wiki"),
+            entry("a.tld.", "@", "A", "192.0.2.1"),
+            entry("a.tld.", "@", "AAAA", "2001:db8::1"),
+            entry("a.tld.", "b", "TXT", "b"),
+            entry("a.tld.", "*.b", "TXT", "*.b"),
+            entry("a.tld.", "a.b", "TXT", "a.b"),
+            entry("a.tld.", "z.a.b", "TXT", "z.a.b"),
+            entry("a.tld.", "bb", "TXT", "bb"),
+            entry("a.tld.", "a.z", "TXT", "a.z"),
+            entry("b.a.tld.", "@", "TXT", "b"),
+            entry("ab.tld.", "@", "TXT", "ab"),
+        ]
+        .map(|entry| entry.key());
+
+        assert!(keys.windows(2).all(|keys| keys[0] < keys[1]));
+    }
+
+    #[test]
+    fn search_input_maps_to_key_prefix_range() {
+        let input = DomainZoneEntryInput::new(
+            DomainZoneInput {
+                name: Some(String::from("a.tld.")),
+            },
+            DomainRecordInput::default(),
+        );
+        let lower = input.map_to_lower_range_key().unwrap();
+        let upper = input.map_to_upper_range_key().unwrap();
+        let in_range = |entry: DomainZoneEntry| lower <= entry.key() && entry.key() <= upper;
+
+        assert!(in_range(entry("a.tld.", "@", "A", "192.0.2.1")));
+        assert!(in_range(entry("a.tld.", "zzz", "TXT", "zzz")));
+        assert!(!in_range(entry("b.a.tld.", "@", "TXT", "b")));
+        assert!(!in_range(entry("ab.tld.", "@", "TXT", "ab")));
+        assert!(!in_range(entry("tld.", "a", "TXT", "a")));
+    }
+
+    #[test]
+    fn subtree_prefix_matches_names_at_or_below() {
+        let apex_domain = ZoneApexDomain::new(String::from("a.tld.")).unwrap();
+        let record_name = RecordName::new(String::from("b"), &apex_domain).unwrap();
+        let lower = DomainZoneKey::subtree_prefix(&apex_domain, Some(&record_name));
+        let upper = lower.prefix_upper_bound();
+        let in_range = |entry: DomainZoneEntry| lower <= entry.key() && entry.key() <= upper;
+
+        assert!(in_range(entry("a.tld.", "b", "TXT", "b")));
+        assert!(in_range(entry("a.tld.", "*.b", "TXT", "*.b")));
+        assert!(in_range(entry("a.tld.", "c.a.b", "TXT", "c.a.b")));
+        assert!(!in_range(entry("a.tld.", "@", "TXT", "apex")));
+        assert!(!in_range(entry("a.tld.", "bb", "TXT", "bb")));
+        assert!(!in_range(entry("a.tld.", "b.c", "TXT", "b.c")));
+        assert!(!in_range(entry("b.a.tld.", "@", "TXT", "b")));
     }
 }
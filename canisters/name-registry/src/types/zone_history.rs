@@ -0,0 +1,346 @@
+use crate::types::{DomainRecord, DomainZone, DomainZoneKey, RecordName, ZoneApexDomain};
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{BoundedStorable, Storable};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+
+/// The operation of `manage_records` that changed an RRset of a zone.
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ZoneHistoryOperation {
+    /// Records were appended to the RRset.
+    Append,
+    /// The RRset was replaced, which is also the case when the serial of the SOA record is incremented.
+    Override,
+    /// Records were removed from the RRset.
+    Remove,
+}
+
+/// A change made to an RRset of a zone, the history of a zone is the list of its changes in the order they
+/// were made.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ZoneHistoryEntry {
+    /// The apex domain of the zone.
+    pub zone: ZoneApexDomain,
+    /// The position of the change in the history of the zone, starting at 0.
+    pub sequence: u64,
+    /// The time of the change in nanoseconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// The principal that made the change.
+    pub caller: Principal,
+    /// The operation that made the change.
+    pub operation: ZoneHistoryOperation,
+    /// The name of the changed RRset.
+    pub name: RecordName,
+    /// The record type of the changed RRset.
+    pub record_type: String,
+    /// The records of the RRset before the change, empty if the RRset was created.
+    pub before: Vec<DomainRecord>,
+    /// The records of the RRset after the change, empty if the RRset was removed.
+    pub after: Vec<DomainRecord>,
+}
+
+impl ZoneHistoryEntry {
+    /// Returns the items that the entry is stored as, the change itself followed by the records of the RRset
+    /// before and after the change.
+    pub fn items(&self) -> Vec<(ZoneHistoryKey, ZoneHistoryItem)> {
+        let change = ZoneHistoryItem::Change {
+            timestamp: self.timestamp,
+            caller: self.caller,
+            operation: self.operation,
+            name: self.name.clone(),
+            record_type: self.record_type.clone(),
+        };
+        let before = self.before.iter().map(|record| ZoneHistoryItem::Before {
+            ttl: record.ttl,
+            data: record.data.clone(),
+        });
+        let after = self.after.iter().map(|record| ZoneHistoryItem::After {
+            ttl: record.ttl,
+            data: record.data.clone(),
+        });
+
+        std::iter::once(change)
+            .chain(before)
+            .chain(after)
+            .map(|item| (ZoneHistoryKey::new(&self.zone, self.sequence, &item), item))
+            .collect()
+    }
+
+    /// Rebuilds the entry from its stored items, returns `None` if the change itself is missing.
+    pub fn from_items(
+        zone: ZoneApexDomain,
+        sequence: u64,
+        items: impl IntoIterator<Item = ZoneHistoryItem>,
+    ) -> Option<Self> {
+        let mut entry: Option<Self> = None;
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        for item in items {
+            match item {
+                ZoneHistoryItem::Change {
+                    timestamp,
+                    caller,
+                    operation,
+                    name,
+                    record_type,
+                } => {
+                    entry = Some(Self {
+                        zone: zone.clone(),
+                        sequence,
+                        timestamp,
+                        caller,
+                        operation,
+                        name,
+                        record_type,
+                        before: Vec::new(),
+                        after: Vec::new(),
+                    })
+                }
+                ZoneHistoryItem::Before { ttl, data } => before.push((ttl, data)),
+                ZoneHistoryItem::After { ttl, data } => after.push((ttl, data)),
+            }
+        }
+
+        entry.map(|entry| {
+            let records = |records: Vec<(u32, String)>| {
+                records
+                    .into_iter()
+                    .map(|(ttl, data)| {
+                        DomainRecord::new(entry.name.clone(), entry.record_type.clone(), ttl, data)
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let (before, after) = (records(before), records(after));
+
+            Self {
+                before,
+                after,
+                ..entry
+            }
+        })
+    }
+}
+
+/// An item of a stored change, a change is stored as several items to keep the size of each of them bounded
+/// regardless of the number of records of the changed RRset.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum ZoneHistoryItem {
+    /// The change itself.
+    Change {
+        timestamp: u64,
+        caller: Principal,
+        operation: ZoneHistoryOperation,
+        name: RecordName,
+        record_type: String,
+    },
+    /// A record of the RRset before the change.
+    Before { ttl: u32, data: String },
+    /// A record of the RRset after the change.
+    After { ttl: u32, data: String },
+}
+
+impl ZoneHistoryItem {
+    /// The maximum byte size of an item, the largest item is a record with the maximum data size and the
+    /// remaining space is reserved for the candid type table.
+    pub const MAX_SIZE: u32 = DomainRecord::MAX_SIZE + 256;
+
+    /// Returns the byte that sorts the item after the change and the items of the same kind next to each other.
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Change { .. } => 0,
+            Self::Before { .. } => 1,
+            Self::After { .. } => 2,
+        }
+    }
+}
+
+/// Adds serialization and deserialization support to ZoneHistoryItem to stable memory.
+impl Storable for ZoneHistoryItem {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+/// Represents the memory required to store a ZoneHistoryItem in stable memory.
+impl BoundedStorable for ZoneHistoryItem {
+    const MAX_SIZE: u32 = ZoneHistoryItem::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// The order preserving binary key of an item of a stored change.
+///
+/// The key starts with the same zone prefix as [DomainZoneKey] followed by the big endian sequence of the change,
+/// the tag of the item and, for records, the SHA-256 digest of their data. The changes of a zone are hence stored
+/// next to each other in the order they were made.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ZoneHistoryKey(Vec<u8>);
+
+impl ZoneHistoryKey {
+    /// The size of the digest of the record data.
+    const DATA_DIGEST_SIZE: u32 = 32;
+
+    /// The maximum byte size of a key.
+    pub const MAX_SIZE: u32 =
+        (DomainZone::FIELD_NAME_BYTE_SIZE + 1) + u64::BITS / 8 + 1 + Self::DATA_DIGEST_SIZE;
+
+    /// Builds the key of an item of the change with the given sequence.
+    pub fn new(apex_domain: &ZoneApexDomain, sequence: u64, item: &ZoneHistoryItem) -> Self {
+        let mut key = Self::prefix(apex_domain, Some(sequence));
+        key.0.push(item.tag());
+        match item {
+            ZoneHistoryItem::Change { .. } => {}
+            ZoneHistoryItem::Before { data, .. } | ZoneHistoryItem::After { data, .. } => {
+                key.0.extend_from_slice(&Sha256::digest(data.as_bytes()))
+            }
+        }
+
+        key
+    }
+
+    /// Builds the prefix shared by the keys of the items of the change with the given sequence, or of all the
+    /// changes of the zone when no sequence is given.
+    pub fn prefix(apex_domain: &ZoneApexDomain, sequence: Option<u64>) -> Self {
+        let mut bytes = DomainZoneKey::subtree_prefix(apex_domain, None)
+            .to_bytes()
+            .into_owned();
+        if let Some(sequence) = sequence {
+            bytes.extend_from_slice(&sequence.to_be_bytes());
+        }
+
+        Self(bytes)
+    }
+
+    /// Returns the greatest key that starts with this key, used as the upper bound of a range scan.
+    pub fn prefix_upper_bound(&self) -> Self {
+        let mut bytes = self.0.clone();
+        bytes.extend_from_slice(&[u8::MAX; Self::DATA_DIGEST_SIZE as usize + 1]);
+
+        Self(bytes)
+    }
+
+    /// Returns the sequence of the change that the item belongs to.
+    pub fn sequence(&self) -> u64 {
+        // the zone prefix ends with the first empty label
+        let mut offset = 0;
+        while let Some(label_length) = self.0[offset..]
+            .iter()
+            .position(|byte| *byte == DomainZoneKey::TERMINATOR)
+        {
+            offset += label_length + 1;
+            if label_length == 0 {
+                break;
+            }
+        }
+
+        self.0
+            .get(offset..offset + 8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map_or(0, u64::from_be_bytes)
+    }
+}
+
+/// Adds serialization and deserialization support to ZoneHistoryKey to stable memory, the key is stored as is.
+impl Storable for ZoneHistoryKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Self(bytes.into_owned())
+    }
+}
+
+/// Represents the memory required to store a ZoneHistoryKey in stable memory.
+impl BoundedStorable for ZoneHistoryKey {
+    const MAX_SIZE: u32 = ZoneHistoryKey::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(apex_domain: &str, sequence: u64) -> ZoneHistoryEntry {
+        let zone = ZoneApexDomain::new(String::from(apex_domain)).unwrap();
+        let name = RecordName::new(String::from("wiki"), &zone).unwrap();
+        let record = |data: &str| {
+            DomainRecord::new(name.clone(), String::from("TXT"), 60, String::from(data))
+        };
+
+        ZoneHistoryEntry {
+            zone: zone.clone(),
+            sequence,
+            timestamp: 1_700_000_000_000_000_000,
+            caller: Principal::from_slice(&[1; 29]),
+            operation: ZoneHistoryOperation::Override,
+            name: name.clone(),
+            record_type: String::from("TXT"),
+            before: vec![record("first")],
+            after: vec![record("second"), record("third")],
+        }
+    }
+
+    #[test]
+    fn zone_history_entry_round_trip() {
+        let entry = entry("history.tld.", 7);
+        let items = entry.items();
+
+        assert_eq!(items.len(), 4);
+        assert!(items.iter().all(|(key, _)| key.sequence() == 7));
+
+        let mut stored_items = items
+            .into_iter()
+            .map(|(key, item)| {
+                (
+                    ZoneHistoryKey::from_bytes(key.to_bytes()),
+                    ZoneHistoryItem::from_bytes(item.to_bytes()),
+                )
+            })
+            .collect::<Vec<_>>();
+        stored_items.sort_by(|left, right| left.0.cmp(&right.0));
+        let mut rebuilt = ZoneHistoryEntry::from_items(
+            entry.zone.clone(),
+            7,
+            stored_items.into_iter().map(|(_, item)| item),
+        )
+        .unwrap();
+        rebuilt.after.sort();
+
+        assert_eq!(rebuilt, entry);
+    }
+
+    #[test]
+    fn zone_history_keys_are_grouped_by_zone_and_sequence() {
+        let zone = ZoneApexDomain::new(String::from("a.tld.")).unwrap();
+        let lower = ZoneHistoryKey::prefix(&zone, Some(1));
+        let upper = lower.prefix_upper_bound();
+        let in_range = |entry: ZoneHistoryEntry| {
+            entry
+                .items()
+                .iter()
+                .all(|(key, _)| &lower <= key && key <= &upper)
+        };
+
+        assert!(in_range(entry("a.tld.", 1)));
+        assert!(!in_range(entry("a.tld.", 0)));
+        assert!(!in_range(entry("a.tld.", 2)));
+        assert!(!in_range(entry("b.a.tld.", 1)));
+        assert!(entry("a.tld.", 255).items()[0].0 < entry("a.tld.", 256).items()[0].0);
+    }
+
+    #[test]
+    fn max_size_fits_largest_item() {
+        let item = ZoneHistoryItem::After {
+            ttl: u32::MAX,
+            data: "z".repeat(DomainRecord::FIELD_DATA_BYTE_SIZE as usize),
+        };
+
+        assert!(item.to_bytes().len() <= ZoneHistoryItem::MAX_SIZE as usize);
+    }
+}
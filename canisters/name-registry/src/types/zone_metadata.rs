@@ -0,0 +1,117 @@
+use crate::types::{RegistrationController, ZoneApexDomain};
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{BoundedStorable, Storable};
+use std::borrow::Cow;
+
+/// Holds the information of a domain zone that is not part of its records, such as who controls it.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct DomainZoneMetadata {
+    /// The apex domain of the zone.
+    pub name: ZoneApexDomain,
+    /// The principals that control the zone and their roles.
+    pub controllers: Vec<RegistrationController>,
+}
+
+impl DomainZoneMetadata {
+    /// The maximum number of controllers a zone can have.
+    pub const MAX_CONTROLLERS: usize = 10;
+
+    /// The maximum byte size of a DomainZoneMetadata.
+    ///
+    /// Represents the memory required to store a DomainZoneMetadata in stable memory, each controller takes
+    /// less than 64 bytes and the remaining space is reserved for the zone name and the candid type table.
+    pub const MAX_SIZE: u32 = 1024;
+
+    pub fn new(name: ZoneApexDomain, controllers: Vec<RegistrationController>) -> Self {
+        Self { name, controllers }
+    }
+
+    /// Returns `true` if the principal is one of the controllers of the zone.
+    pub fn is_controller(&self, principal: &Principal) -> bool {
+        self.controllers
+            .iter()
+            .any(|controller| &controller.controller_id == principal)
+    }
+
+    /// Returns `true` if the principal controls the zone with a role that allows changing its records.
+    pub fn can_manage_records(&self, principal: &Principal) -> bool {
+        self.controllers.iter().any(|controller| {
+            &controller.controller_id == principal
+                && controller
+                    .roles
+                    .iter()
+                    .any(|role| role.can_manage_records())
+        })
+    }
+}
+
+/// Adds serialization and deserialization support to DomainZoneMetadata to stable memory.
+impl Storable for DomainZoneMetadata {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+/// Represents the memory required to store a DomainZoneMetadata in stable memory.
+impl BoundedStorable for DomainZoneMetadata {
+    const MAX_SIZE: u32 = DomainZoneMetadata::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RegistrationControllerRole;
+    use crate::utils::max_domain_name;
+
+    #[test]
+    fn max_size_fits_max_controllers() {
+        let metadata = DomainZoneMetadata::new(
+            ZoneApexDomain::new(max_domain_name()).unwrap(),
+            (0..DomainZoneMetadata::MAX_CONTROLLERS)
+                .map(|index| {
+                    RegistrationController::new(
+                        Principal::from_slice(&[index as u8; 29]),
+                        vec![
+                            RegistrationControllerRole::Registrar,
+                            RegistrationControllerRole::Registrant,
+                            RegistrationControllerRole::Technical,
+                            RegistrationControllerRole::Administrative,
+                        ],
+                    )
+                })
+                .collect(),
+        );
+
+        let bytes = metadata.to_bytes();
+
+        assert!(bytes.len() <= DomainZoneMetadata::MAX_SIZE as usize);
+        assert_eq!(DomainZoneMetadata::from_bytes(bytes), metadata);
+    }
+
+    #[test]
+    fn controllers_can_manage_records_by_role() {
+        let technical = Principal::from_slice(&[1; 29]);
+        let administrative = Principal::from_slice(&[2; 29]);
+        let metadata = DomainZoneMetadata::new(
+            ZoneApexDomain::new(String::from("internetcomputer.tld.")).unwrap(),
+            vec![
+                RegistrationController::new(technical, vec![RegistrationControllerRole::Technical]),
+                RegistrationController::new(
+                    administrative,
+                    vec![RegistrationControllerRole::Administrative],
+                ),
+            ],
+        );
+
+        assert!(metadata.can_manage_records(&technical));
+        assert!(!metadata.can_manage_records(&administrative));
+        assert!(metadata.is_controller(&administrative));
+        assert!(!metadata.is_controller(&Principal::anonymous()));
+    }
+}
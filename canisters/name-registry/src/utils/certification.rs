@@ -0,0 +1,70 @@
+use crate::api::DomainRecord;
+use sha2::{Digest, Sha256};
+
+/// Hashes an RRset, which are the records with the same name and type, to be stored in the certified records tree.
+///
+/// Each record is hashed independently and the record hashes are sorted before being combined, which makes the
+/// resulting hash independent of the order in which the records are returned.
+pub fn rrset_hash(records: &[DomainRecord]) -> [u8; 32] {
+    let mut record_hashes = records.iter().map(record_hash).collect::<Vec<_>>();
+    record_hashes.sort();
+
+    let mut hasher = Sha256::new();
+    for record_hash in record_hashes {
+        hasher.update(record_hash);
+    }
+
+    hasher.finalize().into()
+}
+
+/// Hashes the fields of a record, each field is prefixed by its length to avoid ambiguous concatenations.
+fn record_hash(record: &DomainRecord) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for field in [
+        record.name.as_bytes(),
+        record.record_type.as_bytes(),
+        &record.ttl.0.to_bytes_be(),
+        record.data.as_bytes(),
+    ] {
+        hasher.update((field.len() as u64).to_be_bytes());
+        hasher.update(field);
+    }
+
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Nat;
+
+    fn record(ttl: u32, data: &str) -> DomainRecord {
+        DomainRecord {
+            name: String::from("mydomain.tld."),
+            record_type: String::from("TXT"),
+            ttl: Nat::from(ttl),
+            data: String::from(data),
+        }
+    }
+
+    #[test]
+    fn rrset_hash_is_independent_of_record_order() {
+        assert_eq!(
+            rrset_hash(&[record(60, "first"), record(60, "second")]),
+            rrset_hash(&[record(60, "second"), record(60, "first")])
+        );
+    }
+
+    #[test]
+    fn rrset_hash_changes_with_record_fields() {
+        let hash = rrset_hash(&[record(60, "first")]);
+
+        assert_ne!(hash, rrset_hash(&[record(61, "first")]));
+        assert_ne!(hash, rrset_hash(&[record(60, "firs")]));
+        assert_ne!(
+            hash,
+            rrset_hash(&[record(60, "first"), record(60, "first")])
+        );
+        assert_ne!(hash, rrset_hash(&[]));
+    }
+}
@@ -0,0 +1,11 @@
+/// Encodes the stable memory representation of the last item of a page into an opaque pagination cursor.
+pub fn encode_cursor(bytes: &[u8]) -> String {
+    hex::encode(bytes)
+}
+
+/// Decodes an opaque pagination cursor back into the bytes of the item it points to.
+///
+/// Returns `None` if the cursor is malformed.
+pub fn decode_cursor(cursor: &str) -> Option<Vec<u8>> {
+    hex::decode(cursor).ok()
+}
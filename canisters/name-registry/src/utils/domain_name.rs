@@ -14,3 +14,44 @@ pub fn max_domain_label() -> String {
 pub fn repeat_char(c: char, times: usize) -> String {
     std::iter::repeat(c).take(times).collect::<String>()
 }
+
+/// Returns the possible splits of a fully qualified domain name into a record name and its parent domain.
+///
+/// The splits are ordered from the most specific parent domain to the least specific one, e.g. "wiki.mydomain.tld."
+/// results in `("@", "wiki.mydomain.tld.")`, `("wiki", "mydomain.tld.")` and `("wiki.mydomain", "tld.")`.
+pub fn domain_name_splits(domain: &str) -> Vec<(String, String)> {
+    if !domain.ends_with('.') || domain.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut splits = vec![(String::from("@"), domain.to_string())];
+    for (index, _) in domain[..domain.len() - 1].match_indices('.') {
+        splits.push((domain[..index].to_string(), domain[index + 1..].to_string()));
+    }
+
+    splits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domain_name_splits_from_most_specific_parent() {
+        assert_eq!(
+            domain_name_splits("wiki.mydomain.tld."),
+            vec![
+                (String::from("@"), String::from("wiki.mydomain.tld.")),
+                (String::from("wiki"), String::from("mydomain.tld.")),
+                (String::from("wiki.mydomain"), String::from("tld.")),
+            ]
+        );
+    }
+
+    #[test]
+    fn domain_name_splits_empty_for_invalid_names() {
+        assert!(domain_name_splits("").is_empty());
+        assert!(domain_name_splits(".").is_empty());
+        assert!(domain_name_splits("mydomain.tld").is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::errors::InternationalizedNameError;
+use idna::uts46::{AsciiDenyList, DnsLength, Hyphens, Uts46};
+use idna_adapter::{Adapter, RTL_MASK};
+
+/// The prefix of the labels that are the ASCII compatible encoding of an internationalized label.
+const ACE_PREFIX: &str = "xn--";
+
+/// Converts a domain name with Unicode labels to its ASCII form as defined by IDNA 2008 and UTS #46, the Unicode
+/// labels are normalized and encoded as punycode A-labels, e.g. "café.tld." results in "xn--caf-dma.tld.".
+///
+/// The UTS #46 processing allows symbols and emojis that IDNA 2008 disallows, hence the non-ASCII code points of
+/// the normalized labels are also required to be letters, digits or combining marks.
+///
+/// Names that only have ASCII labels are returned as is, the ASCII rules of the names are left to their validators.
+pub fn name_to_ascii(name: &str) -> Result<String, InternationalizedNameError> {
+    if !is_internationalized_name(name) {
+        return Ok(name.to_string());
+    }
+
+    let ascii_name = label_to_ascii(name).ok_or_else(|| invalid_name_error(name))?;
+    for label in name_to_unicode(&ascii_name).split('.') {
+        if let Some(code_point) = label.chars().find(|c| !is_allowed_code_point(*c)) {
+            return Err(InternationalizedNameError::DisallowedCodePoint {
+                label: label.to_string(),
+                code_point,
+            });
+        }
+    }
+
+    Ok(ascii_name)
+}
+
+/// Returns the canonical form of a looked up domain name, which is lowercase and has its Unicode labels converted
+/// to A-labels. Invalid internationalized names are only lowercased since they can't be part of any zone.
+pub fn canonical_name(name: &str) -> String {
+    name_to_ascii(name)
+        .unwrap_or_else(|_| name.to_string())
+        .to_ascii_lowercase()
+}
+
+/// Converts the A-labels of a domain name to their Unicode form for display purposes, e.g. "xn--caf-dma.tld."
+/// results in "café.tld.", labels that can't be decoded are kept as is.
+pub fn name_to_unicode(name: &str) -> String {
+    if !is_internationalized_name(name) {
+        return name.to_string();
+    }
+
+    name.split('.')
+        .map(|label| {
+            let (unicode_label, result) =
+                Uts46::new().to_unicode(label.as_bytes(), AsciiDenyList::EMPTY, Hyphens::Allow);
+
+            match result {
+                Ok(_) => unicode_label.to_string(),
+                Err(_) => label.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Returns true if the domain name has Unicode labels or A-labels.
+fn is_internationalized_name(name: &str) -> bool {
+    !name.is_ascii()
+        || name.split('.').any(|label| {
+            label
+                .get(..ACE_PREFIX.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(ACE_PREFIX))
+        })
+}
+
+/// Returns true if IDNA 2008 allows the code point in a label, the ASCII code points are left to the validators.
+fn is_allowed_code_point(c: char) -> bool {
+    c.is_ascii() || c.is_alphanumeric() || Adapter::new().is_mark(c)
+}
+
+/// Converts the labels to their ASCII form, labels are separated by dots and processed together since the bidi
+/// rule applies to all the labels of a name.
+fn label_to_ascii(labels: &str) -> Option<String> {
+    Uts46::new()
+        .to_ascii(
+            labels.as_bytes(),
+            AsciiDenyList::EMPTY,
+            Hyphens::Allow,
+            DnsLength::Ignore,
+        )
+        .ok()
+        .map(|ascii_labels| ascii_labels.to_string())
+}
+
+/// Finds out why the conversion of the domain name failed, since the IDNA processing does not report it.
+///
+/// A label that can't be converted on its own has a disallowed code point when one of its characters can't be
+/// converted on its own either, otherwise right-to-left labels fail the bidi rule. A name whose labels can be
+/// converted on their own fails the bidi rule across its labels.
+fn invalid_name_error(name: &str) -> InternationalizedNameError {
+    let adapter = Adapter::new();
+    let is_right_to_left = |label: &str| {
+        label
+            .chars()
+            .any(|c| adapter.bidi_class(c).to_mask().intersects(RTL_MASK))
+    };
+
+    let labels = name.split('.').filter(|label| !label.is_empty());
+    for label in labels.clone() {
+        if label_to_ascii(label).is_some() {
+            continue;
+        }
+
+        let disallowed_code_point = label.chars().find(|c| {
+            !adapter.is_mark(*c)
+                && (!is_allowed_code_point(*c)
+                    || label_to_ascii(c.encode_utf8(&mut [0; 4])).is_none())
+        });
+
+        return match disallowed_code_point {
+            Some(code_point) => InternationalizedNameError::DisallowedCodePoint {
+                label: label.to_string(),
+                code_point,
+            },
+            None if is_right_to_left(label) => InternationalizedNameError::BidiRule {
+                label: label.to_string(),
+            },
+            None => InternationalizedNameError::InvalidLabel {
+                label: label.to_string(),
+            },
+        };
+    }
+
+    match labels.clone().find(|label| is_right_to_left(label)) {
+        Some(_) => InternationalizedNameError::BidiRule {
+            label: labels
+                .clone()
+                .find(|label| !is_right_to_left(label))
+                .unwrap_or(name)
+                .to_string(),
+        },
+        None => InternationalizedNameError::InvalidLabel {
+            label: name.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::ascii_name("mydomain.tld.", "mydomain.tld.")]
+    #[case::unicode_name("café.tld.", "xn--caf-dma.tld.")]
+    #[case::normalized_unicode_name("CAFÉ.tld.", "xn--caf-dma.tld.")]
+    #[case::unicode_tld("例え.テスト.", "xn--r8jz45g.xn--zckzah.")]
+    #[case::a_label("XN--CAF-DMA.tld.", "xn--caf-dma.tld.")]
+    #[case::right_to_left_name("שלום.tld.", "xn--9dbne9b.tld.")]
+    #[case::wildcard_record_name("*.café", "*.xn--caf-dma")]
+    fn converts_names_to_ascii(#[case] name: &str, #[case] ascii_name: &str) {
+        assert_eq!(name_to_ascii(name), Ok(ascii_name.to_string()));
+        assert_eq!(name_to_unicode(ascii_name), name_to_unicode(name));
+    }
+
+    #[test]
+    fn canonical_names_are_lowercase_ascii() {
+        assert_eq!(canonical_name("Wiki.CAFÉ.tld."), "wiki.xn--caf-dma.tld.");
+        assert_eq!(canonical_name("Wiki.i❤.tld."), "wiki.i❤.tld.");
+    }
+
+    #[test]
+    fn converts_names_to_unicode() {
+        assert_eq!(name_to_unicode("xn--caf-dma.tld."), "café.tld.");
+        assert_eq!(name_to_unicode("mydomain.tld."), "mydomain.tld.");
+        assert_eq!(name_to_unicode("xn--invalid-.tld."), "xn--invalid-.tld.");
+    }
+
+    #[rstest]
+    #[case::emoji("i❤.tld.", InternationalizedNameError::DisallowedCodePoint { label: String::from("i❤"), code_point: '❤' })]
+    #[case::invalid_code_point("a\u{fffe}.tld.", InternationalizedNameError::DisallowedCodePoint { label: String::from("a\u{fffe}"), code_point: '\u{fffe}' })]
+    #[case::symbol("a⌘b.tld.", InternationalizedNameError::DisallowedCodePoint { label: String::from("a⌘b"), code_point: '⌘' })]
+    #[case::mixed_direction_label("aשלום.tld.", InternationalizedNameError::BidiRule { label: String::from("aשלום") })]
+    #[case::mixed_direction_name("שלום.1abc.", InternationalizedNameError::BidiRule { label: String::from("1abc") })]
+    #[case::invalid_a_label("xn--a.tld.", InternationalizedNameError::InvalidLabel { label: String::from("xn--a") })]
+    #[case::leading_combining_mark("\u{0301}a.tld.", InternationalizedNameError::InvalidLabel { label: String::from("\u{0301}a") })]
+    fn rejects_invalid_names(#[case] name: &str, #[case] error: InternationalizedNameError) {
+        assert_eq!(name_to_ascii(name), Err(error));
+    }
+}
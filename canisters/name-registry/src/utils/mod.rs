@@ -3,3 +3,19 @@
 /// Utils and helpers for the domain name.
 mod domain_name;
 pub use domain_name::*;
+
+/// Utils to convert internationalized domain names.
+mod internationalized_name;
+pub use internationalized_name::*;
+
+/// Utils to create and read pagination cursors.
+mod cursor;
+pub use cursor::*;
+
+/// Utils to hash the certified records.
+mod certification;
+pub use certification::*;
+
+/// Utils to format and parse zone files.
+mod zone_file;
+pub use zone_file::*;
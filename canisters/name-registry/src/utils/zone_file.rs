@@ -0,0 +1,503 @@
+use crate::{
+    api::DomainRecordInput,
+    common::DEFAULT_SOA_TTL,
+    errors::{DomainRecordInputError, ZoneFileError},
+    types::{DomainRecord, DomainRecordTypes, DomainZone, RecordData, RecordName, ZoneApexDomain},
+};
+use candid::Nat;
+use std::{fmt::Write, ops::Deref};
+
+/// The classes of RFC 1035, only records of the Internet class are supported.
+const RECORD_CLASSES: [&str; 4] = ["IN", "CS", "CH", "HS"];
+
+/// Formats the records of the zone as an RFC 1035 master file, with the apex domain as its $ORIGIN and the ttl of
+/// the SOA record as its $TTL.
+///
+/// The SOA record comes first followed by the other records in the given order, names are relative to the origin
+/// and every record has an explicit ttl and class, the record data is kept in its canonical form.
+pub fn format_zone_file(zone: &DomainZone, records: &[DomainRecord]) -> String {
+    let soa_record_type = DomainRecordTypes::SOA.to_string();
+    let (soa_records, other_records): (Vec<_>, Vec<_>) = records
+        .iter()
+        .partition(|record| record.record_type == soa_record_type);
+    let default_ttl = soa_records
+        .first()
+        .map_or(DEFAULT_SOA_TTL, |record| record.ttl);
+
+    let mut zone_file = String::new();
+    let _ = writeln!(zone_file, "$ORIGIN {}", zone.name.deref());
+    let _ = writeln!(zone_file, "$TTL {}", default_ttl);
+    for record in soa_records.into_iter().chain(other_records) {
+        let _ = writeln!(
+            zone_file,
+            "{}\t{}\tIN\t{}\t{}",
+            record.name.deref(),
+            record.ttl,
+            record.record_type,
+            record.data
+        );
+    }
+
+    zone_file
+}
+
+/// Parses an RFC 1035 master file into the records of the zone of the apex domain, which is also the initial
+/// origin of the file.
+///
+/// Comments, $ORIGIN and $TTL directives, relative names, `@`, owner names inherited from the previous record,
+/// optional ttl and class in any order and records continued over several lines with parentheses are supported.
+/// The records are validated against the zone, their names are fully qualified and their data is canonical.
+pub fn parse_zone_file(
+    apex_domain: &ZoneApexDomain,
+    zone_file: &str,
+) -> Result<Vec<DomainRecordInput>, ZoneFileError> {
+    let mut parser = ZoneFileParser::new(apex_domain);
+    let mut depth = 0;
+    let mut entry_line = 0;
+    let mut has_owner = false;
+    let mut tokens = Vec::new();
+
+    for (index, line) in zone_file.lines().enumerate() {
+        if depth == 0 {
+            entry_line = index + 1;
+            has_owner = !line.starts_with(char::is_whitespace);
+        }
+
+        tokenize(line, index + 1, &mut depth, &mut tokens)?;
+
+        if depth == 0 && !tokens.is_empty() {
+            parser.parse_entry(entry_line, has_owner, std::mem::take(&mut tokens))?;
+        }
+    }
+
+    if depth > 0 {
+        return Err(ZoneFileError::UnbalancedParentheses { line: entry_line });
+    }
+
+    Ok(parser.records)
+}
+
+/// The state of a zone file while its entries are parsed in order.
+struct ZoneFileParser<'a> {
+    apex_domain: &'a ZoneApexDomain,
+    origin: String,
+    default_ttl: Option<u32>,
+    previous_ttl: Option<u32>,
+    previous_owner: Option<String>,
+    records: Vec<DomainRecordInput>,
+}
+
+impl<'a> ZoneFileParser<'a> {
+    fn new(apex_domain: &'a ZoneApexDomain) -> Self {
+        Self {
+            apex_domain,
+            origin: apex_domain.deref().to_string(),
+            default_ttl: None,
+            previous_ttl: None,
+            previous_owner: None,
+            records: Vec::new(),
+        }
+    }
+
+    fn parse_entry(
+        &mut self,
+        line: usize,
+        has_owner: bool,
+        tokens: Vec<String>,
+    ) -> Result<(), ZoneFileError> {
+        match tokens.first() {
+            Some(token) if has_owner && token.starts_with('$') => {
+                self.parse_directive(line, tokens)
+            }
+            _ => self.parse_record(line, has_owner, tokens),
+        }
+    }
+
+    fn parse_directive(&mut self, line: usize, tokens: Vec<String>) -> Result<(), ZoneFileError> {
+        let directive = tokens[0].to_ascii_uppercase();
+        let value = match &tokens[1..] {
+            [value] => Some(value.as_str()),
+            _ => None,
+        };
+        let invalid_directive = || ZoneFileError::InvalidDirective {
+            line,
+            directive: directive.clone(),
+            value: tokens[1..].join(" "),
+        };
+
+        match directive.as_str() {
+            "$ORIGIN" => {
+                let origin = value
+                    .and_then(|value| {
+                        ZoneApexDomain::new(self.absolute_name(value).to_ascii_lowercase()).ok()
+                    })
+                    .ok_or_else(invalid_directive)?;
+                self.origin = origin.deref().to_string();
+            }
+            "$TTL" => {
+                self.default_ttl = Some(value.and_then(parse_ttl).ok_or_else(invalid_directive)?);
+            }
+            _ => return Err(ZoneFileError::UnsupportedDirective { line, directive }),
+        }
+
+        Ok(())
+    }
+
+    fn parse_record(
+        &mut self,
+        line: usize,
+        has_owner: bool,
+        tokens: Vec<String>,
+    ) -> Result<(), ZoneFileError> {
+        let invalid_record = |error| ZoneFileError::InvalidRecord { line, error };
+        let mut tokens = tokens.into_iter();
+        let owner = match has_owner {
+            true => tokens.next().map(|name| self.absolute_name(&name)),
+            false => self.previous_owner.clone(),
+        }
+        .ok_or(ZoneFileError::MissingOwner { line })?;
+
+        let mut ttl = None;
+        let mut has_class = false;
+        let record_type = loop {
+            let token = tokens
+                .next()
+                .ok_or(ZoneFileError::MissingRecordType { line })?;
+
+            if ttl.is_none() {
+                if let Some(value) = parse_ttl(&token) {
+                    ttl = Some(value);
+                    continue;
+                }
+            }
+
+            let is_class = RECORD_CLASSES
+                .iter()
+                .any(|class| token.eq_ignore_ascii_case(class));
+            if !has_class && is_class {
+                if !token.eq_ignore_ascii_case("IN") {
+                    return Err(ZoneFileError::UnsupportedClass { line, class: token });
+                }
+
+                has_class = true;
+                continue;
+            }
+
+            break token;
+        };
+
+        let name =
+            RecordName::from_fully_qualified_name(&owner, self.apex_domain).map_err(|error| {
+                invalid_record(DomainRecordInputError::InvalidRecordName {
+                    name: owner.clone(),
+                    error,
+                })
+            })?;
+        let record_type = record_type
+            .to_ascii_uppercase()
+            .parse::<DomainRecordTypes>()
+            .map_err(|error| {
+                invalid_record(DomainRecordInputError::UnsupportedRecordType(error))
+            })?;
+
+        let mut data = tokens.collect::<Vec<_>>();
+        for index in domain_name_positions(record_type) {
+            if let Some(token) = data.get_mut(*index) {
+                *token = self.absolute_name(token);
+            }
+        }
+        if record_type == DomainRecordTypes::SOA {
+            // the refresh, retry, expire and minimum values of the SOA record are durations like ttls
+            for token in data.iter_mut().skip(3) {
+                if let Some(seconds) = parse_ttl(token) {
+                    *token = seconds.to_string();
+                }
+            }
+        }
+        let data = RecordData::parse(record_type, &data.join(" "))
+            .map_err(|error| {
+                invalid_record(DomainRecordInputError::InvalidRecordData {
+                    record_type: record_type.to_string(),
+                    error,
+                })
+            })?
+            .to_string();
+
+        if ttl.is_some() {
+            self.previous_ttl = ttl;
+        }
+        let ttl = ttl
+            .or(self.default_ttl)
+            .or(self.previous_ttl)
+            .unwrap_or(DomainRecord::DEFAULT_TTL);
+
+        self.records.push(DomainRecordInput {
+            name: name.to_fully_qualified_name(self.apex_domain),
+            record_type: record_type.to_string(),
+            ttl: Nat::from(ttl),
+            data,
+        });
+        self.previous_owner = Some(owner);
+
+        Ok(())
+    }
+
+    /// Returns the fully qualified form of a name of the zone file, `@` is the origin and names that don't end
+    /// with a dot are relative to it.
+    fn absolute_name(&self, name: &str) -> String {
+        match name {
+            "@" => self.origin.clone(),
+            name if name.ends_with('.') => name.to_string(),
+            name => format!("{}.{}", name, self.origin),
+        }
+    }
+}
+
+/// Splits a line of a zone file into its tokens, quoted strings are kept with their quotes and the comments are
+/// skipped, the depth of the parentheses that continue a record over several lines is kept across lines.
+fn tokenize(
+    line: &str,
+    line_number: usize,
+    depth: &mut usize,
+    tokens: &mut Vec<String>,
+) -> Result<(), ZoneFileError> {
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => break,
+            '(' => *depth += 1,
+            ')' => {
+                *depth = depth
+                    .checked_sub(1)
+                    .ok_or(ZoneFileError::UnbalancedParentheses { line: line_number })?;
+            }
+            '"' => {
+                let mut token = String::from(c);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => {
+                            token.push('\\');
+                            token.extend(chars.next());
+                        }
+                        Some(c) => token.push(c),
+                        None => {
+                            return Err(ZoneFileError::UnterminatedString { line: line_number })
+                        }
+                    }
+                }
+                token.push('"');
+                tokens.push(token);
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut token = String::from(c);
+                while let Some(c) =
+                    chars.next_if(|c| !c.is_whitespace() && !matches!(c, ';' | '(' | ')' | '"'))
+                {
+                    token.push(c);
+                }
+                tokens.push(token);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a ttl in seconds, or with the units of BIND, e.g. "1h30m".
+fn parse_ttl(ttl: &str) -> Option<u32> {
+    if !ttl.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    if let Ok(seconds) = ttl.parse() {
+        return Some(seconds);
+    }
+
+    let mut seconds = 0u32;
+    let mut value = String::new();
+    for c in ttl.chars() {
+        if c.is_ascii_digit() {
+            value.push(c);
+            continue;
+        }
+
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3_600,
+            'd' => 86_400,
+            'w' => 604_800,
+            _ => return None,
+        };
+        let value = std::mem::take(&mut value).parse::<u32>().ok()?;
+        seconds = seconds.checked_add(value.checked_mul(unit)?)?;
+    }
+
+    value.is_empty().then_some(seconds)
+}
+
+/// The positions of the domain names within the data of the record type, which can be relative to the origin.
+fn domain_name_positions(record_type: DomainRecordTypes) -> &'static [usize] {
+    match record_type {
+        DomainRecordTypes::CNAME | DomainRecordTypes::NS | DomainRecordTypes::PTR => &[0],
+        DomainRecordTypes::MX => &[1],
+        DomainRecordTypes::SRV => &[3],
+        DomainRecordTypes::SOA => &[0, 1],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::RecordNameError;
+    use rstest::rstest;
+
+    fn apex_domain() -> ZoneApexDomain {
+        ZoneApexDomain::new(String::from("mydomain.tld.")).unwrap()
+    }
+
+    fn input(name: &str, record_type: &str, ttl: u32, data: &str) -> DomainRecordInput {
+        DomainRecordInput {
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            ttl: Nat::from(ttl),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn format_and_parse_zone_file() {
+        let apex_domain = apex_domain();
+        let record = |name: &str, record_type: &str, ttl: u32, data: &str| {
+            DomainRecord::new(
+                RecordName::new(name.to_string(), &apex_domain).unwrap(),
+                record_type.to_string(),
+                ttl,
+                data.to_string(),
+            )
+        };
+        let soa = "ns1.mydomain.tld. hostmaster.mydomain.tld. 7 7200 3600 1209600 3600";
+        let records = vec![
+            record("@", "NS", 300, "ns1.mydomain.tld."),
+            record("@", "SOA", 900, soa),
+            record("@", "TXT", 300, r#""v=spf1 -all" "a;b""#),
+            record("wiki", "CID", 60, "qoctq-giaaa-aaaaa-aaaea-cai"),
+        ];
+
+        let zone_file = format_zone_file(&DomainZone::new(apex_domain.clone()), &records);
+
+        assert_eq!(
+            zone_file,
+            format!(
+                "$ORIGIN mydomain.tld.\n$TTL 900\n@\t900\tIN\tSOA\t{}\n@\t300\tIN\tNS\tns1.mydomain.tld.\n\
+                 @\t300\tIN\tTXT\t\"v=spf1 -all\" \"a;b\"\nwiki\t60\tIN\tCID\tqoctq-giaaa-aaaaa-aaaea-cai\n",
+                soa
+            )
+        );
+        assert_eq!(
+            parse_zone_file(&apex_domain, &zone_file).unwrap(),
+            vec![
+                input("mydomain.tld.", "SOA", 900, soa),
+                input("mydomain.tld.", "NS", 300, "ns1.mydomain.tld."),
+                input("mydomain.tld.", "TXT", 300, r#""v=spf1 -all" "a;b""#),
+                input(
+                    "wiki.mydomain.tld.",
+                    "CID",
+                    60,
+                    "qoctq-giaaa-aaaaa-aaaea-cai"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_zone_file_with_master_file_syntax() {
+        let zone_file = r#"
+; zone of mydomain.tld.
+$TTL 1h
+@   IN  SOA ns1 hostmaster ( 2024010101 ; serial
+            2h 1h 2w 1h )
+    IN  NS  ns1
+ns1 300 IN A 192.0.2.1
+www IN 60 CNAME @
+mail    MX  10 mx.external.tld.
+$ORIGIN eu.mydomain.tld.
+api TXT "hello world" ; comment
+    AAAA 2001:DB8::1
+"#;
+
+        assert_eq!(
+            parse_zone_file(&apex_domain(), zone_file).unwrap(),
+            vec![
+                input(
+                    "mydomain.tld.",
+                    "SOA",
+                    3600,
+                    "ns1.mydomain.tld. hostmaster.mydomain.tld. 2024010101 7200 3600 1209600 3600"
+                ),
+                input("mydomain.tld.", "NS", 3600, "ns1.mydomain.tld."),
+                input("ns1.mydomain.tld.", "A", 300, "192.0.2.1"),
+                input("www.mydomain.tld.", "CNAME", 60, "mydomain.tld."),
+                input("mail.mydomain.tld.", "MX", 3600, "10 mx.external.tld."),
+                input("api.eu.mydomain.tld.", "TXT", 3600, r#""hello world""#),
+                input("api.eu.mydomain.tld.", "AAAA", 3600, "2001:db8::1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_zone_file_inherits_previous_ttl_without_default_ttl() {
+        let zone_file = "a 120 TXT first\nb TXT second\n";
+
+        assert_eq!(
+            parse_zone_file(&apex_domain(), zone_file).unwrap(),
+            vec![
+                input("a.mydomain.tld.", "TXT", 120, "first"),
+                input("b.mydomain.tld.", "TXT", 120, "second"),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::include("$INCLUDE other.zone", ZoneFileError::UnsupportedDirective { line: 1, directive: String::from("$INCLUDE") })]
+    #[case::invalid_ttl("$TTL 1x", ZoneFileError::InvalidDirective { line: 1, directive: String::from("$TTL"), value: String::from("1x") })]
+    #[case::missing_owner("\n  TXT hello", ZoneFileError::MissingOwner { line: 2 })]
+    #[case::missing_type("www 60 IN", ZoneFileError::MissingRecordType { line: 1 })]
+    #[case::unsupported_class("www CH TXT hello", ZoneFileError::UnsupportedClass { line: 1, class: String::from("CH") })]
+    #[case::unclosed_parentheses("www TXT ( hello\n world", ZoneFileError::UnbalancedParentheses { line: 1 })]
+    #[case::unopened_parentheses("www TXT hello )", ZoneFileError::UnbalancedParentheses { line: 1 })]
+    #[case::unterminated_string("www TXT \"hello", ZoneFileError::UnterminatedString { line: 1 })]
+    #[case::outside_zone(
+        "www.other.tld. TXT hello",
+        ZoneFileError::InvalidRecord {
+            line: 1,
+            error: DomainRecordInputError::InvalidRecordName {
+                name: String::from("www.other.tld."),
+                error: RecordNameError::NotInZone {
+                    name: String::from("www.other.tld."),
+                    apex_domain: String::from("mydomain.tld."),
+                },
+            },
+        }
+    )]
+    fn parse_invalid_zone_file(#[case] zone_file: &str, #[case] expected: ZoneFileError) {
+        assert_eq!(
+            parse_zone_file(&apex_domain(), zone_file).unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_zone_file_with_invalid_record_data() {
+        assert!(matches!(
+            parse_zone_file(&apex_domain(), "\n\nwww A not-an-ip"),
+            Err(ZoneFileError::InvalidRecord {
+                line: 3,
+                error: DomainRecordInputError::InvalidRecordData { .. }
+            })
+        ));
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+/// The top level domain (TLD) operated by a new canister, other TLDs are added with their own policy.
+pub const DEFAULT_TLD: &str = "icp.";
+
+/// The label below the default TLD whose domains can be registered by anyone, e.g. "mydomain.test.icp.".
+pub const DEFAULT_TEST_LABEL: &str = "test";
+
+/// The label below each TLD of the reverse index of the registered principals, e.g. "<principal>.reverse.icp.".
+pub const REVERSE_LABEL: &str = "reverse";
+
+/// The label below each TLD of the domains of the subnets, e.g. "app-1.subnet.icp.".
+pub const SUBNET_LABEL: &str = "subnet";
+
+/// The maximum length of the name, record type and data of a registered record, which prevents record stuffing.
+pub const MAX_REGISTRATION_FIELD_LENGTH: usize = 100;
+
+/// The maximum number of records of a registration, which keeps the registration within its storage size.
+pub const MAX_REGISTRATION_RECORDS: usize = 10;
+
+/// The maximum number of reserved labels of a TLD policy, which keeps the policy within its storage size.
+pub const MAX_RESERVED_LABELS: usize = 100;
+
+/// The time a registration of the default TLD lasts before it expires, in seconds (1 year).
+pub const DEFAULT_REGISTRATION_PERIOD: u64 = 365 * 24 * 60 * 60;
+
+/// The time after expiry in which the registrant can renew a registration of the default TLD, in seconds (30 days).
+pub const DEFAULT_GRACE_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+/// The time after the grace period in which canister controllers can restore a registration of the default TLD,
+/// in seconds (30 days).
+pub const DEFAULT_REDEMPTION_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+/// The interval of the sweep of the expired registrations.
+pub const EXPIRED_REGISTRATIONS_SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The time a transfer can be accepted after it was initiated, in seconds (7 days).
+pub const TRANSFER_ACCEPTANCE_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+/// The maximum number of entries kept in the transfer log of a domain, the oldest entries are removed first.
+pub const MAX_TRANSFER_LOG_ENTRIES: u64 = 100;
+
+/// The maximum number of registrations processed by a sweep, the remaining ones are processed by the next sweeps.
+pub const MAX_SWEPT_REGISTRATIONS: usize = 100;
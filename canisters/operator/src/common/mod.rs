@@ -0,0 +1,5 @@
+//! Common constants of the operator canister.
+
+/// Constants used across the operator canister.
+mod constants;
+pub use constants::*;
@@ -0,0 +1,44 @@
+use super::call_context;
+use crate::{errors::ManageControllersError, services::ControllerService};
+use candid::Principal;
+use cns_domain_registry::{api::UpdateOperationResult, types::RegistrationController};
+use ic_cdk::{api::time, query, update};
+
+/// Get the controllers of a domain and their roles.
+#[query]
+fn get_controllers(domain: String) -> Vec<RegistrationController> {
+    ControllerService::default().get_controllers(&domain, time())
+}
+
+/// Add a controller to a domain or replace its roles, see `spec.did` for who can manage the controllers.
+#[update]
+fn add_controller(domain: String, controller: RegistrationController) -> UpdateOperationResult {
+    operation_result(ControllerService::default().add_controller(
+        &call_context(),
+        &domain,
+        &controller,
+    ))
+}
+
+/// Remove a controller from a domain, see `spec.did` for who can manage the controllers.
+#[update]
+fn remove_controller(domain: String, controller_id: Principal) -> UpdateOperationResult {
+    operation_result(ControllerService::default().remove_controller(
+        &call_context(),
+        &domain,
+        controller_id,
+    ))
+}
+
+fn operation_result(result: Result<(), ManageControllersError>) -> UpdateOperationResult {
+    match result {
+        Ok(()) => UpdateOperationResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => UpdateOperationResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
@@ -0,0 +1,35 @@
+use super::{call_context, update_certified_data};
+use crate::{common::EXPIRED_REGISTRATIONS_SWEEP_INTERVAL, services::LeaseService};
+use cns_domain_registry::types::{CallContext, RegisterResult};
+use ic_cdk::{
+    api::{id, time},
+    update,
+};
+use ic_cdk_timers::set_timer_interval;
+
+/// Renew the registration of a domain, see `spec.did` for who can renew registrations and when.
+#[update]
+fn renew(domain: String) -> RegisterResult {
+    let result = LeaseService::default().renew(&call_context(), &domain);
+    update_certified_data();
+
+    match result {
+        Ok(()) => RegisterResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => RegisterResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
+
+/// Starts the periodic sweep of the expired registrations, timers don't survive upgrades hence it is started
+/// again after every upgrade.
+pub(super) fn start_expired_registrations_sweep() {
+    set_timer_interval(EXPIRED_REGISTRATIONS_SWEEP_INTERVAL, || {
+        LeaseService::default().sweep_expired(&CallContext::new(id(), true, time()));
+        update_certified_data();
+    });
+}
@@ -0,0 +1,40 @@
+use super::{leases::start_expired_registrations_sweep, update_certified_data};
+use crate::{repositories::RegistrationRepository, services::TldPolicyService};
+use cns_domain_registry::services::{CertificationService, MigrationService};
+use ic_cdk::{init, post_upgrade, pre_upgrade, trap};
+
+/// The stored data of a new canister is written with the current schema version of the name registry, and the
+/// canister starts operating the default TLD. The certified data is set so that lookups can be verified before the
+/// first update.
+#[init]
+fn init() {
+    MigrationService::default().save_schema_version();
+    TldPolicyService::default().install_default_policy();
+    update_certified_data();
+    start_expired_registrations_sweep();
+}
+
+/// The data is kept in stable memory, only the schema version it was written with has to be saved for the
+/// next version of the canister.
+#[pre_upgrade]
+fn pre_upgrade() {
+    MigrationService::default().save_schema_version();
+}
+
+/// The zones are migrated to the current schema version of the name registry before anything else reads them,
+/// and the certified records tree is rebuilt since it is kept in heap memory, which is cleared on upgrades.
+///
+/// Canisters upgraded from a version without TLD policies keep operating the default TLD, and the registrations of
+/// versions without records and leases are moved to the current layout.
+#[post_upgrade]
+fn post_upgrade() {
+    if let Err(error) = MigrationService::default().migrate() {
+        trap(&error.to_string());
+    }
+    RegistrationRepository::default().migrate_legacy_registrations();
+    TldPolicyService::default().install_default_policy();
+
+    CertificationService::default().certify_all();
+    update_certified_data();
+    start_expired_registrations_sweep();
+}
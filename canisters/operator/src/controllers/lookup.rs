@@ -0,0 +1,37 @@
+use crate::services::LeaseService;
+use cns_domain_registry::{
+    api::{Certification, DomainLookup},
+    services::{CertificationService, LookupService},
+};
+use ic_cdk::{
+    api::{data_certificate, time},
+    query,
+};
+
+/// Lookup a domain name of the TLDs and return the records that match the specified record type, the principals
+/// of the registered domains are resolved with PTR lookups of "<principal>.reverse.<tld>".
+///
+/// Lookups of expired domains have no answers and are flagged as expired, they are only certified once the sweep
+/// removed the records of the domain since the answers differ from the certified records until then.
+///
+/// The answers are certified when the data certificate is available, which is the case for non-replicated queries.
+#[query]
+fn lookup(domain: String, record_type: String) -> DomainLookup {
+    let expired = LeaseService::default().find_expired(&domain, time());
+    let mut lookup = LookupService::default().lookup(&domain, &record_type);
+    if expired.is_some() {
+        lookup.answers.clear();
+        lookup.additionals.clear();
+        lookup.expired = Some(true);
+    }
+
+    let is_certified = expired.map_or(true, |registration| registration.is_suspended());
+    lookup.certification = data_certificate()
+        .filter(|_| is_certified)
+        .map(|ic_certificate| Certification {
+            ic_certificate,
+            state_tree: CertificationService::default().witness(&domain, &record_type, &lookup),
+        });
+
+    lookup
+}
@@ -0,0 +1,40 @@
+//! Canister endpoints of the operator canister, as defined in `spec.did`.
+
+/// Endpoints to manage the controllers of the registered domains.
+mod domain_controllers;
+
+/// Endpoints to renew registrations and the sweep of the expired ones.
+mod leases;
+
+/// Hooks of the canister lifecycle.
+mod lifecycle;
+
+/// Endpoints to resolve domain names.
+mod lookup;
+
+/// Endpoints to register domain names.
+mod register;
+
+/// Endpoints to manage the registration policies of the TLDs.
+mod tld_policies;
+
+/// Endpoints to transfer domains between registrants.
+mod transfers;
+
+use cns_domain_registry::{services::CertificationService, types::CallContext};
+use ic_cdk::{
+    api::{is_controller, set_certified_data, time},
+    caller,
+};
+
+/// Builds the context of the current call, used by the services to authorize the caller.
+fn call_context() -> CallContext {
+    let caller = caller();
+
+    CallContext::new(caller, is_controller(&caller), time())
+}
+
+/// Sets the certified data of the canister to the root hash of the certified records tree.
+fn update_certified_data() {
+    set_certified_data(&CertificationService::default().certified_data());
+}
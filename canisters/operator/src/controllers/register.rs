@@ -0,0 +1,22 @@
+use super::{call_context, update_certified_data};
+use crate::services::RegistrationService;
+use cns_domain_registry::{api::RegistrationRecords, types::RegisterResult};
+use ic_cdk::update;
+
+/// Register a domain of the TLD with its record, see `spec.did` for who can register which domains.
+#[update]
+fn register(domain: String, records: RegistrationRecords) -> RegisterResult {
+    let result = RegistrationService::default().register(&call_context(), &domain, &records);
+    update_certified_data();
+
+    match result {
+        Ok(()) => RegisterResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => RegisterResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
@@ -0,0 +1,26 @@
+use super::call_context;
+use crate::{services::TldPolicyService, types::TldPolicy};
+use cns_domain_registry::api::UpdateOperationResult;
+use ic_cdk::{query, update};
+
+/// Get the registration policies of the TLDs operated by the canister.
+#[query]
+fn get_tld_policies() -> Vec<TldPolicy> {
+    TldPolicyService::default().get_policies()
+}
+
+/// Operate a TLD with the given registration policy or replace its policy, only canister controllers can set
+/// policies.
+#[update]
+fn set_tld_policy(policy: TldPolicy) -> UpdateOperationResult {
+    match TldPolicyService::default().set_policy(&call_context(), &policy) {
+        Ok(()) => UpdateOperationResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => UpdateOperationResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
@@ -0,0 +1,56 @@
+use super::call_context;
+use crate::{
+    errors::TransferError,
+    services::TransferService,
+    types::{PendingTransfer, TransferLogEntry},
+};
+use candid::Principal;
+use cns_domain_registry::api::UpdateOperationResult;
+use ic_cdk::{query, update};
+
+/// Initiate the transfer of a domain to a new registrant, see `spec.did` for who can initiate transfers.
+#[update]
+fn initiate_transfer(domain: String, new_owner: Principal) -> UpdateOperationResult {
+    operation_result(
+        TransferService::default()
+            .initiate_transfer(&call_context(), &domain, new_owner)
+            .map(|_| ()),
+    )
+}
+
+/// Accept the pending transfer of a domain, only the new registrant of the transfer can accept it.
+#[update]
+fn accept_transfer(domain: String) -> UpdateOperationResult {
+    operation_result(TransferService::default().accept_transfer(&call_context(), &domain))
+}
+
+/// Cancel or decline the pending transfer of a domain.
+#[update]
+fn cancel_transfer(domain: String) -> UpdateOperationResult {
+    operation_result(TransferService::default().cancel_transfer(&call_context(), &domain))
+}
+
+/// Get the pending transfer of a domain.
+#[query]
+fn get_pending_transfer(domain: String) -> Option<PendingTransfer> {
+    TransferService::default().get_pending_transfer(&domain)
+}
+
+/// Get the transfer log of a domain.
+#[query]
+fn get_transfer_log(domain: String) -> Vec<TransferLogEntry> {
+    TransferService::default().get_transfer_log(&domain)
+}
+
+fn operation_result(result: Result<(), TransferError>) -> UpdateOperationResult {
+    match result {
+        Ok(()) => UpdateOperationResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => UpdateOperationResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
@@ -0,0 +1,70 @@
+use cns_domain_registry::errors::ZoneApexDomainError;
+
+/// Container for manage controllers errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum ManageControllersError {
+    /// The domain is not a valid domain name
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The domain is not registered or its registration was released
+    #[error("Domain {domain:?} is not registered")]
+    NotRegistered {
+        /// The domain of the controllers
+        domain: String,
+    },
+
+    /// Expired registrations have to be renewed before their controllers can be changed
+    #[error("The registration of domain {domain:?} expired")]
+    RegistrationExpired {
+        /// The domain of the controllers
+        domain: String,
+    },
+
+    /// The caller is not allowed to change the given controller of the domain
+    #[error(
+        "Caller {caller} is not allowed to change controller {controller} of domain {domain:?}"
+    )]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+        /// The domain of the controllers
+        domain: String,
+        /// The principal of the changed controller
+        controller: String,
+    },
+
+    /// The registrant only changes with a transfer of the domain
+    #[error("The registrant of domain {domain:?} can only be changed with a transfer")]
+    RegistrantChange {
+        /// The domain of the controllers
+        domain: String,
+    },
+
+    /// The controller is anonymous or has no roles
+    #[error("Controller {controller} is invalid: {reason}")]
+    InvalidController {
+        /// The principal of the controller
+        controller: String,
+        /// The reason why the controller is invalid
+        reason: String,
+    },
+
+    /// The domain has the maximum number of controllers
+    #[error("Domain {domain:?} can't have more than {max_controllers} controllers")]
+    TooManyControllers {
+        /// The domain of the controllers
+        domain: String,
+        /// The maximum number of controllers of a domain
+        max_controllers: usize,
+    },
+
+    /// The principal is not a controller of the domain
+    #[error("Principal {controller} is not a controller of domain {domain:?}")]
+    ControllerNotFound {
+        /// The domain of the controllers
+        domain: String,
+        /// The principal of the controller
+        controller: String,
+    },
+}
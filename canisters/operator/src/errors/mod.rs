@@ -0,0 +1,21 @@
+//! Various error types for failure scenarios
+
+/// Error types for the operations that manage the controllers of a domain.
+mod manage_controllers;
+pub use manage_controllers::*;
+
+/// Error types for the register operation.
+mod register;
+pub use register::*;
+
+/// Error types for the renew operation.
+mod renew;
+pub use renew::*;
+
+/// Error types for the set TLD policy operation.
+mod set_tld_policy;
+pub use set_tld_policy::*;
+
+/// Error types for the transfer operations.
+mod transfer;
+pub use transfer::*;
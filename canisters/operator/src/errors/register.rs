@@ -0,0 +1,130 @@
+use cns_domain_registry::errors::{ManageRecordsError, ZoneApexDomainError};
+
+/// Container for register errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum RegisterError {
+    /// The domain is not a valid domain name
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The domain is not below a TLD operated by the canister
+    #[error("Domain {domain:?} is not below a TLD operated by the canister")]
+    UnsupportedTld {
+        /// The registered domain
+        domain: String,
+    },
+
+    /// The domain is at or below a label of the TLD that is reserved
+    #[error("Domain {domain:?} is reserved by label {label:?}")]
+    ReservedLabel {
+        /// The registered domain
+        domain: String,
+        /// The reserved label
+        label: String,
+    },
+
+    /// A domain is registered with at least one and a limited number of records
+    #[error("Between 1 and {max_count:?} domain records must be specified, received {count:?}")]
+    InvalidRecordCount {
+        /// The number of records received
+        count: usize,
+        /// The maximum number of records of a registration
+        max_count: usize,
+    },
+
+    /// The same record is specified more than once
+    #[error("Domain record {record_type:?} with data {data:?} is specified more than once")]
+    DuplicateRecord {
+        /// The record type of the duplicated record
+        record_type: String,
+        /// The data of the duplicated record
+        data: String,
+    },
+
+    /// The controllers of a domain are assigned by the operator
+    #[error("Currently no explicit controller setting is supported")]
+    ControllersNotSupported,
+
+    /// The fields of the records are limited in length to prevent record stuffing
+    #[error("Domain record name, record type and data fields must be limited to {max_length:?} characters")]
+    RecordTooLong {
+        /// The max length of the fields
+        max_length: usize,
+    },
+
+    /// The name of the record is not the registered domain
+    #[error("Inconsistent domain record, record name {name:?} doesn't match domain {domain:?}")]
+    RecordNameMismatch {
+        /// The name of the record
+        name: String,
+        /// The registered domain
+        domain: String,
+    },
+
+    /// The policy of the TLD doesn't allow the record type
+    #[error("Record type {record_type:?} can't be registered in TLD {tld:?}")]
+    UnsupportedRecordType {
+        /// The record type received
+        record_type: String,
+        /// The TLD of the domain
+        tld: String,
+    },
+
+    /// The data of a CID record must be the principal of a canister
+    #[error("CID record data is not a valid canister principal. Received {data:?}")]
+    InvalidCanisterPrincipal {
+        /// The data of the record
+        data: String,
+    },
+
+    /// The data of an SID record must be a self-authenticating principal
+    #[error("SID record data is not a valid service principal. Received {data:?}")]
+    InvalidServicePrincipal {
+        /// The data of the record
+        data: String,
+    },
+
+    /// Subnet domains follow the format `{subnet_type}-(optional {subnet_specialization})-{counter}.subnet.{tld}`
+    #[error("Subnet record name {name:?} has an improper format: {reason}")]
+    InvalidSubnetName {
+        /// The name of the record
+        name: String,
+        /// The reason why the name is invalid
+        reason: String,
+    },
+
+    /// Callers that are not canister controllers can only register the test domains and, if the TLD is open for
+    /// self-service registration, the domains that are not reserved
+    #[error(
+        "Caller {caller} is not allowed to register {record_type:?} records for domain {domain:?}"
+    )]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+        /// The registered domain
+        domain: String,
+        /// The record type of the registration
+        record_type: String,
+    },
+
+    /// The domain was registered by another principal and the caller is not a controller that can change its
+    /// records
+    #[error("Caller {caller} does not match the registrant {registrant}")]
+    RegistrantMismatch {
+        /// The principal of the caller
+        caller: String,
+        /// The principal of the registrant
+        registrant: String,
+    },
+
+    /// Expired registrations can only be restored with `renew`, which enforces their grace and redemption period
+    #[error("The registration of domain {domain:?} expired and has to be renewed")]
+    RegistrationExpired {
+        /// The registered domain
+        domain: String,
+    },
+
+    /// The records could not be stored in the zone of the TLD, no record was changed
+    #[error("{0}")]
+    ManageRecords(ManageRecordsError),
+}
@@ -0,0 +1,58 @@
+use cns_domain_registry::errors::{ManageRecordsError, ZoneApexDomainError};
+
+/// Container for renew errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum RenewError {
+    /// The domain is not a valid domain name
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The domain is not registered or its registration was released
+    #[error("Domain {domain:?} is not registered")]
+    NotRegistered {
+        /// The renewed domain
+        domain: String,
+    },
+
+    /// The registration doesn't expire, hence it can't be renewed
+    #[error("The registration of domain {domain:?} doesn't expire")]
+    NotExpiring {
+        /// The renewed domain
+        domain: String,
+    },
+
+    /// Only the registrant and canister controllers can renew a registration
+    #[error("Caller {caller} is not allowed to renew domain {domain:?}")]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+        /// The renewed domain
+        domain: String,
+    },
+
+    /// The grace period of the registration ended, only canister controllers can restore it
+    #[error(
+        "The grace period of domain {domain:?} ended, only canister controllers can restore it"
+    )]
+    RedemptionPeriod {
+        /// The renewed domain
+        domain: String,
+    },
+
+    /// A registration can only be renewed once it expires within the registration period
+    #[error(
+        "Domain {domain:?} can't be renewed before {renewable_at:?}, it expires at {expires_at:?}"
+    )]
+    TooEarly {
+        /// The renewed domain
+        domain: String,
+        /// The time the registration can be renewed in nanoseconds since the UNIX epoch
+        renewable_at: u64,
+        /// The time the registration expires in nanoseconds since the UNIX epoch
+        expires_at: u64,
+    },
+
+    /// The records of the expired registration could not be restored, the registration was not renewed
+    #[error("{0}")]
+    ManageRecords(ManageRecordsError),
+}
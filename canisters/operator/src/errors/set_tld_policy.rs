@@ -0,0 +1,50 @@
+use cns_domain_registry::errors::ZoneApexDomainError;
+
+/// Container for set TLD policy errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum SetTldPolicyError {
+    /// Only canister controllers can change the policies of the TLDs
+    #[error("Caller {caller} is not allowed to change the policies of the TLDs")]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+    },
+
+    /// The TLD is not a valid domain name
+    #[error("TLD is invalid: {0}")]
+    InvalidTld(ZoneApexDomainError),
+
+    /// The TLD has more than one label
+    #[error("Domain {domain:?} is not a TLD")]
+    NotTld {
+        /// The domain of the policy
+        domain: String,
+    },
+
+    /// The record type can't be registered, PTR records are managed by the operator and SOA records by the registry
+    #[error("Record type {record_type:?} can't be allowed for registrations")]
+    UnsupportedRecordType {
+        /// The record type of the policy
+        record_type: String,
+    },
+
+    /// Reserved and test labels are single labels of a domain name
+    #[error("Label {label:?} is invalid")]
+    InvalidLabel {
+        /// The label of the policy
+        label: String,
+    },
+
+    /// Registrations of a TLD with a lease must last for some time
+    #[error("The registration period of a lease must be greater than 0 seconds")]
+    InvalidRegistrationPeriod,
+
+    /// The number of reserved labels is limited to keep the policy within its storage size
+    #[error("A TLD policy can have at most {max_labels:?} reserved labels, received {count:?}")]
+    TooManyReservedLabels {
+        /// The max number of reserved labels
+        max_labels: usize,
+        /// The number of reserved labels received
+        count: usize,
+    },
+}
@@ -0,0 +1,57 @@
+use cns_domain_registry::errors::ZoneApexDomainError;
+
+/// Container for transfer errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum TransferError {
+    /// The domain is not a valid domain name
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The domain is not registered or its registration was released
+    #[error("Domain {domain:?} is not registered")]
+    NotRegistered {
+        /// The transferred domain
+        domain: String,
+    },
+
+    /// Expired registrations have to be renewed before they can be transferred
+    #[error("The registration of domain {domain:?} expired")]
+    RegistrationExpired {
+        /// The transferred domain
+        domain: String,
+    },
+
+    /// The caller is not allowed to change the transfer of the domain
+    #[error("Caller {caller} is not allowed to change the transfer of domain {domain:?}")]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+        /// The transferred domain
+        domain: String,
+    },
+
+    /// The new registrant must be another principal than the current one and can't be anonymous
+    #[error("Principal {new_owner} can't become the registrant of domain {domain:?}")]
+    InvalidNewOwner {
+        /// The transferred domain
+        domain: String,
+        /// The principal of the new registrant
+        new_owner: String,
+    },
+
+    /// The domain has no pending transfer
+    #[error("Domain {domain:?} has no pending transfer")]
+    NoPendingTransfer {
+        /// The transferred domain
+        domain: String,
+    },
+
+    /// The transfer was not accepted in time
+    #[error("The transfer of domain {domain:?} expired at {expired_at:?}")]
+    TransferExpired {
+        /// The transferred domain
+        domain: String,
+        /// The time the transfer expired in nanoseconds since the UNIX epoch
+        expired_at: u64,
+    },
+}
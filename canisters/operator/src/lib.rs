@@ -0,0 +1,14 @@
+//! # CNS TLD operator canister
+//!
+//! The operator canister manages the domains of one or more top level domains (TLD) of the Chain Name System (CNS)
+//! on the Internet Computer(https://internetcomputer.org), it builds on top of the name registry and adds the
+//! registration of new domain names according to the registration policy of each TLD.
+//!
+//! The records of the registered domains are kept in the zone of their TLD, hence they are resolved, certified and
+//! recorded in the zone history by the name registry.
+
+pub mod common;
+pub mod errors;
+pub mod repositories;
+pub mod services;
+pub mod types;
@@ -0,0 +1,22 @@
+//! Repositories for the data kept by the operator canister, the records of the domains are kept by the
+//! repositories of the name registry.
+
+/// Common configurations for repositories.
+mod setup;
+pub use setup::*;
+
+/// Repository for the pending transfers of the domains.
+mod pending_transfer;
+pub use pending_transfer::*;
+
+/// Repository for the registrations of the domains.
+mod registration;
+pub use registration::*;
+
+/// Repository for the registration policies of the TLDs.
+mod tld_policy;
+pub use tld_policy::*;
+
+/// Repository for the transfer log of the domains.
+mod transfer_log;
+pub use transfer_log::*;
@@ -0,0 +1,59 @@
+use crate::{repositories::PENDING_TRANSFERS_MEMORY_ID, types::PendingTransfer};
+use cns_domain_registry::{
+    repositories::{with_memory_manager, Memory, Repository},
+    types::ZoneApexDomain,
+};
+use ic_stable_structures::BTreeMap;
+use std::cell::RefCell;
+
+/// The database schema for the PendingTransfer repository, indexed by the transferred domain.
+pub type PendingTransferDatabase = BTreeMap<ZoneApexDomain, PendingTransfer, Memory>;
+
+thread_local! {
+  /// The memory reference to the PendingTransfer repository.
+  static DB: RefCell<PendingTransferDatabase> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      BTreeMap::init(memory_manager.get(PENDING_TRANSFERS_MEMORY_ID))
+    )
+  })
+}
+
+/// A repository that enables keeping the pending transfers of the domains in stable memory, a domain has at most
+/// one pending transfer.
+pub struct PendingTransferRepository {}
+
+/// Enables the initialization of the PendingTransfer repository.
+impl PendingTransferRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for PendingTransferRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PendingTransferRepository {
+    /// Returns the pending transfer of the domain, if any.
+    pub fn get(&self, domain: &ZoneApexDomain) -> Option<PendingTransfer> {
+        DB.with(|m| m.borrow().get(domain))
+    }
+}
+
+/// Common interfaces for the PendingTransfer repository, inserting a transfer replaces the pending transfer of
+/// the domain.
+impl Repository<PendingTransfer> for PendingTransferRepository {
+    fn exists(&self, record: &PendingTransfer) -> bool {
+        DB.with(|m| m.borrow().contains_key(&record.domain))
+    }
+
+    fn insert(&self, record: PendingTransfer) {
+        DB.with(|m| m.borrow_mut().insert(record.domain.clone(), record));
+    }
+
+    fn remove(&self, record: &PendingTransfer) -> bool {
+        DB.with(|m| m.borrow_mut().remove(&record.domain).is_some())
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    repositories::{
+        LEGACY_REGISTRATIONS_MEMORY_ID, REGISTRATIONS_MEMORY_ID, REGISTRATION_SWEEPS_MEMORY_ID,
+    },
+    types::{LegacyRegistration, Registration},
+};
+use cns_domain_registry::{
+    repositories::{with_memory_manager, Memory, Repository},
+    types::ZoneApexDomain,
+};
+use ic_stable_structures::BTreeMap;
+use std::cell::RefCell;
+
+/// The database schema for the Registration repository, indexed by the registered domain.
+pub type RegistrationDatabase = BTreeMap<ZoneApexDomain, Registration, Memory>;
+
+/// The database schema for the index of the registrations that expire, sorted by the time they have to be swept.
+pub type RegistrationSweepIndex = BTreeMap<(u64, ZoneApexDomain), (), Memory>;
+
+thread_local! {
+  /// The memory reference to the Registration repository.
+  static DB: RefCell<RegistrationDatabase> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      BTreeMap::init(memory_manager.get(REGISTRATIONS_MEMORY_ID))
+    )
+  });
+
+  /// The memory reference to the index of the registrations by the time they have to be swept.
+  static SWEEP_INDEX: RefCell<RegistrationSweepIndex> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      BTreeMap::init(memory_manager.get(REGISTRATION_SWEEPS_MEMORY_ID))
+    )
+  });
+}
+
+/// A repository that enables keeping the registrations of the domains in stable memory.
+pub struct RegistrationRepository {}
+
+/// Enables the initialization of the Registration repository.
+impl RegistrationRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for RegistrationRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegistrationRepository {
+    /// Returns the registration of the domain, if it is registered.
+    pub fn get(&self, domain: &ZoneApexDomain) -> Option<Registration> {
+        DB.with(|m| m.borrow().get(domain))
+    }
+
+    /// Returns the number of registered domains.
+    pub fn count(&self) -> u64 {
+        DB.with(|m| m.borrow().len())
+    }
+
+    /// Returns up to `limit` registrations that have to be swept at the given time, the ones that are due the
+    /// longest first.
+    pub fn find_due_for_sweep(&self, time: u64, limit: usize) -> Vec<Registration> {
+        let domains = SWEEP_INDEX.with(|m| {
+            m.borrow()
+                .iter()
+                .take_while(|((sweep_at, _), _)| *sweep_at <= time)
+                .take(limit)
+                .map(|((_, domain), _)| domain)
+                .collect::<Vec<_>>()
+        });
+
+        domains
+            .iter()
+            .filter_map(|domain| self.get(domain))
+            .collect()
+    }
+
+    /// Moves the registrations stored by the versions of the canister before registrations had records and leases
+    /// to the current layout, the legacy registrations are removed once they are migrated.
+    ///
+    /// The registrations are migrated one by one within the upgrade, which bounds the number of legacy
+    /// registrations by the instruction limit of an upgrade, registrations could only be created by canister
+    /// controllers and for test domains until then.
+    pub fn migrate_legacy_registrations(&self) {
+        let memory = with_memory_manager(|memory_manager| {
+            memory_manager.get(LEGACY_REGISTRATIONS_MEMORY_ID)
+        });
+        let legacy_registrations =
+            BTreeMap::<ZoneApexDomain, LegacyRegistration, Memory>::init(memory);
+        if legacy_registrations.is_empty() {
+            return;
+        }
+
+        for (_, registration) in legacy_registrations.iter() {
+            self.insert(Registration::from(registration));
+        }
+        legacy_registrations.clear();
+    }
+
+    fn remove_from_sweep_index(registration: &Registration) {
+        if let Some(sweep_at) = registration.sweep_at() {
+            SWEEP_INDEX.with(|m| {
+                m.borrow_mut()
+                    .remove(&(sweep_at, registration.domain.clone()))
+            });
+        }
+    }
+}
+
+/// Common interfaces for the Registration repository, inserting a registration replaces the previous
+/// registration of the domain, the index of the sweeps is kept in sync with the registrations.
+impl Repository<Registration> for RegistrationRepository {
+    fn exists(&self, record: &Registration) -> bool {
+        DB.with(|m| m.borrow().contains_key(&record.domain))
+    }
+
+    fn insert(&self, record: Registration) {
+        if let Some(sweep_at) = record.sweep_at() {
+            SWEEP_INDEX.with(|m| m.borrow_mut().insert((sweep_at, record.domain.clone()), ()));
+        }
+
+        let previous = DB.with(|m| m.borrow_mut().insert(record.domain.clone(), record.clone()));
+        if let Some(previous) = previous.filter(|previous| previous.sweep_at() != record.sweep_at())
+        {
+            Self::remove_from_sweep_index(&previous);
+        }
+    }
+
+    fn remove(&self, record: &Registration) -> bool {
+        match DB.with(|m| m.borrow_mut().remove(&record.domain)) {
+            Some(previous) => {
+                Self::remove_from_sweep_index(&previous);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use cns_domain_registry::types::{RegistrationController, RegistrationControllerRole};
+
+    #[test]
+    fn migrate_legacy_registrations() {
+        let domain = ZoneApexDomain::new(String::from("legacy.icp.")).unwrap();
+        let controllers = vec![RegistrationController::new(
+            Principal::from_slice(&[1; 29]),
+            vec![RegistrationControllerRole::Registrant],
+        )];
+        let memory = with_memory_manager(|memory_manager| {
+            memory_manager.get(LEGACY_REGISTRATIONS_MEMORY_ID)
+        });
+        BTreeMap::<ZoneApexDomain, LegacyRegistration, Memory>::init(memory.clone()).insert(
+            domain.clone(),
+            LegacyRegistration {
+                domain: domain.clone(),
+                controllers: controllers.clone(),
+            },
+        );
+
+        let repository = RegistrationRepository::default();
+        repository.migrate_legacy_registrations();
+
+        assert_eq!(
+            repository.get(&domain),
+            Some(Registration::new(domain, controllers, vec![], None))
+        );
+        assert!(BTreeMap::<ZoneApexDomain, LegacyRegistration, Memory>::init(memory).is_empty());
+        assert!(repository.find_due_for_sweep(u64::MAX, 10).is_empty());
+    }
+
+    #[test]
+    fn failed_sweeps_are_retried_after_due_registrations() {
+        let repository = RegistrationRepository::default();
+        let registration = |label: &str, expires_at: u64| {
+            Registration::new(
+                ZoneApexDomain::new(format!("{}.icp.", label)).unwrap(),
+                vec![],
+                vec![],
+                Some(expires_at),
+            )
+        };
+        repository.insert(Registration {
+            sweep_retry_at: Some(30),
+            ..registration("failing", 10)
+        });
+        repository.insert(registration("due", 20));
+
+        let due = |time: u64| {
+            repository
+                .find_due_for_sweep(time, 1)
+                .into_iter()
+                .map(|registration| registration.domain.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(due(25), vec![String::from("due.icp.")]);
+        repository.remove(&registration("due", 20));
+        assert!(due(25).is_empty());
+        assert_eq!(due(30), vec![String::from("failing.icp.")]);
+    }
+}
@@ -0,0 +1,24 @@
+use ic_stable_structures::memory_manager::MemoryId;
+
+/// Stable memory id used to store the registrations of the domains before they had records and leases, they are
+/// migrated to [REGISTRATIONS_MEMORY_ID] on upgrade.
+///
+/// The memories are shared with the repositories of the name registry, which use the ids starting from 0, hence
+/// the ids of the operator start from the end of the range.
+pub const LEGACY_REGISTRATIONS_MEMORY_ID: MemoryId = MemoryId::new(254);
+
+/// Stable memory id used to store the registration policies of the TLDs.
+pub const TLD_POLICIES_MEMORY_ID: MemoryId = MemoryId::new(253);
+
+/// Stable memory id used to store the index of the registrations by the time they have to be swept.
+pub const REGISTRATION_SWEEPS_MEMORY_ID: MemoryId = MemoryId::new(252);
+
+/// Stable memory id used to store the pending transfers of the domains.
+pub const PENDING_TRANSFERS_MEMORY_ID: MemoryId = MemoryId::new(251);
+
+/// Stable memory id used to store the transfer log of the domains.
+pub const TRANSFER_LOG_MEMORY_ID: MemoryId = MemoryId::new(250);
+
+/// Stable memory id used to store the registrations of the domains, a new memory is used since the registrations
+/// with records and leases exceed the size the legacy registrations were stored with.
+pub const REGISTRATIONS_MEMORY_ID: MemoryId = MemoryId::new(249);
@@ -0,0 +1,67 @@
+use crate::{repositories::TLD_POLICIES_MEMORY_ID, types::TldPolicy};
+use cns_domain_registry::{
+    repositories::{with_memory_manager, Memory, Repository},
+    types::ZoneApexDomain,
+};
+use ic_stable_structures::BTreeMap;
+use std::cell::RefCell;
+
+/// The database schema for the TldPolicy repository, indexed by the TLD.
+pub type TldPolicyDatabase = BTreeMap<ZoneApexDomain, TldPolicy, Memory>;
+
+thread_local! {
+  /// The memory reference to the TldPolicy repository.
+  static DB: RefCell<TldPolicyDatabase> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      BTreeMap::init(memory_manager.get(TLD_POLICIES_MEMORY_ID))
+    )
+  })
+}
+
+/// A repository that enables keeping the registration policies of the TLDs in stable memory.
+pub struct TldPolicyRepository {}
+
+/// Enables the initialization of the TldPolicy repository.
+impl TldPolicyRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for TldPolicyRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TldPolicyRepository {
+    /// Returns the policy of the TLD, if the TLD is operated by the canister.
+    pub fn get(&self, tld: &ZoneApexDomain) -> Option<TldPolicy> {
+        DB.with(|m| m.borrow().get(tld))
+    }
+
+    /// Returns the policies of all the TLDs, sorted by their TLD.
+    pub fn list(&self) -> Vec<TldPolicy> {
+        DB.with(|m| m.borrow().iter().map(|(_, policy)| policy).collect())
+    }
+
+    /// Returns the number of TLDs operated by the canister.
+    pub fn count(&self) -> u64 {
+        DB.with(|m| m.borrow().len())
+    }
+}
+
+/// Common interfaces for the TldPolicy repository, inserting a policy replaces the previous policy of the TLD.
+impl Repository<TldPolicy> for TldPolicyRepository {
+    fn exists(&self, record: &TldPolicy) -> bool {
+        DB.with(|m| m.borrow().contains_key(&record.tld))
+    }
+
+    fn insert(&self, record: TldPolicy) {
+        DB.with(|m| m.borrow_mut().insert(record.tld.clone(), record));
+    }
+
+    fn remove(&self, record: &TldPolicy) -> bool {
+        DB.with(|m| m.borrow_mut().remove(&record.tld).is_some())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    common::MAX_TRANSFER_LOG_ENTRIES, repositories::TRANSFER_LOG_MEMORY_ID, types::TransferLogEntry,
+};
+use cns_domain_registry::{
+    repositories::{with_memory_manager, Memory},
+    types::ZoneApexDomain,
+};
+use ic_stable_structures::BTreeMap;
+use std::cell::RefCell;
+
+/// The database schema for the TransferLog repository, indexed by the domain and the sequence of the entry, which
+/// keeps the entries of a domain next to each other in the order they were added.
+pub type TransferLogDatabase = BTreeMap<(ZoneApexDomain, u64), TransferLogEntry, Memory>;
+
+thread_local! {
+  /// The memory reference to the TransferLog repository.
+  static DB: RefCell<TransferLogDatabase> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      BTreeMap::init(memory_manager.get(TRANSFER_LOG_MEMORY_ID))
+    )
+  })
+}
+
+/// A repository that enables keeping the transfer log of the domains in stable memory, only the latest
+/// [MAX_TRANSFER_LOG_ENTRIES] entries of each domain are kept.
+pub struct TransferLogRepository {}
+
+/// Enables the initialization of the TransferLog repository.
+impl TransferLogRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for TransferLogRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransferLogRepository {
+    /// Returns the entries of the transfer log of the domain in the order they were added.
+    pub fn list(&self, domain: &ZoneApexDomain) -> Vec<TransferLogEntry> {
+        DB.with(|m| {
+            m.borrow()
+                .range((domain.clone(), 0)..=(domain.clone(), u64::MAX))
+                .map(|(_, entry)| entry)
+                .collect()
+        })
+    }
+
+    /// Adds the entry to the transfer log of the domain with the next sequence of the log and removes the oldest
+    /// entries beyond the max number of entries, the sequence of the given entry is ignored.
+    pub fn append(&self, domain: &ZoneApexDomain, entry: TransferLogEntry) -> TransferLogEntry {
+        DB.with(|m| {
+            let mut log = m.borrow_mut();
+            let sequences = log
+                .range((domain.clone(), 0)..=(domain.clone(), u64::MAX))
+                .map(|((_, sequence), _)| sequence)
+                .collect::<Vec<_>>();
+            let sequence = sequences.last().map_or(0, |sequence| sequence + 1);
+            let entry = TransferLogEntry { sequence, ..entry };
+            log.insert((domain.clone(), sequence), entry.clone());
+
+            let removed_count =
+                (sequences.len() as u64 + 1).saturating_sub(MAX_TRANSFER_LOG_ENTRIES);
+            for sequence in sequences.into_iter().take(removed_count as usize) {
+                log.remove(&(domain.clone(), sequence));
+            }
+
+            entry
+        })
+    }
+}
@@ -0,0 +1,408 @@
+use crate::{
+    errors::ManageControllersError, repositories::RegistrationRepository,
+    services::TldPolicyService, types::Registration,
+};
+use candid::Principal;
+use cns_domain_registry::{
+    repositories::Repository,
+    types::{CallContext, RegistrationController, RegistrationControllerRole, ZoneApexDomain},
+};
+use std::ops::Deref;
+
+/// A service that manages the controllers of the registered domains and their roles.
+///
+/// The registrant owns the domain and only changes with a transfer. Registrars act on behalf of the registrant and
+/// can do everything the registrant can, technical controllers can change the records of the domain and
+/// administrative controllers its controllers. Only the registrant, registrars and canister controllers can grant
+/// or revoke the registrar role, which would otherwise allow administrative controllers to take over the domain.
+pub struct ControllerService {
+    registration_repository: RegistrationRepository,
+    tld_policy_service: TldPolicyService,
+}
+
+impl ControllerService {
+    pub fn new() -> Self {
+        Self {
+            registration_repository: RegistrationRepository::default(),
+            tld_policy_service: TldPolicyService::default(),
+        }
+    }
+
+    /// Returns the controllers of the domain and their roles, the controllers are public like the records of the
+    /// domain.
+    pub fn get_controllers(&self, domain: &str, time: u64) -> Vec<RegistrationController> {
+        ZoneApexDomain::new(domain.to_ascii_lowercase())
+            .ok()
+            .and_then(|domain| self.registration_repository.get(&domain))
+            .filter(|registration| !self.is_released(registration, time))
+            .map(|registration| registration.controllers)
+            .unwrap_or_default()
+    }
+
+    /// Adds the controller to the domain or, if it already controls the domain, replaces its roles.
+    pub fn add_controller(
+        &self,
+        context: &CallContext,
+        domain: &str,
+        controller: &RegistrationController,
+    ) -> Result<(), ManageControllersError> {
+        let mut registration = self.find_registration(context, domain)?;
+        let mut roles = controller.roles.clone();
+        roles.sort();
+        roles.dedup();
+        let controller = RegistrationController::new(controller.controller_id, roles);
+
+        self.authorize(context, &registration, &controller)?;
+        let invalid_controller = |reason: &str| ManageControllersError::InvalidController {
+            controller: controller.controller_id.to_text(),
+            reason: reason.to_string(),
+        };
+        if controller.controller_id == Principal::anonymous() {
+            return Err(invalid_controller(
+                "the anonymous principal can't be a controller",
+            ));
+        }
+        if controller.roles.is_empty() {
+            return Err(invalid_controller("at least one role is required"));
+        }
+
+        let controller_count = registration.controllers.len();
+        match registration
+            .controllers
+            .iter_mut()
+            .find(|existing| existing.controller_id == controller.controller_id)
+        {
+            Some(existing) => existing.roles = controller.roles,
+            None if controller_count >= Registration::MAX_CONTROLLERS => {
+                return Err(ManageControllersError::TooManyControllers {
+                    domain: registration.domain.deref().to_string(),
+                    max_controllers: Registration::MAX_CONTROLLERS,
+                })
+            }
+            None => registration.controllers.push(controller),
+        }
+        self.registration_repository.insert(registration);
+
+        Ok(())
+    }
+
+    /// Removes the controller from the domain, the registrant can't be removed.
+    pub fn remove_controller(
+        &self,
+        context: &CallContext,
+        domain: &str,
+        controller_id: Principal,
+    ) -> Result<(), ManageControllersError> {
+        let mut registration = self.find_registration(context, domain)?;
+        let Some(controller) = registration.controller(&controller_id).cloned() else {
+            return Err(ManageControllersError::ControllerNotFound {
+                domain: registration.domain.deref().to_string(),
+                controller: controller_id.to_text(),
+            });
+        };
+
+        self.authorize(context, &registration, &controller)?;
+        registration
+            .controllers
+            .retain(|existing| existing.controller_id != controller_id);
+        self.registration_repository.insert(registration);
+
+        Ok(())
+    }
+
+    /// Authorizes the caller to change the given controller of the registration, either its current roles or the
+    /// roles it is given.
+    fn authorize(
+        &self,
+        context: &CallContext,
+        registration: &Registration,
+        controller: &RegistrationController,
+    ) -> Result<(), ManageControllersError> {
+        let existing_roles = registration
+            .controller(&controller.controller_id)
+            .map(|existing| existing.roles.as_slice())
+            .unwrap_or_default();
+        let changes_registrar = existing_roles
+            .iter()
+            .chain(controller.roles.iter())
+            .any(RegistrationControllerRole::can_manage_registration);
+        let is_authorized = context.is_canister_controller
+            || match changes_registrar {
+                true => registration.can_manage_registration(&context.caller),
+                false => registration.can_manage_controllers(&context.caller),
+            };
+        if !is_authorized {
+            return Err(ManageControllersError::Unauthorized {
+                caller: context.caller.to_text(),
+                domain: registration.domain.deref().to_string(),
+                controller: controller.controller_id.to_text(),
+            });
+        }
+
+        if registration.registrant() == Some(controller.controller_id)
+            || controller.has_role(&RegistrationControllerRole::Registrant)
+        {
+            return Err(ManageControllersError::RegistrantChange {
+                domain: registration.domain.deref().to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the registration of the domain, the registration must not be expired.
+    fn find_registration(
+        &self,
+        context: &CallContext,
+        domain: &str,
+    ) -> Result<Registration, ManageControllersError> {
+        let domain = ZoneApexDomain::new(domain.to_ascii_lowercase())
+            .map_err(ManageControllersError::InvalidDomain)?;
+        let Some(registration) = self
+            .registration_repository
+            .get(&domain)
+            .filter(|registration| !self.is_released(registration, context.time))
+        else {
+            return Err(ManageControllersError::NotRegistered {
+                domain: domain.deref().to_string(),
+            });
+        };
+
+        if registration.is_expired(context.time) {
+            return Err(ManageControllersError::RegistrationExpired {
+                domain: domain.deref().to_string(),
+            });
+        }
+
+        Ok(registration)
+    }
+
+    fn is_released(&self, registration: &Registration, time: u64) -> bool {
+        self.tld_policy_service
+            .find_policy(&registration.domain)
+            .map_or(true, |policy| policy.is_released(registration, time))
+    }
+}
+
+impl Default for ControllerService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        errors::TransferError,
+        services::{RegistrationService, TransferService},
+    };
+    use candid::Nat;
+    use cns_domain_registry::api::{DomainRecord as DomainRecordResult, RegistrationRecords};
+    use rstest::rstest;
+
+    const CANISTER_ID: &str = "qoctq-giaaa-aaaaa-aaaea-cai";
+    const OTHER_CANISTER_ID: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+    const DOMAIN: &str = "controlled.test.icp.";
+
+    fn user(id: u8) -> CallContext {
+        CallContext::new(Principal::from_slice(&[id; 29]), false, 0)
+    }
+
+    fn register(context: &CallContext, data: &str) -> Result<(), crate::errors::RegisterError> {
+        TldPolicyService::default().install_default_policy();
+        RegistrationService::default().register(
+            context,
+            DOMAIN,
+            &RegistrationRecords {
+                controllers: vec![],
+                records: Some(vec![DomainRecordResult {
+                    name: DOMAIN.to_string(),
+                    record_type: String::from("CID"),
+                    ttl: Nat::from(60u32),
+                    data: data.to_string(),
+                }]),
+            },
+        )
+    }
+
+    fn controller(id: u8, role: RegistrationControllerRole) -> RegistrationController {
+        RegistrationController::new(user(id).caller, vec![role])
+    }
+
+    fn add_controller(
+        context: &CallContext,
+        controller: &RegistrationController,
+    ) -> Result<(), ManageControllersError> {
+        ControllerService::default().add_controller(context, DOMAIN, controller)
+    }
+
+    #[test]
+    fn technical_controller_changes_records_but_not_ownership() {
+        register(&user(1), CANISTER_ID).unwrap();
+        add_controller(
+            &user(1),
+            &controller(2, RegistrationControllerRole::Technical),
+        )
+        .unwrap();
+
+        register(&user(2), OTHER_CANISTER_ID).unwrap();
+        let controllers = ControllerService::default().get_controllers(DOMAIN, 0);
+        assert_eq!(
+            controllers,
+            vec![
+                controller(1, RegistrationControllerRole::Registrant),
+                controller(2, RegistrationControllerRole::Technical),
+            ]
+        );
+
+        assert_eq!(
+            TransferService::default().initiate_transfer(&user(2), DOMAIN, user(2).caller),
+            Err(TransferError::Unauthorized {
+                caller: user(2).caller.to_text(),
+                domain: DOMAIN.to_string(),
+            })
+        );
+        assert_eq!(
+            add_controller(
+                &user(2),
+                &controller(3, RegistrationControllerRole::Technical)
+            ),
+            Err(ManageControllersError::Unauthorized {
+                caller: user(2).caller.to_text(),
+                domain: DOMAIN.to_string(),
+                controller: user(3).caller.to_text(),
+            })
+        );
+    }
+
+    #[test]
+    fn administrative_controller_manages_controllers_but_not_records() {
+        register(&user(1), CANISTER_ID).unwrap();
+        add_controller(
+            &user(1),
+            &controller(2, RegistrationControllerRole::Administrative),
+        )
+        .unwrap();
+
+        add_controller(
+            &user(2),
+            &controller(3, RegistrationControllerRole::Technical),
+        )
+        .unwrap();
+        ControllerService::default()
+            .remove_controller(&user(2), DOMAIN, user(3).caller)
+            .unwrap();
+        assert_eq!(
+            ControllerService::default()
+                .get_controllers(DOMAIN, 0)
+                .len(),
+            2
+        );
+
+        assert!(register(&user(2), OTHER_CANISTER_ID).is_err());
+        // administrative controllers can't grant themselves the registrar role
+        assert_eq!(
+            add_controller(
+                &user(2),
+                &controller(2, RegistrationControllerRole::Registrar)
+            ),
+            Err(ManageControllersError::Unauthorized {
+                caller: user(2).caller.to_text(),
+                domain: DOMAIN.to_string(),
+                controller: user(2).caller.to_text(),
+            })
+        );
+    }
+
+    #[test]
+    fn registrar_acts_on_behalf_of_registrant() {
+        register(&user(1), CANISTER_ID).unwrap();
+        add_controller(
+            &CallContext::canister_controller(0),
+            &controller(2, RegistrationControllerRole::Registrar),
+        )
+        .unwrap();
+
+        register(&user(2), OTHER_CANISTER_ID).unwrap();
+        add_controller(
+            &user(2),
+            &controller(3, RegistrationControllerRole::Administrative),
+        )
+        .unwrap();
+        TransferService::default()
+            .initiate_transfer(&user(2), DOMAIN, user(4).caller)
+            .unwrap();
+        TransferService::default()
+            .accept_transfer(&user(4), DOMAIN)
+            .unwrap();
+
+        // the controllers granted by the previous registrant don't keep their roles
+        assert_eq!(
+            ControllerService::default().get_controllers(DOMAIN, 0),
+            vec![controller(4, RegistrationControllerRole::Registrant)]
+        );
+    }
+
+    #[rstest]
+    #[case::registrant_role(
+        controller(2, RegistrationControllerRole::Registrant),
+        ManageControllersError::RegistrantChange { domain: DOMAIN.to_string() }
+    )]
+    #[case::change_registrant(
+        controller(1, RegistrationControllerRole::Technical),
+        ManageControllersError::RegistrantChange { domain: DOMAIN.to_string() }
+    )]
+    #[case::no_roles(
+        RegistrationController::new(user(2).caller, vec![]),
+        ManageControllersError::InvalidController {
+            controller: user(2).caller.to_text(),
+            reason: String::from("at least one role is required"),
+        }
+    )]
+    #[case::anonymous(
+        RegistrationController::new(Principal::anonymous(), vec![RegistrationControllerRole::Technical]),
+        ManageControllersError::InvalidController {
+            controller: Principal::anonymous().to_text(),
+            reason: String::from("the anonymous principal can't be a controller"),
+        }
+    )]
+    fn add_controller_rejects_invalid_controllers(
+        #[case] controller: RegistrationController,
+        #[case] expected: ManageControllersError,
+    ) {
+        register(&user(1), CANISTER_ID).unwrap();
+
+        assert_eq!(add_controller(&user(1), &controller), Err(expected));
+    }
+
+    #[test]
+    fn controllers_are_limited() {
+        register(&user(1), CANISTER_ID).unwrap();
+        for id in 2..=Registration::MAX_CONTROLLERS as u8 {
+            add_controller(
+                &user(1),
+                &controller(id, RegistrationControllerRole::Technical),
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            add_controller(
+                &user(1),
+                &controller(100, RegistrationControllerRole::Technical)
+            ),
+            Err(ManageControllersError::TooManyControllers {
+                domain: DOMAIN.to_string(),
+                max_controllers: Registration::MAX_CONTROLLERS,
+            })
+        );
+        assert_eq!(
+            ControllerService::default().remove_controller(&user(1), DOMAIN, user(100).caller),
+            Err(ManageControllersError::ControllerNotFound {
+                domain: DOMAIN.to_string(),
+                controller: user(100).caller.to_text(),
+            })
+        );
+    }
+}
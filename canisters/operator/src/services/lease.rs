@@ -0,0 +1,409 @@
+use crate::{
+    common::{EXPIRED_REGISTRATIONS_SWEEP_INTERVAL, MAX_SWEPT_REGISTRATIONS},
+    errors::RenewError,
+    repositories::RegistrationRepository,
+    services::{RegistrationService, TldPolicyService},
+    types::Registration,
+};
+use cns_domain_registry::{
+    repositories::Repository,
+    types::{CallContext, ZoneApexDomain},
+};
+use std::ops::Deref;
+
+/// A service that manages the leases of the registrations, which expire unless they are renewed.
+///
+/// Expired registrations stop resolving, the sweep removes their records from the zone of their TLD and, once their
+/// redemption period ended, the registrations themselves.
+pub struct LeaseService {
+    registration_repository: RegistrationRepository,
+    registration_service: RegistrationService,
+    tld_policy_service: TldPolicyService,
+}
+
+impl LeaseService {
+    pub fn new() -> Self {
+        Self {
+            registration_repository: RegistrationRepository::default(),
+            registration_service: RegistrationService::default(),
+            tld_policy_service: TldPolicyService::default(),
+        }
+    }
+
+    /// Returns the registration of the domain if it expired at the given time.
+    pub fn find_expired(&self, domain: &str, time: u64) -> Option<Registration> {
+        let domain = ZoneApexDomain::new(domain.to_ascii_lowercase()).ok()?;
+
+        self.registration_repository
+            .get(&domain)
+            .filter(|registration| registration.is_expired(time))
+    }
+
+    /// Extends the registration of the domain by the registration period of the lease of its TLD, starting from
+    /// its expiry or, if it already expired, from now. The records of a suspended registration are restored.
+    ///
+    /// The registrant and registrars can renew the registration until the end of its grace period, canister
+    /// controllers until the end of its redemption period. A registration can't be renewed before it expires within
+    /// a registration period, which prevents leasing a domain indefinitely ahead.
+    pub fn renew(&self, context: &CallContext, domain: &str) -> Result<(), RenewError> {
+        let domain =
+            ZoneApexDomain::new(domain.to_ascii_lowercase()).map_err(RenewError::InvalidDomain)?;
+        let not_registered = || RenewError::NotRegistered {
+            domain: domain.deref().to_string(),
+        };
+        let policy = self
+            .tld_policy_service
+            .find_policy(&domain)
+            .ok_or_else(not_registered)?;
+        let registration = self
+            .registration_repository
+            .get(&domain)
+            .filter(|registration| !policy.is_released(registration, context.time))
+            .ok_or_else(not_registered)?;
+        let (Some(expires_at), Some(lease)) = (registration.expires_at, &policy.lease) else {
+            return Err(RenewError::NotExpiring {
+                domain: domain.deref().to_string(),
+            });
+        };
+
+        if !context.is_canister_controller {
+            if !registration.can_manage_registration(&context.caller) {
+                return Err(RenewError::Unauthorized {
+                    caller: context.caller.to_text(),
+                    domain: domain.deref().to_string(),
+                });
+            }
+
+            if policy.grace_period_end(expires_at) <= context.time {
+                return Err(RenewError::RedemptionPeriod {
+                    domain: domain.deref().to_string(),
+                });
+            }
+        }
+
+        if expires_at > lease.expires_at(context.time) {
+            return Err(RenewError::TooEarly {
+                domain: domain.deref().to_string(),
+                renewable_at: expires_at.saturating_sub(lease.expires_at(0)),
+                expires_at,
+            });
+        }
+
+        if registration.is_suspended() {
+            self.registration_service
+                .restore_records(context, &policy, &registration)
+                .map_err(RenewError::ManageRecords)?;
+        }
+
+        self.registration_repository.insert(Registration {
+            expires_at: Some(lease.expires_at(expires_at.max(context.time))),
+            suspended_until: None,
+            sweep_retry_at: None,
+            ..registration
+        });
+
+        Ok(())
+    }
+
+    /// Sweeps the registrations that are due at the time of the context, the records of the registrations that
+    /// expired are removed and the registrations whose redemption period ended are removed as well.
+    ///
+    /// At most [MAX_SWEPT_REGISTRATIONS] registrations are swept at once, the remaining ones are left for the next
+    /// sweep. A registration whose records can't be removed is retried by the next sweep, after the ones that are
+    /// due by then, so that failing registrations don't block the others.
+    pub fn sweep_expired(&self, context: &CallContext) {
+        for registration in self
+            .registration_repository
+            .find_due_for_sweep(context.time, MAX_SWEPT_REGISTRATIONS)
+        {
+            let Some(policy) = self.tld_policy_service.find_policy(&registration.domain) else {
+                self.registration_repository.remove(&registration);
+                continue;
+            };
+
+            if !registration.is_suspended()
+                && self
+                    .registration_service
+                    .remove_records(context, &policy, &registration.domain)
+                    .is_err()
+            {
+                let retry_at = context
+                    .time
+                    .saturating_add(EXPIRED_REGISTRATIONS_SWEEP_INTERVAL.as_nanos() as u64);
+                self.registration_repository.insert(Registration {
+                    sweep_retry_at: Some(retry_at),
+                    ..registration
+                });
+                continue;
+            }
+
+            let removed_at =
+                policy.redemption_period_end(registration.expires_at.unwrap_or(context.time));
+            match removed_at <= context.time {
+                true => {
+                    self.registration_repository.remove(&registration);
+                }
+                false => self.registration_repository.insert(Registration {
+                    suspended_until: Some(removed_at),
+                    sweep_retry_at: None,
+                    ..registration
+                }),
+            }
+        }
+    }
+}
+
+impl Default for LeaseService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{LeasePolicy, TldPolicy};
+    use candid::{Nat, Principal};
+    use cns_domain_registry::{
+        api::{DomainRecord as DomainRecordResult, RegistrationRecords},
+        services::LookupService,
+    };
+
+    const CANISTER_ID: &str = "qoctq-giaaa-aaaaa-aaaea-cai";
+    const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    /// Registrations of the community TLD last 10 days, with 2 days of grace and 3 days of redemption period.
+    fn set_community_policy() {
+        TldPolicyService::default()
+            .set_policy(
+                &CallContext::canister_controller(0),
+                &TldPolicy {
+                    tld: ZoneApexDomain::new(String::from("dao.")).unwrap(),
+                    self_service: true,
+                    allowed_record_types: vec![String::from("CID")],
+                    reserved_labels: vec![],
+                    test_label: None,
+                    lease: Some(LeasePolicy {
+                        registration_period: 10 * DAY / 1_000_000_000,
+                        grace_period: 2 * DAY / 1_000_000_000,
+                        redemption_period: 3 * DAY / 1_000_000_000,
+                    }),
+                },
+            )
+            .unwrap();
+    }
+
+    fn user(id: u8, time: u64) -> CallContext {
+        CallContext::new(Principal::from_slice(&[id; 29]), false, time)
+    }
+
+    fn register(context: &CallContext, domain: &str) {
+        RegistrationService::default()
+            .register(
+                context,
+                domain,
+                &RegistrationRecords {
+                    controllers: vec![],
+                    records: Some(vec![DomainRecordResult {
+                        name: domain.to_string(),
+                        record_type: String::from("CID"),
+                        ttl: Nat::from(60u32),
+                        data: CANISTER_ID.to_string(),
+                    }]),
+                },
+            )
+            .unwrap();
+    }
+
+    fn has_records(domain: &str) -> bool {
+        !LookupService::default()
+            .lookup(domain, "CID")
+            .answers
+            .is_empty()
+    }
+
+    fn has_reverse_index() -> bool {
+        !LookupService::default()
+            .lookup(&format!("{}.reverse.dao.", CANISTER_ID), "PTR")
+            .answers
+            .is_empty()
+    }
+
+    fn registration(domain: &str) -> Option<Registration> {
+        RegistrationRepository::default().get(&ZoneApexDomain::new(domain.to_string()).unwrap())
+    }
+
+    #[test]
+    fn registrations_expire_after_registration_period() {
+        set_community_policy();
+        register(&user(1, DAY), "mine.dao.");
+
+        let service = LeaseService::default();
+        assert_eq!(
+            registration("mine.dao.").unwrap().expires_at,
+            Some(11 * DAY)
+        );
+        assert!(service.find_expired("mine.dao.", 11 * DAY - 1).is_none());
+        assert!(service.find_expired("MINE.dao.", 11 * DAY).is_some());
+
+        // registering the domain again keeps its expiry
+        register(&user(1, 2 * DAY), "mine.dao.");
+        assert_eq!(
+            registration("mine.dao.").unwrap().expires_at,
+            Some(11 * DAY)
+        );
+    }
+
+    #[test]
+    fn renew_extends_registration() {
+        set_community_policy();
+        register(&user(1, 0), "mine.dao.");
+
+        let service = LeaseService::default();
+        assert_eq!(
+            service.renew(&user(2, DAY), "mine.dao."),
+            Err(RenewError::Unauthorized {
+                caller: user(2, 0).caller.to_text(),
+                domain: String::from("mine.dao."),
+            })
+        );
+
+        service.renew(&user(1, DAY), "mine.dao.").unwrap();
+        assert_eq!(
+            registration("mine.dao.").unwrap().expires_at,
+            Some(20 * DAY)
+        );
+
+        // a registration is renewable once it expires within a registration period
+        assert_eq!(
+            service.renew(&user(1, 10 * DAY - 1), "mine.dao."),
+            Err(RenewError::TooEarly {
+                domain: String::from("mine.dao."),
+                renewable_at: 10 * DAY,
+                expires_at: 20 * DAY,
+            })
+        );
+        service.renew(&user(1, 10 * DAY), "mine.dao.").unwrap();
+        assert_eq!(
+            registration("mine.dao.").unwrap().expires_at,
+            Some(30 * DAY)
+        );
+    }
+
+    #[test]
+    fn sweep_suspends_and_removes_expired_registrations() {
+        set_community_policy();
+        register(&user(1, 0), "mine.dao.");
+        assert!(has_records("mine.dao.") && has_reverse_index());
+
+        let service = LeaseService::default();
+        service.sweep_expired(&CallContext::canister_controller(10 * DAY - 1));
+        assert!(has_records("mine.dao."));
+
+        // the records of expired registrations are removed together with their reverse index
+        service.sweep_expired(&CallContext::canister_controller(10 * DAY));
+        assert!(!has_records("mine.dao.") && !has_reverse_index());
+        assert_eq!(
+            registration("mine.dao.").unwrap().suspended_until,
+            Some(15 * DAY)
+        );
+
+        // other callers can only register the domain once the redemption period ended
+        service.sweep_expired(&CallContext::canister_controller(15 * DAY - 1));
+        assert!(registration("mine.dao.").is_some());
+        service.sweep_expired(&CallContext::canister_controller(15 * DAY));
+        assert!(registration("mine.dao.").is_none());
+        register(&user(2, 15 * DAY), "mine.dao.");
+        assert!(has_records("mine.dao."));
+    }
+
+    #[test]
+    fn renew_restores_suspended_registrations() {
+        set_community_policy();
+        register(&user(1, 0), "grace.dao.");
+        register(&user(1, 0), "redeemed.dao.");
+        let service = LeaseService::default();
+        service.sweep_expired(&CallContext::canister_controller(10 * DAY));
+
+        // the registrant can renew during the grace period
+        service.renew(&user(1, 11 * DAY), "grace.dao.").unwrap();
+        assert!(has_records("grace.dao."));
+        assert!(service.find_expired("grace.dao.", 11 * DAY).is_none());
+        assert_eq!(
+            registration("grace.dao.").unwrap().expires_at,
+            Some(21 * DAY)
+        );
+
+        // only canister controllers can restore the registration during the redemption period
+        assert_eq!(
+            service.renew(&user(1, 12 * DAY), "redeemed.dao."),
+            Err(RenewError::RedemptionPeriod {
+                domain: String::from("redeemed.dao.")
+            })
+        );
+        service
+            .renew(&CallContext::canister_controller(12 * DAY), "redeemed.dao.")
+            .unwrap();
+        assert!(has_records("redeemed.dao."));
+        assert_eq!(
+            registration("redeemed.dao.").unwrap().expires_at,
+            Some(22 * DAY)
+        );
+    }
+
+    #[test]
+    fn expired_registrations_are_not_registered_again() {
+        set_community_policy();
+        register(&user(1, 0), "lapsed.dao.");
+        LeaseService::default().sweep_expired(&CallContext::canister_controller(10 * DAY));
+
+        // registering the domain again would extend the lease without the restrictions of `renew`
+        for time in [11 * DAY, 12 * DAY] {
+            assert_eq!(
+                RegistrationService::default().register(
+                    &user(1, time),
+                    "lapsed.dao.",
+                    &RegistrationRecords {
+                        controllers: vec![],
+                        records: Some(vec![DomainRecordResult {
+                            name: String::from("lapsed.dao."),
+                            record_type: String::from("CID"),
+                            ttl: Nat::from(60u32),
+                            data: CANISTER_ID.to_string(),
+                        }]),
+                    },
+                ),
+                Err(crate::errors::RegisterError::RegistrationExpired {
+                    domain: String::from("lapsed.dao.")
+                })
+            );
+        }
+        assert!(!has_records("lapsed.dao."));
+        assert_eq!(
+            registration("lapsed.dao.").unwrap().expires_at,
+            Some(10 * DAY)
+        );
+    }
+
+    #[test]
+    fn registrations_of_canister_controllers_never_expire() {
+        set_community_policy();
+        register(&CallContext::canister_controller(0), "system.dao.");
+
+        let service = LeaseService::default();
+        assert_eq!(
+            service.renew(&CallContext::canister_controller(0), "system.dao."),
+            Err(RenewError::NotExpiring {
+                domain: String::from("system.dao.")
+            })
+        );
+        assert_eq!(
+            service.renew(&CallContext::canister_controller(0), "unknown.dao."),
+            Err(RenewError::NotRegistered {
+                domain: String::from("unknown.dao.")
+            })
+        );
+        service.sweep_expired(&CallContext::canister_controller(u64::MAX));
+        assert!(has_records("system.dao."));
+    }
+}
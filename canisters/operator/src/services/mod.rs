@@ -0,0 +1,21 @@
+//! Services of the operator canister, where the business logic is implemented.
+
+/// Service for the controllers of the registered domains.
+mod controller;
+pub use controller::*;
+
+/// Service for the leases of the registrations.
+mod lease;
+pub use lease::*;
+
+/// Service for the registration of the domains of the TLD.
+mod registration;
+pub use registration::*;
+
+/// Service for the registration policies of the TLDs.
+mod tld_policy;
+pub use tld_policy::*;
+
+/// Service for the transfers of the domains between registrants.
+mod transfer;
+pub use transfer::*;
@@ -0,0 +1,960 @@
+use crate::{
+    common::{
+        MAX_REGISTRATION_FIELD_LENGTH, MAX_REGISTRATION_RECORDS, REVERSE_LABEL, SUBNET_LABEL,
+    },
+    errors::RegisterError,
+    repositories::RegistrationRepository,
+    services::TldPolicyService,
+    types::{Registration, TldPolicy},
+};
+use candid::Principal;
+use cns_domain_registry::{
+    api::{
+        DomainRecord as DomainRecordResult, DomainRecordInput, ManageRecordsInput,
+        ManageRecordsOperation, RegistrationRecords, RemoveRecordOperationInput,
+    },
+    errors::ManageRecordsError,
+    repositories::Repository,
+    services::{LookupService, RecordsService},
+    types::{
+        CallContext, DomainRecordTypes, RegistrationController, RegistrationControllerRole,
+        ZoneApexDomain,
+    },
+};
+use std::{collections::BTreeSet, ops::Deref};
+
+/// The last byte of the principals of canisters.
+const CANISTER_PRINCIPAL_SUFFIX: u8 = 0x01;
+
+/// The last byte of self-authenticating principals, which are used for the subnets.
+const SELF_AUTHENTICATING_PRINCIPAL_SUFFIX: u8 = 0x02;
+
+/// The byte length of the principals of canisters.
+const CANISTER_PRINCIPAL_LENGTH: usize = 10;
+
+/// The byte length of self-authenticating principals.
+const SELF_AUTHENTICATING_PRINCIPAL_LENGTH: usize = 29;
+
+/// A service that registers the domains of the TLDs operated by the canister.
+///
+/// The records of the registered domains are written to the zone of their TLD with `manage_records`, hence the
+/// name registry resolves, certifies and records them in the history of the zone.
+pub struct RegistrationService {
+    registration_repository: RegistrationRepository,
+    records_service: RecordsService,
+    lookup_service: LookupService,
+    tld_policy_service: TldPolicyService,
+}
+
+impl RegistrationService {
+    pub fn new() -> Self {
+        Self {
+            registration_repository: RegistrationRepository::default(),
+            records_service: RecordsService::default(),
+            lookup_service: LookupService::default(),
+            tld_policy_service: TldPolicyService::default(),
+        }
+    }
+
+    /// Registers the domain with its records, replacing the records of a previous registration of the domain.
+    ///
+    /// A registration has up to [MAX_REGISTRATION_RECORDS] records of the domain, e.g. several CID and TXT records,
+    /// which are written as whole RRsets. The caller becomes the registrant of the domain unless it is already a
+    /// controller of the domain, in which case the controllers are kept. The registrant, registrars and technical
+    /// controllers of a domain can change its records.
+    ///
+    /// The policy of the TLD of the domain defines the record types that can be registered. Canister controllers
+    /// can register any domain of the TLD, other callers can register the test domains of the TLD, e.g.
+    /// "mydomain.test.icp.", and the domains that are not reserved if the TLD is open for self-service
+    /// registration, as long as they are not registered by someone else and with any allowed record type but SID.
+    ///
+    /// Every principal registered outside of the test domains is indexed with a PTR record at
+    /// "<principal>.reverse.<tld>" that points back to the domain.
+    ///
+    /// Registrations of callers that are not canister controllers expire according to the lease of the TLD, see
+    /// [crate::services::LeaseService]. Until an expired registration is released only canister controllers can
+    /// register the domain again, other callers have to renew it.
+    pub fn register(
+        &self,
+        context: &CallContext,
+        domain: &str,
+        registration: &RegistrationRecords,
+    ) -> Result<(), RegisterError> {
+        let records = Self::validate_registration(registration)?;
+        let domain = ZoneApexDomain::new(domain.to_ascii_lowercase())
+            .map_err(RegisterError::InvalidDomain)?;
+        let policy = self
+            .tld_policy_service
+            .find_policy(&domain)
+            .filter(|policy| policy.top_label(&domain).is_some())
+            .ok_or_else(|| RegisterError::UnsupportedTld {
+                domain: domain.deref().to_string(),
+            })?;
+        let mut normalized_records = Vec::with_capacity(records.len());
+        for record in records {
+            Self::validate_record(&policy, &domain, record)?;
+            let record = DomainRecordResult {
+                name: domain.deref().to_string(),
+                record_type: record.record_type.to_ascii_uppercase(),
+                ttl: record.ttl.clone(),
+                data: record.data.clone(),
+            };
+            if normalized_records
+                .iter()
+                .any(|existing: &DomainRecordResult| {
+                    existing.record_type == record.record_type
+                        && existing.data.eq_ignore_ascii_case(&record.data)
+                })
+            {
+                return Err(RegisterError::DuplicateRecord {
+                    record_type: record.record_type,
+                    data: record.data,
+                });
+            }
+            normalized_records.push(record);
+        }
+        let records = normalized_records;
+
+        let registration = self
+            .registration_repository
+            .get(&domain)
+            .filter(|registration| !policy.is_released(registration, context.time));
+        if !context.is_canister_controller {
+            self.authorize_registrant(context, &policy, &domain, registration.as_ref(), &records)?;
+
+            // registering an expired domain again would bypass the grace and redemption period of `renew`
+            if registration
+                .as_ref()
+                .is_some_and(|registration| registration.is_expired(context.time))
+            {
+                return Err(RegisterError::RegistrationExpired {
+                    domain: domain.deref().to_string(),
+                });
+            }
+        }
+
+        // Registrations of canister controllers never expire, registering a domain again keeps the expiry of its
+        // registration.
+        let expires_at = match (&policy.lease, context.is_canister_controller) {
+            (Some(lease), false) => Some(
+                registration
+                    .as_ref()
+                    .and_then(|registration| registration.expires_at)
+                    .unwrap_or_else(|| lease.expires_at(context.time)),
+            ),
+            _ => None,
+        };
+        // The controllers of the domain keep their roles when one of them changes its records, otherwise the caller
+        // becomes the registrant of the domain.
+        let controllers = match registration {
+            Some(registration) if registration.controller(&context.caller).is_some() => {
+                registration.controllers
+            }
+            _ => vec![RegistrationController {
+                controller_id: context.caller,
+                roles: vec![RegistrationControllerRole::Registrant],
+            }],
+        };
+
+        self.write_zone(
+            context,
+            &policy.tld,
+            self.registration_operations(&policy, &domain, &records),
+        )
+        .map_err(RegisterError::ManageRecords)?;
+
+        self.registration_repository.insert(Registration::new(
+            domain,
+            controllers,
+            records,
+            expires_at,
+        ));
+
+        Ok(())
+    }
+
+    /// Adds the records of the registration back to the zone of its TLD, together with their reverse index.
+    pub(crate) fn restore_records(
+        &self,
+        context: &CallContext,
+        policy: &TldPolicy,
+        registration: &Registration,
+    ) -> Result<(), ManageRecordsError> {
+        self.write_zone(
+            context,
+            &policy.tld,
+            self.registration_operations(policy, &registration.domain, &registration.records),
+        )
+    }
+
+    /// Removes the records of the domain from the zone of its TLD, together with their reverse index.
+    pub(crate) fn remove_records(
+        &self,
+        context: &CallContext,
+        policy: &TldPolicy,
+        domain: &ZoneApexDomain,
+    ) -> Result<(), ManageRecordsError> {
+        let mut operations = vec![ManageRecordsOperation::Remove(vec![
+            RemoveRecordOperationInput {
+                name: domain.deref().to_string(),
+                record_type: None,
+            },
+        ])];
+        operations.extend(self.reverse_index_operations(&policy.tld, domain, &[]));
+
+        self.write_zone(context, &policy.tld, operations)
+    }
+
+    /// Returns the operations that replace the records of the domain with the given records and, outside of the
+    /// test domains, point the reverse index of their principals to the domain.
+    fn registration_operations(
+        &self,
+        policy: &TldPolicy,
+        domain: &ZoneApexDomain,
+        records: &[DomainRecordResult],
+    ) -> Vec<ManageRecordsOperation> {
+        let mut operations = vec![
+            ManageRecordsOperation::Remove(vec![RemoveRecordOperationInput {
+                name: domain.deref().to_string(),
+                record_type: None,
+            }]),
+            ManageRecordsOperation::Append(
+                records
+                    .iter()
+                    .map(|record| DomainRecordInput {
+                        name: record.name.clone(),
+                        record_type: record.record_type.clone(),
+                        ttl: record.ttl.clone(),
+                        data: record.data.clone(),
+                    })
+                    .collect(),
+            ),
+        ];
+        if !policy.is_test_domain(domain) {
+            operations.extend(self.reverse_index_operations(&policy.tld, domain, records));
+        }
+
+        operations
+    }
+
+    /// Applies the operations to the zone of the TLD on behalf of the caller, whose principal is kept in the
+    /// history of the zone.
+    fn write_zone(
+        &self,
+        context: &CallContext,
+        tld: &ZoneApexDomain,
+        operations: Vec<ManageRecordsOperation>,
+    ) -> Result<(), ManageRecordsError> {
+        let operator_context = CallContext::new(context.caller, true, context.time);
+
+        self.records_service.manage_records(
+            &operator_context,
+            &ManageRecordsInput {
+                domain: tld.deref().to_string(),
+                operations,
+                sign_with_tecdsa: None,
+            },
+        )
+    }
+
+    /// Validates the shape of the registration and returns its records.
+    fn validate_registration(
+        registration: &RegistrationRecords,
+    ) -> Result<&[DomainRecordResult], RegisterError> {
+        let records = registration.records.as_deref().unwrap_or_default();
+        if !(1..=MAX_REGISTRATION_RECORDS).contains(&records.len()) {
+            return Err(RegisterError::InvalidRecordCount {
+                count: records.len(),
+                max_count: MAX_REGISTRATION_RECORDS,
+            });
+        }
+
+        if !registration.controllers.is_empty() {
+            return Err(RegisterError::ControllersNotSupported);
+        }
+
+        if records
+            .iter()
+            .flat_map(|record| [&record.name, &record.record_type, &record.data])
+            .any(|field| field.len() > MAX_REGISTRATION_FIELD_LENGTH)
+        {
+            return Err(RegisterError::RecordTooLong {
+                max_length: MAX_REGISTRATION_FIELD_LENGTH,
+            });
+        }
+
+        Ok(records)
+    }
+
+    /// Validates the record of the domain against the policy of its TLD, the domains of the reverse index are
+    /// managed by the operator and can't be registered by anyone.
+    fn validate_record(
+        policy: &TldPolicy,
+        domain: &ZoneApexDomain,
+        record: &DomainRecordResult,
+    ) -> Result<(), RegisterError> {
+        if record.name.to_ascii_lowercase() != domain.deref() {
+            return Err(RegisterError::RecordNameMismatch {
+                name: record.name.clone(),
+                domain: domain.deref().to_string(),
+            });
+        }
+
+        if policy.top_label(domain) == Some(REVERSE_LABEL) {
+            return Err(RegisterError::ReservedLabel {
+                domain: domain.deref().to_string(),
+                label: REVERSE_LABEL.to_string(),
+            });
+        }
+
+        if !policy.allows_record_type(&record.record_type) {
+            return Err(RegisterError::UnsupportedRecordType {
+                record_type: record.record_type.clone(),
+                tld: policy.tld.deref().to_string(),
+            });
+        }
+
+        let record_type = record.record_type.to_ascii_uppercase();
+        if record_type == DomainRecordTypes::CID.to_string() {
+            Self::validate_canister_record(record)?;
+        } else if record_type == DomainRecordTypes::SID.to_string() {
+            Self::validate_subnet_record(&policy.tld, domain, record)?;
+        }
+
+        Ok(())
+    }
+
+    /// The data of CID records must be the principal of a canister.
+    fn validate_canister_record(record: &DomainRecordResult) -> Result<(), RegisterError> {
+        match Principal::from_text(&record.data) {
+            Ok(principal) if Self::is_canister_principal(&principal) => Ok(()),
+            _ => Err(RegisterError::InvalidCanisterPrincipal {
+                data: record.data.clone(),
+            }),
+        }
+    }
+
+    /// The data of SID records must be a self-authenticating principal and their names follow the format
+    /// `{subnet_type}-(optional {subnet_specialization})-{counter}.subnet.{tld}`, e.g. "app-specialized-12.subnet.icp.".
+    fn validate_subnet_record(
+        tld: &ZoneApexDomain,
+        domain: &str,
+        record: &DomainRecordResult,
+    ) -> Result<(), RegisterError> {
+        match Principal::from_text(&record.data) {
+            Ok(principal) if Self::is_self_authenticating_principal(&principal) => {}
+            _ => {
+                return Err(RegisterError::InvalidServicePrincipal {
+                    data: record.data.clone(),
+                })
+            }
+        }
+
+        let invalid_name = |reason: &str| RegisterError::InvalidSubnetName {
+            name: record.name.clone(),
+            reason: reason.to_string(),
+        };
+        let subnet_domain = format!("{}.{}", SUBNET_LABEL, tld.deref());
+        let Some(prefix) = domain
+            .strip_suffix(&subnet_domain)
+            .and_then(|prefix| prefix.strip_suffix('.'))
+        else {
+            return Err(invalid_name(&format!(
+                "the name must end with .{}",
+                subnet_domain
+            )));
+        };
+        if prefix.contains('.') {
+            return Err(invalid_name(
+                "the name must have exactly one label before the subnet domain",
+            ));
+        }
+
+        let prefix_parts = prefix.split('-').collect::<Vec<_>>();
+        if !(2..=3).contains(&prefix_parts.len()) {
+            return Err(invalid_name(
+                "the label must have a subnet type, an optional specialization and a counter",
+            ));
+        }
+        if !["sys", "app"].contains(&prefix_parts[0]) {
+            return Err(invalid_name("the subnet type must be sys or app"));
+        }
+        let counter = prefix_parts[prefix_parts.len() - 1];
+        if counter.is_empty() || !counter.chars().all(|char| char.is_ascii_digit()) {
+            return Err(invalid_name("the counter must be numeric"));
+        }
+
+        Ok(())
+    }
+
+    /// Authorizes a caller that is not a canister controller to register the domain, see
+    /// [RegistrationService::register].
+    fn authorize_registrant(
+        &self,
+        context: &CallContext,
+        policy: &TldPolicy,
+        domain: &ZoneApexDomain,
+        registration: Option<&Registration>,
+        records: &[DomainRecordResult],
+    ) -> Result<(), RegisterError> {
+        let is_test_domain = policy.is_test_domain(domain);
+        if let Some(record) = records.iter().find(|record| {
+            record.record_type == DomainRecordTypes::SID.to_string()
+                || !(is_test_domain || policy.self_service)
+        }) {
+            return Err(RegisterError::Unauthorized {
+                caller: context.caller.to_text(),
+                domain: domain.deref().to_string(),
+                record_type: record.record_type.clone(),
+            });
+        }
+
+        if let Some(label) = policy.reserved_label(domain).filter(|_| !is_test_domain) {
+            return Err(RegisterError::ReservedLabel {
+                domain: domain.deref().to_string(),
+                label: label.to_string(),
+            });
+        }
+
+        match registration {
+            Some(registration) if !registration.can_manage_records(&context.caller) => {
+                Err(RegisterError::RegistrantMismatch {
+                    caller: context.caller.to_text(),
+                    registrant: registration
+                        .registrant()
+                        .map(|registrant| registrant.to_text())
+                        .unwrap_or_default(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the operations that point the PTR records of the principals of the records to the domain, and that
+    /// remove the PTR records of the other principals the domain is registered with.
+    fn reverse_index_operations(
+        &self,
+        tld: &ZoneApexDomain,
+        domain: &ZoneApexDomain,
+        records: &[DomainRecordResult],
+    ) -> Vec<ManageRecordsOperation> {
+        let principal_records = records
+            .iter()
+            .filter(|record| Self::is_principal_record_type(&record.record_type))
+            .collect::<Vec<_>>();
+        let principals = principal_records
+            .iter()
+            .map(|record| record.data.to_ascii_lowercase())
+            .collect::<BTreeSet<_>>();
+        let removed_ptr_records = self
+            .lookup_service
+            .find_records(domain, None)
+            .into_iter()
+            .filter(|previous| Self::is_principal_record_type(&previous.record_type))
+            .map(|previous| previous.data.to_ascii_lowercase())
+            .filter(|previous_principal| !principals.contains(previous_principal))
+            .map(|previous_principal| Self::reverse_name(tld, &previous_principal))
+            .filter(|reverse_name| {
+                self.lookup_service
+                    .find_records(reverse_name, Some(&DomainRecordTypes::PTR.to_string()))
+                    .iter()
+                    .any(|ptr| ptr.data == domain.deref())
+            })
+            .collect::<BTreeSet<_>>();
+
+        let mut operations = Vec::new();
+        if !removed_ptr_records.is_empty() {
+            operations.push(ManageRecordsOperation::Remove(
+                removed_ptr_records
+                    .into_iter()
+                    .map(|reverse_name| RemoveRecordOperationInput {
+                        name: reverse_name,
+                        record_type: Some(DomainRecordTypes::PTR.to_string()),
+                    })
+                    .collect(),
+            ));
+        }
+        if !principal_records.is_empty() {
+            operations.push(ManageRecordsOperation::Override(
+                principal_records
+                    .into_iter()
+                    .map(|record| DomainRecordInput {
+                        name: Self::reverse_name(tld, &record.data.to_ascii_lowercase()),
+                        record_type: DomainRecordTypes::PTR.to_string(),
+                        ttl: record.ttl.clone(),
+                        data: domain.deref().to_string(),
+                    })
+                    .collect(),
+            ));
+        }
+
+        operations
+    }
+
+    /// Returns the name of the PTR record of the principal, e.g. "<principal>.reverse.icp.".
+    fn reverse_name(tld: &ZoneApexDomain, principal: &str) -> String {
+        format!("{}.{}.{}", principal, REVERSE_LABEL, tld.deref())
+    }
+
+    fn is_principal_record_type(record_type: &str) -> bool {
+        record_type == DomainRecordTypes::CID.to_string()
+            || record_type == DomainRecordTypes::SID.to_string()
+    }
+
+    fn is_canister_principal(principal: &Principal) -> bool {
+        let bytes = principal.as_slice();
+
+        bytes.len() == CANISTER_PRINCIPAL_LENGTH && bytes.last() == Some(&CANISTER_PRINCIPAL_SUFFIX)
+    }
+
+    fn is_self_authenticating_principal(principal: &Principal) -> bool {
+        let bytes = principal.as_slice();
+
+        bytes.len() == SELF_AUTHENTICATING_PRINCIPAL_LENGTH
+            && bytes.last() == Some(&SELF_AUTHENTICATING_PRINCIPAL_SUFFIX)
+    }
+}
+
+impl Default for RegistrationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Nat;
+    use rstest::rstest;
+
+    const CANISTER_ID: &str = "qoctq-giaaa-aaaaa-aaaea-cai";
+    const OTHER_CANISTER_ID: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+
+    fn user(id: u8) -> CallContext {
+        CallContext::new(Principal::from_slice(&[id; 29]), false, 0)
+    }
+
+    fn subnet_id() -> String {
+        Principal::self_authenticating([1; 32]).to_text()
+    }
+
+    fn record(name: &str, record_type: &str, data: &str) -> DomainRecordResult {
+        DomainRecordResult {
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            ttl: Nat::from(60u32),
+            data: data.to_string(),
+        }
+    }
+
+    fn register(
+        context: &CallContext,
+        domain: &str,
+        records: Vec<DomainRecordResult>,
+    ) -> Result<(), RegisterError> {
+        TldPolicyService::default().install_default_policy();
+        RegistrationService::default().register(
+            context,
+            domain,
+            &RegistrationRecords {
+                controllers: vec![],
+                records: Some(records),
+            },
+        )
+    }
+
+    fn answers(domain: &str, record_type: &str) -> Vec<DomainRecordResult> {
+        LookupService::default().lookup(domain, record_type).answers
+    }
+
+    fn reverse_name(principal: &str) -> String {
+        format!("{}.reverse.icp.", principal)
+    }
+
+    #[test]
+    fn controller_registers_domain_with_reverse_index() {
+        register(
+            &CallContext::canister_controller(0),
+            "MyDomain.icp.",
+            vec![record("mydomain.ICP.", "cid", CANISTER_ID)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers("mydomain.icp.", "CID"),
+            vec![record("mydomain.icp.", "CID", CANISTER_ID)]
+        );
+        assert_eq!(
+            answers(&reverse_name(CANISTER_ID), "PTR"),
+            vec![record(&reverse_name(CANISTER_ID), "PTR", "mydomain.icp.")]
+        );
+        assert_eq!(
+            RegistrationRepository::default()
+                .get(&ZoneApexDomain::new(String::from("mydomain.icp.")).unwrap())
+                .and_then(|registration| registration.registrant()),
+            Some(CallContext::canister_controller(0).caller)
+        );
+    }
+
+    #[test]
+    fn registering_another_principal_moves_the_reverse_index() {
+        register(
+            &CallContext::canister_controller(0),
+            "moved.icp.",
+            vec![record("moved.icp.", "CID", CANISTER_ID)],
+        )
+        .unwrap();
+        register(
+            &CallContext::canister_controller(0),
+            "moved.icp.",
+            vec![record("moved.icp.", "CID", OTHER_CANISTER_ID)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers("moved.icp.", "CID"),
+            vec![record("moved.icp.", "CID", OTHER_CANISTER_ID)]
+        );
+        assert!(answers(&reverse_name(CANISTER_ID), "PTR").is_empty());
+        assert_eq!(
+            answers(&reverse_name(OTHER_CANISTER_ID), "PTR"),
+            vec![record(
+                &reverse_name(OTHER_CANISTER_ID),
+                "PTR",
+                "moved.icp."
+            )]
+        );
+    }
+
+    #[test]
+    fn controller_registers_subnet() {
+        let subnet_id = subnet_id();
+        register(
+            &CallContext::canister_controller(0),
+            "app-fiduciary-1.subnet.icp.",
+            vec![record("app-fiduciary-1.subnet.icp.", "SID", &subnet_id)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers("app-fiduciary-1.subnet.icp.", "SID"),
+            vec![record("app-fiduciary-1.subnet.icp.", "SID", &subnet_id)]
+        );
+        assert_eq!(
+            answers(&reverse_name(&subnet_id), "PTR"),
+            vec![record(
+                &reverse_name(&subnet_id),
+                "PTR",
+                "app-fiduciary-1.subnet.icp."
+            )]
+        );
+    }
+
+    #[test]
+    fn anyone_registers_test_domains_of_their_own() {
+        let domain = "mine.test.icp.";
+        register(&user(1), domain, vec![record(domain, "CID", CANISTER_ID)]).unwrap();
+        register(
+            &user(1),
+            domain,
+            vec![record(domain, "CID", OTHER_CANISTER_ID)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            register(&user(2), domain, vec![record(domain, "CID", CANISTER_ID)]),
+            Err(RegisterError::RegistrantMismatch {
+                caller: user(2).caller.to_text(),
+                registrant: user(1).caller.to_text(),
+            })
+        );
+        assert_eq!(
+            answers(domain, "CID"),
+            vec![record(domain, "CID", OTHER_CANISTER_ID)]
+        );
+        // test domains are not added to the reverse index
+        assert!(answers(&reverse_name(OTHER_CANISTER_ID), "PTR").is_empty());
+    }
+
+    #[rstest]
+    #[case::not_a_test_domain("mine.icp.", "CID", CANISTER_ID)]
+    #[case::not_a_cid_record("app-1.subnet.icp.", "SID", &subnet_id())]
+    fn users_cannot_register_other_domains(
+        #[case] domain: &str,
+        #[case] record_type: &str,
+        #[case] data: &str,
+    ) {
+        assert_eq!(
+            register(&user(1), domain, vec![record(domain, record_type, data)]),
+            Err(RegisterError::Unauthorized {
+                caller: user(1).caller.to_text(),
+                domain: domain.to_string(),
+                record_type: record_type.to_string(),
+            })
+        );
+        assert!(answers(domain, record_type).is_empty());
+    }
+
+    #[rstest]
+    #[case::no_records(
+        "example.icp.",
+        vec![],
+        RegisterError::InvalidRecordCount { count: 0, max_count: 10 }
+    )]
+    #[case::too_many_records(
+        "example.icp.",
+        (0..11).map(|index| record("example.icp.", "TXT", &index.to_string())).collect(),
+        RegisterError::InvalidRecordCount { count: 11, max_count: 10 }
+    )]
+    #[case::duplicate_records(
+        "example.icp.",
+        vec![record("example.icp.", "CID", CANISTER_ID), record("example.icp.", "cid", CANISTER_ID)],
+        RegisterError::DuplicateRecord { record_type: String::from("CID"), data: CANISTER_ID.to_string() }
+    )]
+    #[case::record_stuffing(
+        "example.icp.",
+        vec![record("example.icp.", "CID", &"a".repeat(101))],
+        RegisterError::RecordTooLong { max_length: 100 }
+    )]
+    #[case::other_tld(
+        "example.com.",
+        vec![record("example.com.", "CID", CANISTER_ID)],
+        RegisterError::UnsupportedTld { domain: String::from("example.com.") }
+    )]
+    #[case::tld_itself(
+        "icp.",
+        vec![record("icp.", "CID", CANISTER_ID)],
+        RegisterError::UnsupportedTld { domain: String::from("icp.") }
+    )]
+    #[case::name_mismatch(
+        "example.icp.",
+        vec![record("other.icp.", "CID", CANISTER_ID)],
+        RegisterError::RecordNameMismatch { name: String::from("other.icp."), domain: String::from("example.icp.") }
+    )]
+    #[case::unsupported_type(
+        "example.icp.",
+        vec![record("example.icp.", "TXT", CANISTER_ID)],
+        RegisterError::UnsupportedRecordType { record_type: String::from("TXT"), tld: String::from("icp.") }
+    )]
+    #[case::reverse_index(
+        "aaaaa-aa.reverse.icp.",
+        vec![record("aaaaa-aa.reverse.icp.", "CID", CANISTER_ID)],
+        RegisterError::ReservedLabel { domain: String::from("aaaaa-aa.reverse.icp."), label: String::from("reverse") }
+    )]
+    #[case::cid_of_user(
+        "example.icp.",
+        vec![record("example.icp.", "CID", "2vxsx-fae")],
+        RegisterError::InvalidCanisterPrincipal { data: String::from("2vxsx-fae") }
+    )]
+    #[case::sid_of_canister(
+        "app-1.subnet.icp.",
+        vec![record("app-1.subnet.icp.", "SID", CANISTER_ID)],
+        RegisterError::InvalidServicePrincipal { data: String::from(CANISTER_ID) }
+    )]
+    #[case::sid_outside_subnets(
+        "app-1.icp.",
+        vec![record("app-1.icp.", "SID", &subnet_id())],
+        RegisterError::InvalidSubnetName { name: String::from("app-1.icp."), reason: String::from("the name must end with .subnet.icp.") }
+    )]
+    #[case::sid_nested_subnet(
+        "one.app-1.subnet.icp.",
+        vec![record("one.app-1.subnet.icp.", "SID", &subnet_id())],
+        RegisterError::InvalidSubnetName { name: String::from("one.app-1.subnet.icp."), reason: String::from("the name must have exactly one label before the subnet domain") }
+    )]
+    #[case::sid_without_counter(
+        "app.subnet.icp.",
+        vec![record("app.subnet.icp.", "SID", &subnet_id())],
+        RegisterError::InvalidSubnetName { name: String::from("app.subnet.icp."), reason: String::from("the label must have a subnet type, an optional specialization and a counter") }
+    )]
+    #[case::sid_unknown_type(
+        "web-1.subnet.icp.",
+        vec![record("web-1.subnet.icp.", "SID", &subnet_id())],
+        RegisterError::InvalidSubnetName { name: String::from("web-1.subnet.icp."), reason: String::from("the subnet type must be sys or app") }
+    )]
+    #[case::sid_text_counter(
+        "sys-one.subnet.icp.",
+        vec![record("sys-one.subnet.icp.", "SID", &subnet_id())],
+        RegisterError::InvalidSubnetName { name: String::from("sys-one.subnet.icp."), reason: String::from("the counter must be numeric") }
+    )]
+    fn register_rejects_invalid_registrations(
+        #[case] domain: &str,
+        #[case] records: Vec<DomainRecordResult>,
+        #[case] expected: RegisterError,
+    ) {
+        assert_eq!(
+            register(&CallContext::canister_controller(0), domain, records),
+            Err(expected)
+        );
+    }
+
+    fn set_community_policy() {
+        TldPolicyService::default()
+            .set_policy(
+                &CallContext::canister_controller(0),
+                &TldPolicy {
+                    tld: ZoneApexDomain::new(String::from("dao.")).unwrap(),
+                    self_service: true,
+                    allowed_record_types: vec![String::from("CID"), String::from("TXT")],
+                    reserved_labels: vec![String::from("admin")],
+                    test_label: None,
+                    lease: None,
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn anyone_registers_domains_of_self_service_tlds() {
+        set_community_policy();
+        register(
+            &user(1),
+            "mine.dao.",
+            vec![record("mine.dao.", "CID", CANISTER_ID)],
+        )
+        .unwrap();
+        register(
+            &user(2),
+            "notes.dao.",
+            vec![record("notes.dao.", "TXT", "hello")],
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers("mine.dao.", "CID"),
+            vec![record("mine.dao.", "CID", CANISTER_ID)]
+        );
+        assert_eq!(
+            answers(&format!("{}.reverse.dao.", CANISTER_ID), "PTR"),
+            vec![record(
+                &format!("{}.reverse.dao.", CANISTER_ID),
+                "PTR",
+                "mine.dao."
+            )]
+        );
+        assert_eq!(
+            answers("notes.dao.", "TXT"),
+            vec![record("notes.dao.", "TXT", "hello")]
+        );
+        // the zones of the TLDs are kept apart
+        assert!(answers(&reverse_name(CANISTER_ID), "PTR").is_empty());
+        assert_eq!(
+            register(
+                &user(2),
+                "mine.dao.",
+                vec![record("mine.dao.", "CID", OTHER_CANISTER_ID)]
+            ),
+            Err(RegisterError::RegistrantMismatch {
+                caller: user(2).caller.to_text(),
+                registrant: user(1).caller.to_text(),
+            })
+        );
+    }
+
+    #[test]
+    fn register_domain_with_whole_rrsets() {
+        set_community_policy();
+        let reverse_name = |principal: &str| format!("{}.reverse.dao.", principal);
+        register(
+            &user(1),
+            "multi.dao.",
+            vec![
+                record("multi.dao.", "CID", CANISTER_ID),
+                record("multi.dao.", "CID", OTHER_CANISTER_ID),
+                record("multi.dao.", "TXT", "hello"),
+                record("multi.dao.", "TXT", "world"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers("multi.dao.", "CID"),
+            vec![
+                record("multi.dao.", "CID", OTHER_CANISTER_ID),
+                record("multi.dao.", "CID", CANISTER_ID),
+            ]
+        );
+        assert_eq!(
+            answers("multi.dao.", "TXT"),
+            vec![
+                record("multi.dao.", "TXT", "hello"),
+                record("multi.dao.", "TXT", "world"),
+            ]
+        );
+        for principal in [CANISTER_ID, OTHER_CANISTER_ID] {
+            assert_eq!(
+                answers(&reverse_name(principal), "PTR"),
+                vec![record(&reverse_name(principal), "PTR", "multi.dao.")]
+            );
+        }
+
+        // registering the domain again replaces all of its RRsets and the stale reverse index
+        register(
+            &user(1),
+            "multi.dao.",
+            vec![record("multi.dao.", "CID", OTHER_CANISTER_ID)],
+        )
+        .unwrap();
+        assert_eq!(
+            answers("multi.dao.", "CID"),
+            vec![record("multi.dao.", "CID", OTHER_CANISTER_ID)]
+        );
+        assert!(answers("multi.dao.", "TXT").is_empty());
+        assert!(answers(&reverse_name(CANISTER_ID), "PTR").is_empty());
+    }
+
+    #[rstest]
+    #[case::reserved_label(
+        "admin.dao.",
+        "CID",
+        CANISTER_ID,
+        RegisterError::ReservedLabel { domain: String::from("admin.dao."), label: String::from("admin") }
+    )]
+    #[case::below_reserved_label(
+        "www.admin.dao.",
+        "CID",
+        CANISTER_ID,
+        RegisterError::ReservedLabel { domain: String::from("www.admin.dao."), label: String::from("admin") }
+    )]
+    #[case::not_allowed_record_type(
+        "mine.dao.",
+        "SID",
+        &subnet_id(),
+        RegisterError::UnsupportedRecordType { record_type: String::from("SID"), tld: String::from("dao.") }
+    )]
+    #[case::not_operated_tld(
+        "mine.other.",
+        "CID",
+        CANISTER_ID,
+        RegisterError::UnsupportedTld { domain: String::from("mine.other.") }
+    )]
+    fn self_service_respects_policy(
+        #[case] domain: &str,
+        #[case] record_type: &str,
+        #[case] data: &str,
+        #[case] expected: RegisterError,
+    ) {
+        set_community_policy();
+
+        assert_eq!(
+            register(&user(1), domain, vec![record(domain, record_type, data)]),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn register_rejects_explicit_controllers() {
+        let result = RegistrationService::default().register(
+            &CallContext::canister_controller(0),
+            "example.icp.",
+            &RegistrationRecords {
+                controllers: vec![RegistrationController {
+                    controller_id: user(1).caller,
+                    roles: vec![RegistrationControllerRole::Registrant],
+                }],
+                records: Some(vec![record("example.icp.", "CID", CANISTER_ID)]),
+            },
+        );
+
+        assert_eq!(result, Err(RegisterError::ControllersNotSupported));
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    common::MAX_RESERVED_LABELS, errors::SetTldPolicyError, repositories::TldPolicyRepository,
+    types::TldPolicy,
+};
+use cns_domain_registry::{
+    repositories::Repository,
+    types::{CallContext, DomainRecordTypes, ZoneApexDomain},
+};
+use std::{collections::BTreeSet, ops::Deref, str::FromStr};
+
+/// A service that manages the registration policies of the top level domains (TLD) operated by the canister.
+pub struct TldPolicyService {
+    tld_policy_repository: TldPolicyRepository,
+}
+
+impl TldPolicyService {
+    pub fn new() -> Self {
+        Self {
+            tld_policy_repository: TldPolicyRepository::default(),
+        }
+    }
+
+    /// Installs the policy of the default TLD when no TLD is operated yet, which is the case for new canisters
+    /// and canisters upgraded from a version that only operated the default TLD.
+    pub fn install_default_policy(&self) {
+        if self.tld_policy_repository.count() == 0 {
+            self.tld_policy_repository.insert(TldPolicy::default_tld());
+        }
+    }
+
+    /// Returns the policies of all the TLDs operated by the canister.
+    pub fn get_policies(&self) -> Vec<TldPolicy> {
+        self.tld_policy_repository.list()
+    }
+
+    /// Returns the policy of the TLD of the domain, if the TLD is operated by the canister.
+    pub fn find_policy(&self, domain: &ZoneApexDomain) -> Option<TldPolicy> {
+        let label = domain.trim_end_matches('.').rsplit('.').next()?;
+        let tld = ZoneApexDomain::new(format!("{}.", label)).ok()?;
+
+        self.tld_policy_repository.get(&tld)
+    }
+
+    /// Adds the TLD to the operated TLDs or replaces its policy, only canister controllers can set policies.
+    ///
+    /// The TLD, record types and labels are normalized to their canonical case and duplicates are removed. The
+    /// registered domains are kept when a policy changes, the policy only applies to new registrations and renewals,
+    /// except for the grace and redemption periods which apply to the expired registrations.
+    pub fn set_policy(
+        &self,
+        context: &CallContext,
+        policy: &TldPolicy,
+    ) -> Result<(), SetTldPolicyError> {
+        if !context.is_canister_controller {
+            return Err(SetTldPolicyError::Unauthorized {
+                caller: context.caller.to_text(),
+            });
+        }
+
+        let tld = ZoneApexDomain::new(policy.tld.to_ascii_lowercase())
+            .map_err(SetTldPolicyError::InvalidTld)?;
+        if tld.trim_end_matches('.').contains('.') {
+            return Err(SetTldPolicyError::NotTld {
+                domain: tld.deref().to_string(),
+            });
+        }
+
+        let allowed_record_types = policy
+            .allowed_record_types
+            .iter()
+            .map(
+                |record_type| match DomainRecordTypes::from_str(record_type) {
+                    Ok(DomainRecordTypes::PTR | DomainRecordTypes::SOA) | Err(_) => {
+                        Err(SetTldPolicyError::UnsupportedRecordType {
+                            record_type: record_type.clone(),
+                        })
+                    }
+                    Ok(record_type) => Ok(record_type.to_string()),
+                },
+            )
+            .collect::<Result<BTreeSet<_>, _>>()?;
+
+        if policy.reserved_labels.len() > MAX_RESERVED_LABELS {
+            return Err(SetTldPolicyError::TooManyReservedLabels {
+                max_labels: MAX_RESERVED_LABELS,
+                count: policy.reserved_labels.len(),
+            });
+        }
+        let reserved_labels = policy
+            .reserved_labels
+            .iter()
+            .map(|label| Self::normalize_label(&tld, label))
+            .collect::<Result<BTreeSet<_>, _>>()?;
+        let test_label = policy
+            .test_label
+            .as_ref()
+            .map(|label| Self::normalize_label(&tld, label))
+            .transpose()?;
+
+        if policy
+            .lease
+            .as_ref()
+            .is_some_and(|lease| lease.registration_period == 0)
+        {
+            return Err(SetTldPolicyError::InvalidRegistrationPeriod);
+        }
+
+        self.tld_policy_repository.insert(TldPolicy {
+            tld,
+            self_service: policy.self_service,
+            allowed_record_types: allowed_record_types.into_iter().collect(),
+            reserved_labels: reserved_labels.into_iter().collect(),
+            test_label,
+            lease: policy.lease.clone(),
+        });
+
+        Ok(())
+    }
+
+    /// Returns the lowercase label, a label is valid if it makes a valid domain below the TLD.
+    fn normalize_label(tld: &ZoneApexDomain, label: &str) -> Result<String, SetTldPolicyError> {
+        let label = label.to_ascii_lowercase();
+        if label.is_empty()
+            || label.contains('.')
+            || ZoneApexDomain::new(format!("{}.{}", label, tld.deref())).is_err()
+        {
+            return Err(SetTldPolicyError::InvalidLabel { label });
+        }
+
+        Ok(label)
+    }
+}
+
+impl Default for TldPolicyService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::LeasePolicy;
+    use candid::Principal;
+    use rstest::rstest;
+
+    fn policy(tld: &str) -> TldPolicy {
+        TldPolicy {
+            tld: ZoneApexDomain::new(tld.to_string()).unwrap(),
+            self_service: true,
+            allowed_record_types: vec![String::from("cid"), String::from("TXT")],
+            reserved_labels: vec![String::from("Admin"), String::from("admin")],
+            test_label: None,
+            lease: None,
+        }
+    }
+
+    #[test]
+    fn set_policy_normalizes_policy() {
+        let mut community_policy = policy("dao.");
+        community_policy.tld = ZoneApexDomain::new(String::from("DAO.")).unwrap();
+        TldPolicyService::default()
+            .set_policy(&CallContext::canister_controller(0), &community_policy)
+            .unwrap();
+
+        let service = TldPolicyService::default();
+        let expected = TldPolicy {
+            tld: ZoneApexDomain::new(String::from("dao.")).unwrap(),
+            self_service: true,
+            allowed_record_types: vec![String::from("CID"), String::from("TXT")],
+            reserved_labels: vec![String::from("admin")],
+            test_label: None,
+            lease: None,
+        };
+        assert_eq!(service.get_policies(), vec![expected.clone()]);
+        assert_eq!(
+            service.find_policy(&ZoneApexDomain::new(String::from("www.my.dao.")).unwrap()),
+            Some(expected)
+        );
+        assert_eq!(
+            service.find_policy(&ZoneApexDomain::new(String::from("my.icp.")).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn install_default_policy_keeps_configured_tlds() {
+        let service = TldPolicyService::default();
+        service.install_default_policy();
+        service
+            .set_policy(&CallContext::canister_controller(0), &policy("dao."))
+            .unwrap();
+        service.install_default_policy();
+
+        assert_eq!(
+            service
+                .get_policies()
+                .into_iter()
+                .map(|policy| policy.tld.deref().to_string())
+                .collect::<Vec<_>>(),
+            vec![String::from("dao."), String::from("icp.")]
+        );
+    }
+
+    #[rstest]
+    #[case::not_tld(
+        TldPolicy { tld: ZoneApexDomain::new(String::from("my.dao.")).unwrap(), ..policy("dao.") },
+        SetTldPolicyError::NotTld { domain: String::from("my.dao.") }
+    )]
+    #[case::ptr_records(
+        TldPolicy { allowed_record_types: vec![String::from("PTR")], ..policy("dao.") },
+        SetTldPolicyError::UnsupportedRecordType { record_type: String::from("PTR") }
+    )]
+    #[case::unknown_record_type(
+        TldPolicy { allowed_record_types: vec![String::from("XYZ")], ..policy("dao.") },
+        SetTldPolicyError::UnsupportedRecordType { record_type: String::from("XYZ") }
+    )]
+    #[case::nested_reserved_label(
+        TldPolicy { reserved_labels: vec![String::from("a.b")], ..policy("dao.") },
+        SetTldPolicyError::InvalidLabel { label: String::from("a.b") }
+    )]
+    #[case::empty_test_label(
+        TldPolicy { test_label: Some(String::new()), ..policy("dao.") },
+        SetTldPolicyError::InvalidLabel { label: String::new() }
+    )]
+    #[case::lease_without_period(
+        TldPolicy { lease: Some(LeasePolicy { registration_period: 0, ..Default::default() }), ..policy("dao.") },
+        SetTldPolicyError::InvalidRegistrationPeriod
+    )]
+    #[case::too_many_reserved_labels(
+        TldPolicy { reserved_labels: vec![String::from("a"); 101], ..policy("dao.") },
+        SetTldPolicyError::TooManyReservedLabels { max_labels: 100, count: 101 }
+    )]
+    fn set_policy_rejects_invalid_policies(
+        #[case] policy: TldPolicy,
+        #[case] expected: SetTldPolicyError,
+    ) {
+        let service = TldPolicyService::default();
+
+        assert_eq!(
+            service.set_policy(&CallContext::canister_controller(0), &policy),
+            Err(expected)
+        );
+        assert!(service.get_policies().is_empty());
+    }
+
+    #[test]
+    fn set_policy_requires_canister_controller() {
+        let caller = Principal::from_slice(&[1; 29]);
+
+        assert_eq!(
+            TldPolicyService::default()
+                .set_policy(&CallContext::new(caller, false, 0), &policy("dao.")),
+            Err(SetTldPolicyError::Unauthorized {
+                caller: caller.to_text()
+            })
+        );
+    }
+}
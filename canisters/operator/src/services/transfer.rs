@@ -0,0 +1,474 @@
+use crate::{
+    common::TRANSFER_ACCEPTANCE_PERIOD,
+    errors::TransferError,
+    repositories::{PendingTransferRepository, RegistrationRepository, TransferLogRepository},
+    services::TldPolicyService,
+    types::{PendingTransfer, Registration, TransferEvent, TransferLogEntry},
+};
+use candid::Principal;
+use cns_domain_registry::{
+    repositories::Repository,
+    types::{CallContext, ZoneApexDomain},
+};
+use std::ops::Deref;
+
+/// The number of nanoseconds in a second, the times of the transfers are in nanoseconds since the UNIX epoch.
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// A service that transfers the domains between registrants in two steps, the registrant initiates the transfer
+/// and the new registrant accepts it.
+///
+/// A domain has at most one pending transfer, which is void once the registrant it was initiated by is no longer
+/// the registrant of the domain. Every change of a transfer is recorded in the transfer log of the domain.
+pub struct TransferService {
+    pending_transfer_repository: PendingTransferRepository,
+    registration_repository: RegistrationRepository,
+    transfer_log_repository: TransferLogRepository,
+    tld_policy_service: TldPolicyService,
+}
+
+impl TransferService {
+    pub fn new() -> Self {
+        Self {
+            pending_transfer_repository: PendingTransferRepository::default(),
+            registration_repository: RegistrationRepository::default(),
+            transfer_log_repository: TransferLogRepository::default(),
+            tld_policy_service: TldPolicyService::default(),
+        }
+    }
+
+    /// Initiates the transfer of the domain to the new registrant, replacing the pending transfer of the domain
+    /// if any. The new registrant has [TRANSFER_ACCEPTANCE_PERIOD] seconds to accept the transfer.
+    ///
+    /// Only the registrant, registrars and canister controllers can initiate transfers, the registration must not be
+    /// expired.
+    pub fn initiate_transfer(
+        &self,
+        context: &CallContext,
+        domain: &str,
+        new_owner: Principal,
+    ) -> Result<PendingTransfer, TransferError> {
+        let (domain, registration, registrant) = self.find_registration(context, domain)?;
+        if !context.is_canister_controller && !registration.can_manage_registration(&context.caller)
+        {
+            return Err(TransferError::Unauthorized {
+                caller: context.caller.to_text(),
+                domain: domain.deref().to_string(),
+            });
+        }
+
+        if new_owner == registrant || new_owner == Principal::anonymous() {
+            return Err(TransferError::InvalidNewOwner {
+                domain: domain.deref().to_string(),
+                new_owner: new_owner.to_text(),
+            });
+        }
+
+        let transfer = PendingTransfer {
+            domain: domain.clone(),
+            from: registrant,
+            to: new_owner,
+            initiated_at: context.time,
+            expires_at: context
+                .time
+                .saturating_add(TRANSFER_ACCEPTANCE_PERIOD.saturating_mul(NANOS_PER_SEC)),
+        };
+        self.pending_transfer_repository.insert(transfer.clone());
+        self.log(context, &transfer, TransferEvent::Initiated);
+
+        Ok(transfer)
+    }
+
+    /// Accepts the pending transfer of the domain, the caller must be the new registrant of the transfer and
+    /// becomes the registrant and only controller of the domain. The records of the domain are kept, the other
+    /// controllers are removed since they were granted by the previous registrant.
+    pub fn accept_transfer(
+        &self,
+        context: &CallContext,
+        domain: &str,
+    ) -> Result<(), TransferError> {
+        let (domain, mut registration, registrant) = self.find_registration(context, domain)?;
+        let transfer = self.find_pending_transfer(&domain, registrant)?;
+        if context.caller != transfer.to {
+            return Err(TransferError::Unauthorized {
+                caller: context.caller.to_text(),
+                domain: domain.deref().to_string(),
+            });
+        }
+
+        if transfer.is_expired(context.time) {
+            return Err(TransferError::TransferExpired {
+                domain: domain.deref().to_string(),
+                expired_at: transfer.expires_at,
+            });
+        }
+
+        registration.set_registrant(transfer.to);
+        self.registration_repository.insert(registration);
+        self.pending_transfer_repository.remove(&transfer);
+        self.log(context, &transfer, TransferEvent::Accepted);
+
+        Ok(())
+    }
+
+    /// Cancels the pending transfer of the domain, which the registrant, registrars and canister controllers can do
+    /// to cancel it and the new registrant to decline it. Expired transfers can be cancelled as well.
+    pub fn cancel_transfer(
+        &self,
+        context: &CallContext,
+        domain: &str,
+    ) -> Result<(), TransferError> {
+        let domain = Self::parse_domain(domain)?;
+        let registration = self.registration_repository.get(&domain);
+        let transfer = registration
+            .as_ref()
+            .and_then(|registration| registration.registrant())
+            .ok_or_else(|| TransferError::NoPendingTransfer {
+                domain: domain.deref().to_string(),
+            })
+            .and_then(|registrant| self.find_pending_transfer(&domain, registrant))?;
+
+        if !context.is_canister_controller
+            && context.caller != transfer.to
+            && !registration
+                .is_some_and(|registration| registration.can_manage_registration(&context.caller))
+        {
+            return Err(TransferError::Unauthorized {
+                caller: context.caller.to_text(),
+                domain: domain.deref().to_string(),
+            });
+        }
+
+        self.pending_transfer_repository.remove(&transfer);
+        self.log(context, &transfer, TransferEvent::Cancelled);
+
+        Ok(())
+    }
+
+    /// Returns the pending transfer of the domain, including an expired one until it is cancelled or replaced.
+    pub fn get_pending_transfer(&self, domain: &str) -> Option<PendingTransfer> {
+        let domain = Self::parse_domain(domain).ok()?;
+        let registrant = self
+            .registration_repository
+            .get(&domain)
+            .and_then(|registration| registration.registrant())?;
+
+        self.find_pending_transfer(&domain, registrant).ok()
+    }
+
+    /// Returns the transfer log of the domain in the order the entries were added, the log keeps the most recent
+    /// [crate::common::MAX_TRANSFER_LOG_ENTRIES] entries.
+    pub fn get_transfer_log(&self, domain: &str) -> Vec<TransferLogEntry> {
+        Self::parse_domain(domain)
+            .map(|domain| self.transfer_log_repository.list(&domain))
+            .unwrap_or_default()
+    }
+
+    /// Returns the domain, its registration and its registrant, the registration must not be expired.
+    fn find_registration(
+        &self,
+        context: &CallContext,
+        domain: &str,
+    ) -> Result<(ZoneApexDomain, Registration, Principal), TransferError> {
+        let domain = Self::parse_domain(domain)?;
+        let registration = self
+            .registration_repository
+            .get(&domain)
+            .filter(|registration| !self.is_released(registration, context.time));
+        let Some((registration, registrant)) = registration.and_then(|registration| {
+            let registrant = registration.registrant()?;
+            Some((registration, registrant))
+        }) else {
+            return Err(TransferError::NotRegistered {
+                domain: domain.deref().to_string(),
+            });
+        };
+
+        if registration.is_expired(context.time) {
+            return Err(TransferError::RegistrationExpired {
+                domain: domain.deref().to_string(),
+            });
+        }
+
+        Ok((domain, registration, registrant))
+    }
+
+    /// Returns the pending transfer of the domain if it was initiated by the current registrant.
+    fn find_pending_transfer(
+        &self,
+        domain: &ZoneApexDomain,
+        registrant: Principal,
+    ) -> Result<PendingTransfer, TransferError> {
+        self.pending_transfer_repository
+            .get(domain)
+            .filter(|transfer| transfer.from == registrant)
+            .ok_or_else(|| TransferError::NoPendingTransfer {
+                domain: domain.deref().to_string(),
+            })
+    }
+
+    fn is_released(&self, registration: &Registration, time: u64) -> bool {
+        self.tld_policy_service
+            .find_policy(&registration.domain)
+            .map_or(true, |policy| policy.is_released(registration, time))
+    }
+
+    fn log(&self, context: &CallContext, transfer: &PendingTransfer, event: TransferEvent) {
+        self.transfer_log_repository.append(
+            &transfer.domain,
+            TransferLogEntry {
+                sequence: 0,
+                timestamp: context.time,
+                caller: context.caller,
+                event,
+                from: transfer.from,
+                to: transfer.to,
+            },
+        );
+    }
+
+    fn parse_domain(domain: &str) -> Result<ZoneApexDomain, TransferError> {
+        ZoneApexDomain::new(domain.to_ascii_lowercase()).map_err(TransferError::InvalidDomain)
+    }
+}
+
+impl Default for TransferService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{ControllerService, RegistrationService};
+    use candid::Nat;
+    use cns_domain_registry::{
+        api::{DomainRecord as DomainRecordResult, RegistrationRecords},
+        types::{RegistrationController, RegistrationControllerRole},
+    };
+
+    const CANISTER_ID: &str = "qoctq-giaaa-aaaaa-aaaea-cai";
+    const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    fn user(id: u8, time: u64) -> CallContext {
+        CallContext::new(Principal::from_slice(&[id; 29]), false, time)
+    }
+
+    fn register(context: &CallContext, domain: &str) {
+        TldPolicyService::default().install_default_policy();
+        RegistrationService::default()
+            .register(
+                context,
+                domain,
+                &RegistrationRecords {
+                    controllers: vec![],
+                    records: Some(vec![DomainRecordResult {
+                        name: domain.to_string(),
+                        record_type: String::from("CID"),
+                        ttl: Nat::from(60u32),
+                        data: CANISTER_ID.to_string(),
+                    }]),
+                },
+            )
+            .unwrap();
+    }
+
+    fn registrant(domain: &str) -> Option<Principal> {
+        RegistrationRepository::default()
+            .get(&ZoneApexDomain::new(domain.to_string()).unwrap())
+            .and_then(|registration| registration.registrant())
+    }
+
+    fn events(domain: &str) -> Vec<TransferEvent> {
+        TransferService::default()
+            .get_transfer_log(domain)
+            .into_iter()
+            .map(|entry| entry.event)
+            .collect()
+    }
+
+    #[test]
+    fn transfer_to_new_registrant() {
+        let domain = "transferred.test.icp.";
+        let service = TransferService::default();
+        register(&user(1, 0), domain);
+
+        let transfer = service
+            .initiate_transfer(&user(1, DAY), domain, user(2, 0).caller)
+            .unwrap();
+        assert_eq!(transfer.from, user(1, 0).caller);
+        assert_eq!(transfer.expires_at, DAY + 7 * DAY);
+        assert_eq!(service.get_pending_transfer(domain), Some(transfer));
+
+        assert_eq!(
+            service.accept_transfer(&user(3, 2 * DAY), domain),
+            Err(TransferError::Unauthorized {
+                caller: user(3, 0).caller.to_text(),
+                domain: domain.to_string(),
+            })
+        );
+        service.accept_transfer(&user(2, 2 * DAY), domain).unwrap();
+
+        assert_eq!(registrant(domain), Some(user(2, 0).caller));
+        assert_eq!(service.get_pending_transfer(domain), None);
+        assert_eq!(
+            events(domain),
+            vec![TransferEvent::Initiated, TransferEvent::Accepted]
+        );
+
+        // the previous registrant no longer owns the domain
+        assert_eq!(
+            service.initiate_transfer(&user(1, 3 * DAY), domain, user(1, 0).caller),
+            Err(TransferError::Unauthorized {
+                caller: user(1, 0).caller.to_text(),
+                domain: domain.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn previous_registrant_cannot_take_back_domain() {
+        let domain = "handed-over.test.icp.";
+        let service = TransferService::default();
+        register(&user(1, 0), domain);
+        ControllerService::default()
+            .add_controller(
+                &user(1, 0),
+                domain,
+                &RegistrationController::new(
+                    user(3, 0).caller,
+                    vec![RegistrationControllerRole::Registrar],
+                ),
+            )
+            .unwrap();
+        service
+            .initiate_transfer(&user(1, 0), domain, user(2, 0).caller)
+            .unwrap();
+        service.accept_transfer(&user(2, 0), domain).unwrap();
+
+        assert_eq!(
+            service.initiate_transfer(&user(3, 0), domain, user(3, 0).caller),
+            Err(TransferError::Unauthorized {
+                caller: user(3, 0).caller.to_text(),
+                domain: domain.to_string(),
+            })
+        );
+        assert_eq!(
+            RegistrationRepository::default()
+                .get(&ZoneApexDomain::new(domain.to_string()).unwrap())
+                .map(|registration| registration.controllers),
+            Some(vec![RegistrationController::new(
+                user(2, 0).caller,
+                vec![RegistrationControllerRole::Registrant]
+            )])
+        );
+    }
+
+    #[test]
+    fn expired_transfer_cannot_be_accepted() {
+        let domain = "expiring.test.icp.";
+        let service = TransferService::default();
+        register(&user(1, 0), domain);
+        service
+            .initiate_transfer(&user(1, 0), domain, user(2, 0).caller)
+            .unwrap();
+
+        assert_eq!(
+            service.accept_transfer(&user(2, 7 * DAY), domain),
+            Err(TransferError::TransferExpired {
+                domain: domain.to_string(),
+                expired_at: 7 * DAY,
+            })
+        );
+        assert_eq!(registrant(domain), Some(user(1, 0).caller));
+
+        service.cancel_transfer(&user(2, 7 * DAY), domain).unwrap();
+        assert_eq!(service.get_pending_transfer(domain), None);
+        assert_eq!(
+            events(domain),
+            vec![TransferEvent::Initiated, TransferEvent::Cancelled]
+        );
+    }
+
+    #[test]
+    fn cancel_transfer_requires_party_of_transfer() {
+        let domain = "cancelled.test.icp.";
+        let service = TransferService::default();
+        register(&user(1, 0), domain);
+        service
+            .initiate_transfer(&user(1, 0), domain, user(2, 0).caller)
+            .unwrap();
+
+        assert_eq!(
+            service.cancel_transfer(&user(3, 0), domain),
+            Err(TransferError::Unauthorized {
+                caller: user(3, 0).caller.to_text(),
+                domain: domain.to_string(),
+            })
+        );
+        service
+            .cancel_transfer(&CallContext::canister_controller(0), domain)
+            .unwrap();
+        assert_eq!(
+            service.accept_transfer(&user(2, 0), domain),
+            Err(TransferError::NoPendingTransfer {
+                domain: domain.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn initiate_transfer_validates_new_owner() {
+        let domain = "owned.test.icp.";
+        let service = TransferService::default();
+        register(&user(1, 0), domain);
+
+        for new_owner in [user(1, 0).caller, Principal::anonymous()] {
+            assert_eq!(
+                service.initiate_transfer(&user(1, 0), domain, new_owner),
+                Err(TransferError::InvalidNewOwner {
+                    domain: domain.to_string(),
+                    new_owner: new_owner.to_text(),
+                })
+            );
+        }
+        assert_eq!(
+            service.initiate_transfer(&user(1, 0), "unknown.test.icp.", user(2, 0).caller),
+            Err(TransferError::NotRegistered {
+                domain: String::from("unknown.test.icp."),
+            })
+        );
+        assert!(events(domain).is_empty());
+    }
+
+    #[test]
+    fn transfer_of_expired_registration_fails() {
+        let domain = "lapsed.icp.";
+        TldPolicyService::default()
+            .set_policy(
+                &CallContext::canister_controller(0),
+                &crate::types::TldPolicy {
+                    self_service: true,
+                    ..crate::types::TldPolicy::default_tld()
+                },
+            )
+            .unwrap();
+        register(&user(1, 0), domain);
+        let expires_at = RegistrationRepository::default()
+            .get(&ZoneApexDomain::new(domain.to_string()).unwrap())
+            .and_then(|registration| registration.expires_at)
+            .unwrap();
+
+        assert_eq!(
+            TransferService::default().initiate_transfer(
+                &user(1, expires_at),
+                domain,
+                user(2, 0).caller
+            ),
+            Err(TransferError::RegistrationExpired {
+                domain: domain.to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use crate::common::{DEFAULT_GRACE_PERIOD, DEFAULT_REDEMPTION_PERIOD, DEFAULT_REGISTRATION_PERIOD};
+use candid::{CandidType, Deserialize};
+
+/// The number of nanoseconds in a second, the times of the registrations are in nanoseconds since the UNIX epoch.
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// The lease of the registrations of a TLD, a registration expires at the end of its registration period unless it
+/// is renewed.
+///
+/// Expired registrations stop resolving, during the grace period their registrant can still renew them and during
+/// the redemption period that follows only canister controllers can restore them. Afterwards they are removed and
+/// anyone can register the domain again.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LeasePolicy {
+    /// The time a registration lasts, and by which a renewal extends it, in seconds.
+    pub registration_period: u64,
+    /// The time after expiry in which the registrant can renew the registration, in seconds.
+    pub grace_period: u64,
+    /// The time after the grace period in which only canister controllers can restore the registration, in seconds.
+    pub redemption_period: u64,
+}
+
+impl Default for LeasePolicy {
+    fn default() -> Self {
+        Self {
+            registration_period: DEFAULT_REGISTRATION_PERIOD,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            redemption_period: DEFAULT_REDEMPTION_PERIOD,
+        }
+    }
+}
+
+impl LeasePolicy {
+    /// Returns the time a registration made or renewed at the given time expires.
+    pub fn expires_at(&self, time: u64) -> u64 {
+        time.saturating_add(Self::nanos(self.registration_period))
+    }
+
+    /// Returns the end of the grace period of a registration that expires at the given time.
+    pub fn grace_period_end(&self, expires_at: u64) -> u64 {
+        expires_at.saturating_add(Self::nanos(self.grace_period))
+    }
+
+    /// Returns the end of the redemption period of a registration that expires at the given time, when the
+    /// registration is removed.
+    pub fn redemption_period_end(&self, expires_at: u64) -> u64 {
+        self.grace_period_end(expires_at)
+            .saturating_add(Self::nanos(self.redemption_period))
+    }
+
+    fn nanos(seconds: u64) -> u64 {
+        seconds.saturating_mul(NANOS_PER_SEC)
+    }
+}
@@ -0,0 +1,17 @@
+//! Types of the data kept by the operator canister.
+
+/// Types of the registrations of the domains.
+mod registration;
+pub use registration::*;
+
+/// Types of the leases of the registrations.
+mod lease_policy;
+pub use lease_policy::*;
+
+/// Types of the transfers of the domains between registrants.
+mod transfer;
+pub use transfer::*;
+
+/// Types of the registration policies of the TLDs.
+mod tld_policy;
+pub use tld_policy::*;
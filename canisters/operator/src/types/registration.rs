@@ -0,0 +1,188 @@
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use cns_domain_registry::{
+    api::DomainRecord,
+    types::{RegistrationController, RegistrationControllerRole, ZoneApexDomain},
+};
+use ic_stable_structures::{BoundedStorable, Storable};
+use std::borrow::Cow;
+
+/// The registration of a domain of the TLD, its records are kept in the zone of the TLD.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Registration {
+    /// The registered domain, e.g. "mydomain.icp.".
+    pub domain: ZoneApexDomain,
+    /// The principals that control the domain and their roles.
+    pub controllers: Vec<RegistrationController>,
+    /// The records the domain is registered with, which are restored when an expired registration is renewed.
+    pub records: Vec<DomainRecord>,
+    /// The time the registration expires in nanoseconds since the UNIX epoch, if it expires.
+    pub expires_at: Option<u64>,
+    /// The time the registration is removed in nanoseconds since the UNIX epoch, only set once the registration
+    /// expired and its records were removed from the zone.
+    pub suspended_until: Option<u64>,
+    /// The time the sweep retries the registration in nanoseconds since the UNIX epoch, only set once its records
+    /// couldn't be removed from the zone.
+    pub sweep_retry_at: Option<u64>,
+}
+
+impl Registration {
+    /// The maximum byte size of a Registration, which fits its controllers and the records of limited length.
+    pub const MAX_SIZE: u32 = 4096;
+
+    /// The maximum number of controllers of a registration, including its registrant.
+    pub const MAX_CONTROLLERS: usize = 10;
+
+    pub fn new(
+        domain: ZoneApexDomain,
+        controllers: Vec<RegistrationController>,
+        records: Vec<DomainRecord>,
+        expires_at: Option<u64>,
+    ) -> Self {
+        Self {
+            domain,
+            controllers,
+            records,
+            expires_at,
+            suspended_until: None,
+            sweep_retry_at: None,
+        }
+    }
+
+    /// Makes the principal the registrant and only controller of the domain, the controllers granted by the
+    /// previous registrant are removed so that they can't act on the domain once it changed hands.
+    pub fn set_registrant(&mut self, registrant: Principal) {
+        self.controllers = vec![RegistrationController {
+            controller_id: registrant,
+            roles: vec![RegistrationControllerRole::Registrant],
+        }];
+    }
+
+    /// Returns true if the registration expired at the given time.
+    pub fn is_expired(&self, time: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= time)
+    }
+
+    /// Returns true if the records of the expired registration were removed from the zone.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_until.is_some()
+    }
+
+    /// Returns the time the registration has to be swept next, which is when it expires or, once it is suspended,
+    /// when it is removed, unless the sweep has to retry it later.
+    pub fn sweep_at(&self) -> Option<u64> {
+        let sweep_at = self.suspended_until.or(self.expires_at)?;
+
+        Some(
+            self.sweep_retry_at
+                .map_or(sweep_at, |retry_at| retry_at.max(sweep_at)),
+        )
+    }
+
+    /// Returns the principal that owns the domain.
+    pub fn registrant(&self) -> Option<Principal> {
+        self.controllers
+            .iter()
+            .find(|controller| {
+                controller
+                    .roles
+                    .contains(&RegistrationControllerRole::Registrant)
+            })
+            .map(|controller| controller.controller_id)
+    }
+
+    /// Returns the controller of the domain with the given principal.
+    pub fn controller(&self, principal: &Principal) -> Option<&RegistrationController> {
+        self.controllers
+            .iter()
+            .find(|controller| controller.controller_id == *principal)
+    }
+
+    /// Returns true if the principal is a controller with a role that can change the records of the domain.
+    pub fn can_manage_records(&self, principal: &Principal) -> bool {
+        self.has_role_that(principal, RegistrationControllerRole::can_manage_records)
+    }
+
+    /// Returns true if the principal is a controller with a role that can add and remove the controllers of the
+    /// domain.
+    pub fn can_manage_controllers(&self, principal: &Principal) -> bool {
+        self.has_role_that(
+            principal,
+            RegistrationControllerRole::can_manage_controllers,
+        )
+    }
+
+    /// Returns true if the principal is a controller with a role that can transfer and renew the registration.
+    pub fn can_manage_registration(&self, principal: &Principal) -> bool {
+        self.has_role_that(
+            principal,
+            RegistrationControllerRole::can_manage_registration,
+        )
+    }
+
+    fn has_role_that(
+        &self,
+        principal: &Principal,
+        permission: fn(&RegistrationControllerRole) -> bool,
+    ) -> bool {
+        self.controller(principal)
+            .is_some_and(|controller| controller.roles.iter().any(permission))
+    }
+}
+
+/// Adds serialization and deserialization support to Registration to stable memory.
+impl Storable for Registration {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+/// Represents the memory required to store a Registration in stable memory.
+impl BoundedStorable for Registration {
+    const MAX_SIZE: u32 = Registration::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// The registration of a domain as stored by the versions of the canister before registrations had records and
+/// leases, see [crate::repositories::RegistrationRepository::migrate_legacy_registrations].
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRegistration {
+    /// The registered domain, e.g. "mydomain.icp.".
+    pub domain: ZoneApexDomain,
+    /// The principals that control the domain and their roles.
+    pub controllers: Vec<RegistrationController>,
+}
+
+impl LegacyRegistration {
+    /// The maximum byte size the legacy registrations were stored with.
+    pub const MAX_SIZE: u32 = 1024;
+}
+
+/// Legacy registrations never expire, hence their records only have to be kept in the zone of their TLD.
+impl From<LegacyRegistration> for Registration {
+    fn from(registration: LegacyRegistration) -> Self {
+        Registration::new(registration.domain, registration.controllers, vec![], None)
+    }
+}
+
+/// Adds serialization and deserialization support to LegacyRegistration to stable memory.
+impl Storable for LegacyRegistration {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+/// Represents the memory required to store a LegacyRegistration in stable memory.
+impl BoundedStorable for LegacyRegistration {
+    const MAX_SIZE: u32 = LegacyRegistration::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
@@ -0,0 +1,161 @@
+use crate::{
+    common::{DEFAULT_TEST_LABEL, DEFAULT_TLD, REVERSE_LABEL, SUBNET_LABEL},
+    types::{LeasePolicy, Registration},
+};
+use candid::{CandidType, Decode, Deserialize, Encode};
+use cns_domain_registry::types::{DomainRecordTypes, ZoneApexDomain};
+use ic_stable_structures::{BoundedStorable, Storable};
+use std::{borrow::Cow, ops::Deref};
+
+/// The registration policy of a top level domain (TLD) operated by the canister.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct TldPolicy {
+    /// The TLD the policy applies to, e.g. "icp.".
+    pub tld: ZoneApexDomain,
+    /// Whether callers that are not canister controllers can register the domains of the TLD that are not reserved,
+    /// the test domains can be registered by anyone regardless of this setting.
+    pub self_service: bool,
+    /// The record types that domains of the TLD can be registered with, e.g. "CID".
+    pub allowed_record_types: Vec<String>,
+    /// Labels directly below the TLD that only canister controllers can register, together with the domains below
+    /// them, e.g. "subnet". The reverse index label is always reserved.
+    pub reserved_labels: Vec<String>,
+    /// The label below the TLD whose domains can be registered by anyone, e.g. "test" for "mydomain.test.icp.".
+    ///
+    /// Test domains are not added to the reverse index of the registered principals.
+    pub test_label: Option<String>,
+    /// The lease of the registrations of callers that are not canister controllers, the registrations of canister
+    /// controllers never expire. Without a lease no registration of the TLD expires.
+    pub lease: Option<LeasePolicy>,
+}
+
+impl TldPolicy {
+    /// The maximum byte size of a TldPolicy, which fits the max number of reserved labels of max length.
+    pub const MAX_SIZE: u32 = 8192;
+
+    /// The policy of the TLD of a new canister, only canister controllers can register domains outside of the
+    /// test domains, e.g. "mydomain.test.icp.", and the test domains expire after a year unless they are renewed.
+    pub fn default_tld() -> Self {
+        Self {
+            tld: ZoneApexDomain::new(DEFAULT_TLD.to_string()).unwrap(),
+            self_service: false,
+            allowed_record_types: vec![
+                DomainRecordTypes::CID.to_string(),
+                DomainRecordTypes::SID.to_string(),
+            ],
+            reserved_labels: vec![SUBNET_LABEL.to_string()],
+            test_label: Some(DEFAULT_TEST_LABEL.to_string()),
+            lease: Some(LeasePolicy::default()),
+        }
+    }
+
+    /// Returns true if domains of the TLD can be registered with records of the record type.
+    pub fn allows_record_type(&self, record_type: &str) -> bool {
+        self.allowed_record_types
+            .iter()
+            .any(|allowed_type| allowed_type.eq_ignore_ascii_case(record_type))
+    }
+
+    /// Returns the label of the domain directly below the TLD, e.g. "mydomain" for "www.mydomain.icp.".
+    pub fn top_label<'a>(&self, domain: &'a str) -> Option<&'a str> {
+        domain
+            .strip_suffix(self.tld.deref())?
+            .strip_suffix('.')?
+            .rsplit('.')
+            .next()
+    }
+
+    /// Returns the reserved label of the domain, if the domain is at or below one.
+    pub fn reserved_label<'a>(&self, domain: &'a str) -> Option<&'a str> {
+        self.top_label(domain).filter(|label| {
+            *label == REVERSE_LABEL
+                || self
+                    .reserved_labels
+                    .iter()
+                    .any(|reserved| reserved == label)
+        })
+    }
+
+    /// Returns the end of the grace period of a registration of the TLD that expires at the given time, TLDs
+    /// without a lease have no grace period.
+    pub fn grace_period_end(&self, expires_at: u64) -> u64 {
+        self.lease
+            .as_ref()
+            .map_or(expires_at, |lease| lease.grace_period_end(expires_at))
+    }
+
+    /// Returns the end of the redemption period of a registration of the TLD that expires at the given time, TLDs
+    /// without a lease have no redemption period.
+    pub fn redemption_period_end(&self, expires_at: u64) -> u64 {
+        self.lease
+            .as_ref()
+            .map_or(expires_at, |lease| lease.redemption_period_end(expires_at))
+    }
+
+    /// Returns true if the redemption period of the registration ended at the given time, hence the domain can be
+    /// registered by anyone even before the registration is removed.
+    pub fn is_released(&self, registration: &Registration, time: u64) -> bool {
+        registration
+            .expires_at
+            .is_some_and(|expires_at| self.redemption_period_end(expires_at) <= time)
+    }
+
+    /// Returns true if the domain is below the test label of the TLD, the test label itself is not a test domain.
+    pub fn is_test_domain(&self, domain: &str) -> bool {
+        self.test_label.as_ref().is_some_and(|test_label| {
+            domain.ends_with(&format!(".{}.{}", test_label, self.tld.deref()))
+        })
+    }
+}
+
+/// Adds serialization and deserialization support to TldPolicy to stable memory.
+impl Storable for TldPolicy {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+/// Represents the memory required to store a TldPolicy in stable memory.
+impl BoundedStorable for TldPolicy {
+    const MAX_SIZE: u32 = TldPolicy::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::registered_domain("mydomain.icp.", Some("mydomain"))]
+    #[case::nested_domain("www.mydomain.icp.", Some("mydomain"))]
+    #[case::tld("icp.", None)]
+    #[case::other_tld("mydomain.dao.", None)]
+    #[case::suffix_of_label("mydomainicp.", None)]
+    fn top_label_of_domain(#[case] domain: &str, #[case] expected: Option<&str>) {
+        assert_eq!(TldPolicy::default_tld().top_label(domain), expected);
+    }
+
+    #[rstest]
+    #[case::reserved_label("subnet.icp.", Some("subnet"))]
+    #[case::below_reserved_label("app-1.subnet.icp.", Some("subnet"))]
+    #[case::reverse_label("aaaaa-aa.reverse.icp.", Some("reverse"))]
+    #[case::other_label("mydomain.icp.", None)]
+    fn reserved_label_of_domain(#[case] domain: &str, #[case] expected: Option<&str>) {
+        assert_eq!(TldPolicy::default_tld().reserved_label(domain), expected);
+    }
+
+    #[rstest]
+    #[case::test_domain("mydomain.test.icp.", true)]
+    #[case::nested_test_domain("www.mydomain.test.icp.", true)]
+    #[case::test_label("test.icp.", false)]
+    #[case::other_domain("mytest.icp.", false)]
+    fn test_domains(#[case] domain: &str, #[case] expected: bool) {
+        assert_eq!(TldPolicy::default_tld().is_test_domain(domain), expected);
+    }
+}
@@ -0,0 +1,102 @@
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use cns_domain_registry::types::ZoneApexDomain;
+use ic_stable_structures::{BoundedStorable, Storable};
+use std::borrow::Cow;
+
+/// A transfer of a domain to a new registrant, which the new registrant has to accept before it expires.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct PendingTransfer {
+    /// The transferred domain, e.g. "mydomain.icp.".
+    pub domain: ZoneApexDomain,
+    /// The registrant of the domain when the transfer was initiated.
+    pub from: Principal,
+    /// The new registrant of the domain.
+    pub to: Principal,
+    /// The time the transfer was initiated in nanoseconds since the UNIX epoch.
+    pub initiated_at: u64,
+    /// The time after which the transfer can no longer be accepted in nanoseconds since the UNIX epoch.
+    pub expires_at: u64,
+}
+
+impl PendingTransfer {
+    /// The maximum byte size of a PendingTransfer, which fits a domain name of max length and two principals.
+    pub const MAX_SIZE: u32 = 512;
+
+    /// Returns true if the transfer can no longer be accepted at the given time.
+    pub fn is_expired(&self, time: u64) -> bool {
+        self.expires_at <= time
+    }
+}
+
+/// Adds serialization and deserialization support to PendingTransfer to stable memory.
+impl Storable for PendingTransfer {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+/// Represents the memory required to store a PendingTransfer in stable memory.
+impl BoundedStorable for PendingTransfer {
+    const MAX_SIZE: u32 = PendingTransfer::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// The events of the transfers that are recorded in the transfer log.
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferEvent {
+    /// The transfer was initiated by the registrant or a canister controller.
+    #[serde(rename = "initiated")]
+    Initiated,
+    /// The transfer was accepted by the new registrant, who now owns the domain.
+    #[serde(rename = "accepted")]
+    Accepted,
+    /// The transfer was cancelled by the registrant, declined by the new registrant or cancelled by a canister
+    /// controller.
+    #[serde(rename = "cancelled")]
+    Cancelled,
+}
+
+/// An entry of the transfer log of a domain.
+#[derive(CandidType, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct TransferLogEntry {
+    /// The sequence of the entry in the transfer log of the domain, starting at 0.
+    pub sequence: u64,
+    /// The time of the event in nanoseconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// The principal that caused the event.
+    pub caller: Principal,
+    /// The event of the transfer.
+    pub event: TransferEvent,
+    /// The registrant of the domain when the transfer was initiated.
+    pub from: Principal,
+    /// The new registrant of the domain.
+    pub to: Principal,
+}
+
+impl TransferLogEntry {
+    /// The maximum byte size of a TransferLogEntry, which fits three principals.
+    pub const MAX_SIZE: u32 = 512;
+}
+
+/// Adds serialization and deserialization support to TransferLogEntry to stable memory.
+impl Storable for TransferLogEntry {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+/// Represents the memory required to store a TransferLogEntry in stable memory.
+impl BoundedStorable for TransferLogEntry {
+    const MAX_SIZE: u32 = TransferLogEntry::MAX_SIZE;
+
+    const IS_FIXED_SIZE: bool = false;
+}
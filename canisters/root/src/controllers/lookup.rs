@@ -0,0 +1,10 @@
+use crate::services::TldDelegationService;
+use cns_domain_registry::api::DomainLookup;
+use ic_cdk::query;
+
+/// Lookup a domain name and return the NC record of its TLD, in the answers of NC lookups and in the authorities
+/// of lookups of other record types.
+#[query]
+fn lookup(domain: String, record_type: String) -> DomainLookup {
+    TldDelegationService::default().lookup(&domain, &record_type)
+}
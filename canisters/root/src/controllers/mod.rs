@@ -0,0 +1,20 @@
+//! Canister endpoints of the root canister, as defined in `spec.did`.
+
+/// Endpoints to resolve domain names to the naming canisters of their TLDs.
+mod lookup;
+
+/// Endpoints to delegate TLDs.
+mod register;
+
+use cns_domain_registry::types::CallContext;
+use ic_cdk::{
+    api::{is_controller, time},
+    caller,
+};
+
+/// Builds the context of the current call, used by the services to authorize the caller.
+fn call_context() -> CallContext {
+    let caller = caller();
+
+    CallContext::new(caller, is_controller(&caller), time())
+}
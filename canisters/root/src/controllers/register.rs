@@ -0,0 +1,19 @@
+use super::call_context;
+use crate::services::TldDelegationService;
+use cns_domain_registry::{api::RegistrationRecords, types::RegisterResult};
+use ic_cdk::update;
+
+/// Delegate a TLD to the naming canister of its NC record, only canister controllers can register TLDs.
+#[update]
+fn register(domain: String, records: RegistrationRecords) -> RegisterResult {
+    match TldDelegationService::default().register(&call_context(), &domain, &records) {
+        Ok(()) => RegisterResult {
+            success: true,
+            message: None,
+        },
+        Err(error) => RegisterResult {
+            success: false,
+            message: Some(error.to_string()),
+        },
+    }
+}
@@ -0,0 +1,5 @@
+//! Various error types for failure scenarios
+
+/// Error types for the register operation.
+mod register;
+pub use register::*;
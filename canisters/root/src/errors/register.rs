@@ -0,0 +1,63 @@
+use cns_domain_registry::errors::{RecordDataError, ZoneApexDomainError};
+
+/// Container for register errors
+#[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
+pub enum RegisterError {
+    /// Only canister controllers can delegate TLDs
+    #[error(
+        "Currently only a canister controller can register new TLD operators, caller: {caller}"
+    )]
+    Unauthorized {
+        /// The principal of the caller
+        caller: String,
+    },
+
+    /// The domain is not a valid domain name
+    #[error("Domain is invalid: {0}")]
+    InvalidDomain(ZoneApexDomainError),
+
+    /// The domain has more than one label
+    #[error("The given domain {domain:?} is not a TLD, its TLD is {tld:?}")]
+    NotTld {
+        /// The registered domain
+        domain: String,
+        /// The TLD of the registered domain
+        tld: String,
+    },
+
+    /// A TLD is delegated with exactly one NC record
+    #[error("Currently exactly one domain record must be specified, received {count:?}")]
+    InvalidRecordCount {
+        /// The number of records received
+        count: usize,
+    },
+
+    /// The name of the record is not the registered TLD
+    #[error("Inconsistent domain record, record name {name:?} doesn't match TLD {tld:?}")]
+    RecordNameMismatch {
+        /// The name of the record
+        name: String,
+        /// The registered TLD
+        tld: String,
+    },
+
+    /// Only NC records can be registered
+    #[error("Unsupported record type {record_type:?}, expected \"NC\"")]
+    UnsupportedRecordType {
+        /// The record type received
+        record_type: String,
+    },
+
+    /// The ttl of the record does not fit in 32 bits
+    #[error("Record TTL is too large. Received {ttl}, expected smaller or equal to {max_ttl:?}")]
+    TtlTooLarge {
+        /// The ttl received
+        ttl: String,
+        /// The max ttl
+        max_ttl: u32,
+    },
+
+    /// The data of the NC record is not a principal
+    #[error("Record data is invalid: {0}")]
+    InvalidRecordData(RecordDataError),
+}
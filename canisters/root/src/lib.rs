@@ -0,0 +1,13 @@
+//! # CNS root canister
+//!
+//! The root canister is the entry point of the Chain Name System (CNS) on the
+//! Internet Computer(https://internetcomputer.org), it delegates each top level domain (TLD) to the naming canister
+//! that operates it.
+//!
+//! Lookups of any name of a registered TLD are answered with the NC record of the TLD, which points the client to
+//! the naming canister that can answer them.
+
+pub mod errors;
+pub mod repositories;
+pub mod services;
+pub mod types;
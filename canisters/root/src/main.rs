@@ -1,3 +1,16 @@
-fn main() {
-    println!("Not implemented");
-}
+//! # CNS root canister
+//!
+//! The root canister is the entry point of the Chain Name System (CNS) on the
+//! Internet Computer(https://internetcomputer.org), it delegates each top level domain (TLD) to the naming canister
+//! that operates it.
+//!
+//! Lookups of any name of a registered TLD are answered with the NC record of the TLD, which points the client to
+//! the naming canister that can answer them.
+
+mod controllers;
+pub mod errors;
+pub mod repositories;
+pub mod services;
+pub mod types;
+
+fn main() {}
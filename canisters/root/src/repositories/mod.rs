@@ -0,0 +1,9 @@
+//! Repositories for the data kept by the root canister.
+
+/// Common configurations for repositories.
+mod setup;
+pub use setup::*;
+
+/// Repository for the delegations of the top level domains.
+mod tld_delegation;
+pub use tld_delegation::*;
@@ -0,0 +1,23 @@
+use ic_stable_structures::{
+    memory_manager::{MemoryId, MemoryManager, VirtualMemory},
+    DefaultMemoryImpl,
+};
+use std::cell::RefCell;
+
+/// Stable memory id used to store the delegations of the top level domains.
+pub const TLD_DELEGATIONS_MEMORY_ID: MemoryId = MemoryId::new(0);
+
+/// Memory layout for the stable memory.
+pub type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+  // The memory manager is used for simulating multiple memories. Given a `MemoryId` it can
+  // return a memory that can be used by stable structures.
+  static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+      RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+}
+
+/// A helper function that executes a closure with the memory manager.
+pub fn with_memory_manager<R>(f: impl FnOnce(&MemoryManager<DefaultMemoryImpl>) -> R) -> R {
+    MEMORY_MANAGER.with(|cell| f(&cell.borrow()))
+}
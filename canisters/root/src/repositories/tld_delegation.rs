@@ -0,0 +1,67 @@
+use crate::{
+    repositories::{with_memory_manager, Memory, TLD_DELEGATIONS_MEMORY_ID},
+    types::TldDelegation,
+};
+use cns_domain_registry::{
+    repositories::Repository,
+    types::{DomainRecord, ZoneApexDomain},
+};
+use ic_stable_structures::BTreeMap;
+use std::cell::RefCell;
+
+/// The database schema for the TldDelegation repository, the NC record of each delegated TLD.
+pub type TldDelegationDatabase = BTreeMap<ZoneApexDomain, DomainRecord, Memory>;
+
+thread_local! {
+  /// The memory reference to the TldDelegation repository.
+  static DB: RefCell<TldDelegationDatabase> = with_memory_manager(|memory_manager| {
+    RefCell::new(
+      BTreeMap::init(memory_manager.get(TLD_DELEGATIONS_MEMORY_ID))
+    )
+  })
+}
+
+/// A repository that enables keeping the delegations of the top level domains in stable memory.
+pub struct TldDelegationRepository {}
+
+/// Enables the initialization of the TldDelegation repository.
+impl TldDelegationRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for TldDelegationRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TldDelegationRepository {
+    /// Returns the delegation of the TLD, if it is registered.
+    pub fn get(&self, tld: &ZoneApexDomain) -> Option<TldDelegation> {
+        DB.with(|m| m.borrow().get(tld))
+            .map(|record| TldDelegation::new(tld.clone(), record))
+    }
+
+    /// Returns the number of delegated TLDs.
+    pub fn count(&self) -> u64 {
+        DB.with(|m| m.borrow().len())
+    }
+}
+
+/// Common interfaces for the TldDelegation repository, a TLD has a single delegation hence inserting a delegation
+/// replaces the previous one.
+impl Repository<TldDelegation> for TldDelegationRepository {
+    fn exists(&self, record: &TldDelegation) -> bool {
+        DB.with(|m| m.borrow().contains_key(&record.tld))
+    }
+
+    fn insert(&self, record: TldDelegation) {
+        DB.with(|m| m.borrow_mut().insert(record.tld, record.record));
+    }
+
+    fn remove(&self, record: &TldDelegation) -> bool {
+        DB.with(|m| m.borrow_mut().remove(&record.tld).is_some())
+    }
+}
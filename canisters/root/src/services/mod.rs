@@ -0,0 +1,5 @@
+//! Services that implement the business logic of the root canister.
+
+/// Service to delegate the top level domains and resolve them to their naming canisters.
+mod tld_delegation;
+pub use tld_delegation::*;
@@ -0,0 +1,286 @@
+use crate::{errors::RegisterError, repositories::TldDelegationRepository, types::TldDelegation};
+use cns_domain_registry::{
+    api::{DomainLookup, RegistrationRecords},
+    errors::ZoneApexDomainError,
+    repositories::Repository,
+    types::{CallContext, DomainRecord, DomainRecordTypes, RecordData, RecordName, ZoneApexDomain},
+};
+use std::ops::Deref;
+
+/// A service that delegates the top level domains (TLD) to the naming canisters that operate them.
+pub struct TldDelegationService {
+    tld_delegation_repository: TldDelegationRepository,
+}
+
+impl TldDelegationService {
+    pub fn new() -> Self {
+        Self {
+            tld_delegation_repository: TldDelegationRepository::default(),
+        }
+    }
+
+    /// Looks up the delegation of the TLD of the domain, any name of a registered TLD can be looked up.
+    ///
+    /// NC lookups are answered with the NC record of the TLD, lookups of other record types have it in their
+    /// authorities since only the naming canister of the TLD can answer them.
+    pub fn lookup(&self, domain: &str, record_type: &str) -> DomainLookup {
+        let mut lookup = DomainLookup::default();
+        let delegation = Self::parse_domain(domain)
+            .ok()
+            .and_then(|domain| self.tld_delegation_repository.get(&Self::tld(&domain)));
+
+        if let Some(delegation) = delegation {
+            let record = delegation.to_domain_record();
+            match record_type.eq_ignore_ascii_case(&DomainRecordTypes::NC.to_string()) {
+                true => lookup.answers.push(record),
+                false => lookup.authorities.push(record),
+            }
+        }
+
+        lookup
+    }
+
+    /// Delegates the TLD to the naming canister of its NC record, replacing the previous delegation if any.
+    ///
+    /// Only canister controllers can register TLDs, the registration must have exactly one NC record for the TLD.
+    pub fn register(
+        &self,
+        context: &CallContext,
+        domain: &str,
+        registration: &RegistrationRecords,
+    ) -> Result<(), RegisterError> {
+        if !context.is_canister_controller {
+            return Err(RegisterError::Unauthorized {
+                caller: context.caller.to_text(),
+            });
+        }
+
+        let domain = Self::parse_domain(domain).map_err(RegisterError::InvalidDomain)?;
+        let tld = Self::tld(&domain);
+        if tld != domain {
+            return Err(RegisterError::NotTld {
+                domain: domain.deref().to_string(),
+                tld: tld.deref().to_string(),
+            });
+        }
+
+        let records = registration.records.as_deref().unwrap_or_default();
+        let [record] = records else {
+            return Err(RegisterError::InvalidRecordCount {
+                count: records.len(),
+            });
+        };
+
+        if Self::parse_domain(&record.name).ok().as_ref() != Some(&tld) {
+            return Err(RegisterError::RecordNameMismatch {
+                name: record.name.clone(),
+                tld: tld.deref().to_string(),
+            });
+        }
+
+        if !record
+            .record_type
+            .eq_ignore_ascii_case(&DomainRecordTypes::NC.to_string())
+        {
+            return Err(RegisterError::UnsupportedRecordType {
+                record_type: record.record_type.clone(),
+            });
+        }
+
+        let ttl = u32::try_from(&record.ttl.0).map_err(|_| RegisterError::TtlTooLarge {
+            ttl: record.ttl.to_string(),
+            max_ttl: DomainRecord::max_ttl_value(),
+        })?;
+        let data = RecordData::parse(DomainRecordTypes::NC, &record.data)
+            .map_err(RegisterError::InvalidRecordData)?;
+
+        self.tld_delegation_repository.insert(TldDelegation::new(
+            tld,
+            DomainRecord::new(
+                RecordName::default(),
+                DomainRecordTypes::NC.to_string(),
+                ttl,
+                data.to_string(),
+            ),
+        ));
+
+        Ok(())
+    }
+
+    /// Parses the domain name, the leading dot of the TLDs of the Motoko root canister is accepted since clients
+    /// still look up names like ".icp.".
+    fn parse_domain(domain: &str) -> Result<ZoneApexDomain, ZoneApexDomainError> {
+        let domain = domain.to_ascii_lowercase();
+        let domain = match domain.strip_prefix('.') {
+            Some(tld) if !tld.is_empty() => tld.to_string(),
+            _ => domain,
+        };
+
+        ZoneApexDomain::new(domain)
+    }
+
+    /// Returns the TLD of the domain, which is its rightmost label.
+    fn tld(domain: &ZoneApexDomain) -> ZoneApexDomain {
+        let label = domain
+            .trim_end_matches('.')
+            .rsplit('.')
+            .next()
+            .unwrap_or_default();
+
+        ZoneApexDomain::new(format!("{}.", label)).unwrap_or_else(|_| domain.clone())
+    }
+}
+
+impl Default for TldDelegationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::{Nat, Principal};
+    use cns_domain_registry::api::DomainRecord as DomainRecordResult;
+    use rstest::rstest;
+
+    const NAMING_CANISTER: &str = "qoctq-giaaa-aaaaa-aaaea-cai";
+
+    fn nc_record(name: &str, record_type: &str, data: &str) -> DomainRecordResult {
+        DomainRecordResult {
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            ttl: Nat::from(3600u32),
+            data: data.to_string(),
+        }
+    }
+
+    fn register(domain: &str, records: Vec<DomainRecordResult>) -> Result<(), RegisterError> {
+        TldDelegationService::default().register(
+            &CallContext::canister_controller(0),
+            domain,
+            &RegistrationRecords {
+                controllers: vec![],
+                records: Some(records),
+            },
+        )
+    }
+
+    #[rstest]
+    #[case::tld_nc("icp.", "NC")]
+    #[case::legacy_tld_nc(".icp.", "nc")]
+    #[case::subdomain_nc("example.ICP.", "Nc")]
+    #[case::nested_subdomain_nc("one.more.icp.", "NC")]
+    fn lookup_nc_of_registered_tld(#[case] domain: &str, #[case] record_type: &str) {
+        register(".icp.", vec![nc_record(".icp.", "NC", NAMING_CANISTER)]).unwrap();
+
+        let lookup = TldDelegationService::default().lookup(domain, record_type);
+
+        assert_eq!(
+            lookup.answers,
+            vec![nc_record("icp.", "NC", NAMING_CANISTER)]
+        );
+        assert!(lookup.authorities.is_empty());
+        assert!(lookup.additionals.is_empty());
+    }
+
+    #[rstest]
+    #[case::tld_cid("icp.", "CID")]
+    #[case::subdomain_cid("example.icp.", "cid")]
+    #[case::unknown_type("yet.another.one.icp.", "WeirdRecordType")]
+    fn lookup_other_types_of_registered_tld(#[case] domain: &str, #[case] record_type: &str) {
+        register("icp.", vec![nc_record("icp.", "NC", NAMING_CANISTER)]).unwrap();
+
+        let lookup = TldDelegationService::default().lookup(domain, record_type);
+
+        assert!(lookup.answers.is_empty());
+        assert_eq!(
+            lookup.authorities,
+            vec![nc_record("icp.", "NC", NAMING_CANISTER)]
+        );
+    }
+
+    #[test]
+    fn lookup_supports_many_tlds() {
+        let other_canister = "aaaaa-aa";
+        register("icp.", vec![nc_record("icp.", "NC", NAMING_CANISTER)]).unwrap();
+        register("test.", vec![nc_record("test.", "NC", other_canister)]).unwrap();
+
+        let service = TldDelegationService::default();
+
+        assert_eq!(
+            service.lookup("example.test.", "NC").answers,
+            vec![nc_record("test.", "NC", other_canister)]
+        );
+        assert_eq!(
+            service.lookup("example.icp.", "NC").answers,
+            vec![nc_record("icp.", "NC", NAMING_CANISTER)]
+        );
+        assert_eq!(
+            service.lookup("example.com.", "NC"),
+            DomainLookup::default()
+        );
+        assert_eq!(service.lookup("..", "NC"), DomainLookup::default());
+    }
+
+    #[test]
+    fn register_replaces_delegation() {
+        register("icp.", vec![nc_record("icp.", "NC", "aaaaa-aa")]).unwrap();
+        register("icp.", vec![nc_record("icp.", "NC", NAMING_CANISTER)]).unwrap();
+
+        assert_eq!(
+            TldDelegationService::default().lookup("icp.", "NC").answers,
+            vec![nc_record("icp.", "NC", NAMING_CANISTER)]
+        );
+    }
+
+    #[rstest]
+    #[case::not_tld(
+        "example.icp.",
+        vec![nc_record("example.icp.", "NC", NAMING_CANISTER)],
+        RegisterError::NotTld { domain: String::from("example.icp."), tld: String::from("icp.") }
+    )]
+    #[case::missing_record("icp.", vec![], RegisterError::InvalidRecordCount { count: 0 })]
+    #[case::multiple_records(
+        "icp.",
+        vec![nc_record("icp.", "NC", NAMING_CANISTER), nc_record("icp.", "NC", "aaaaa-aa")],
+        RegisterError::InvalidRecordCount { count: 2 }
+    )]
+    #[case::record_name_mismatch(
+        "icp.",
+        vec![nc_record("com.", "NC", NAMING_CANISTER)],
+        RegisterError::RecordNameMismatch { name: String::from("com."), tld: String::from("icp.") }
+    )]
+    #[case::unsupported_record_type(
+        "icp.",
+        vec![nc_record("icp.", "CID", NAMING_CANISTER)],
+        RegisterError::UnsupportedRecordType { record_type: String::from("CID") }
+    )]
+    fn register_invalid_tld(
+        #[case] domain: &str,
+        #[case] records: Vec<DomainRecordResult>,
+        #[case] expected: RegisterError,
+    ) {
+        assert_eq!(register(domain, records), Err(expected));
+        assert_eq!(TldDelegationRepository::default().count(), 0);
+    }
+
+    #[test]
+    fn register_requires_canister_controller() {
+        let caller = Principal::from_slice(&[1; 29]);
+
+        assert_eq!(
+            TldDelegationService::default().register(
+                &CallContext::new(caller, false, 0),
+                "icp.",
+                &RegistrationRecords {
+                    controllers: vec![],
+                    records: Some(vec![nc_record("icp.", "NC", NAMING_CANISTER)]),
+                },
+            ),
+            Err(RegisterError::Unauthorized {
+                caller: caller.to_text()
+            })
+        );
+    }
+}
@@ -0,0 +1,5 @@
+//! Types of the data kept by the root canister.
+
+/// Types of the delegations of the top level domains.
+mod tld_delegation;
+pub use tld_delegation::*;
@@ -0,0 +1,30 @@
+use candid::Nat;
+use cns_domain_registry::{
+    api::DomainRecord as DomainRecordResult,
+    types::{DomainRecord, ZoneApexDomain},
+};
+
+/// The delegation of a top level domain (TLD) to the naming canister that operates it, e.g. "icp.".
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TldDelegation {
+    /// The delegated TLD.
+    pub tld: ZoneApexDomain,
+    /// The NC record of the TLD, its name is the apex of the TLD and its data the principal of the naming canister.
+    pub record: DomainRecord,
+}
+
+impl TldDelegation {
+    pub fn new(tld: ZoneApexDomain, record: DomainRecord) -> Self {
+        Self { tld, record }
+    }
+
+    /// Returns the NC record as exposed by the canister interface, with the TLD as its fully qualified name.
+    pub fn to_domain_record(&self) -> DomainRecordResult {
+        DomainRecordResult {
+            name: self.record.name.to_fully_qualified_name(&self.tld),
+            record_type: self.record.record_type.clone(),
+            ttl: Nat::from(self.record.ttl),
+            data: self.record.data.clone(),
+        }
+    }
+}
@@ -4,7 +4,7 @@
 
 use candid::{CandidType, Deserialize, Principal};
 use ic_cdk::{init, update};
-use ic_cns_canister_client::CnsError;
+use ic_cns_canister_client::{CnsError, DomainRecord};
 
 #[derive(CandidType, Deserialize)]
 pub struct ClientInit {
@@ -23,11 +23,8 @@ fn init(init_arg: Option<ClientInit>) {
 }
 
 #[update]
-async fn register_domain(domain: String, cid_text: String) -> Result<(), CnsError> {
-    let cid = Principal::from_text(&cid_text)
-        .map_err(|e| format!("Failed parsing principal {}: {}", cid_text, e))
-        .unwrap();
-    ic_cns_canister_client::register_domain(&domain, cid).await
+async fn register_domain(domain: String, records: Vec<DomainRecord>) -> Result<(), CnsError> {
+    ic_cns_canister_client::register_domain(&domain, records).await
 }
 
 #[update]
@@ -0,0 +1,965 @@
+use crate::{CanisterId, CnsError, DomainLookup, DomainRecord};
+use cns_domain_registry::{
+    common::{ANY_RECORD_TYPE, CERTIFIED_RECORDS_LABEL, MAX_CNAME_CHAIN_LENGTH, WILDCARD_LABEL},
+    utils::{canonical_name, rrset_hash},
+};
+use ic_certification::{Certificate, HashTree, HashTreeNode, LookupResult, SubtreeLookupResult};
+use serde_bytes::ByteBuf;
+use std::collections::BTreeMap;
+
+/// The DER prefix of a BLS12-381 public key as used by the IC.
+const DER_PREFIX: [u8; 37] = [
+    0x30, 0x81, 0x82, 0x30, 0x1d, 0x06, 0x0d, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xdc, 0x7c, 0x05,
+    0x03, 0x01, 0x02, 0x01, 0x06, 0x0c, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xdc, 0x7c, 0x05, 0x03,
+    0x02, 0x01, 0x03, 0x61, 0x00,
+];
+
+/// The length of a BLS12-381 public key.
+const BLS_KEY_LENGTH: usize = 96;
+
+/// The domain separator used by the IC to sign the root hash of the state tree.
+const IC_STATE_ROOT_DOMAIN_SEPARATOR: &[u8; 14] = b"\x0Dic-state-root";
+
+/// The record type of the alias records that lookups follow.
+const CNAME_RECORD_TYPE: &str = "CNAME";
+
+/// The record type of the record that every zone has at its apex.
+const SOA_RECORD_TYPE: &str = "SOA";
+
+/// The maximum age of a certificate, older certificates are rejected to prevent replaying outdated answers.
+pub const MAX_CERTIFICATE_AGE_NANOS: u64 = 5 * 60 * 1_000_000_000;
+
+/// Verifies the certification of a lookup answered by a query call to a naming canister and returns the
+/// certified answers.
+///
+/// The certificate must be signed by the IC, either with the given root key or with the key of a subnet that
+/// the root key delegated to and that hosts the naming canister, and must not be older than
+/// [MAX_CERTIFICATE_AGE_NANOS]. The state tree must match the data certified by the naming canister and prove
+/// that the answers are exactly the records stored for the domain and record type, or that there are none.
+///
+/// When the lookup followed CNAME records, the answers are the records stored at the end of the chain and every
+/// followed CNAME record in the additionals is verified as well, lookups without answers must also prove that the
+/// chain was not cut short. Answers that were synthesized from a wildcard
+/// name are verified against the records of the wildcard name.
+///
+/// Certifications are only available to query calls made outside of the IC, inter-canister calls don't
+/// receive a data certificate.
+pub fn verify_domain_lookup(
+    naming_canister_id: CanisterId,
+    domain: &str,
+    record_type: &str,
+    lookup: &DomainLookup,
+    root_key: &[u8],
+    current_time_nanos: u64,
+) -> Result<Vec<DomainRecord>, CnsError> {
+    let certification = lookup
+        .certification
+        .as_ref()
+        .ok_or_else(|| verification_failed("The lookup is not certified"))?;
+    let certificate: Certificate = serde_cbor::from_slice(&certification.ic_certificate)
+        .map_err(|e| verification_failed(&format!("Malformed certificate: {}", e)))?;
+
+    verify_certificate(&certificate, naming_canister_id, root_key)?;
+    verify_certificate_time(&certificate, current_time_nanos)?;
+
+    let certified_data = match certificate.tree.lookup_path([
+        b"canister".as_slice(),
+        naming_canister_id.as_slice(),
+        b"certified_data".as_slice(),
+    ]) {
+        LookupResult::Found(certified_data) => certified_data,
+        _ => return Err(verification_failed("Missing certified data")),
+    };
+    let state_tree: HashTree = serde_cbor::from_slice(&certification.state_tree)
+        .map_err(|e| verification_failed(&format!("Malformed state tree: {}", e)))?;
+    if state_tree.digest() != certified_data {
+        return Err(verification_failed(
+            "The state tree does not match the certified data",
+        ));
+    }
+
+    let domain = canonical_name(domain);
+    let record_type = record_type.to_ascii_uppercase();
+    match record_type == ANY_RECORD_TYPE {
+        true => verify_all_rrsets(&state_tree, &domain, &lookup.answers)?,
+        false => {
+            let name = verify_cname_chain(&state_tree, &domain, &record_type, lookup)?;
+            verify_rrset(&state_tree, &name, &record_type, &lookup.answers)?
+        }
+    }
+
+    Ok(lookup.answers.clone())
+}
+
+/// Verifies the CNAME records that the lookup followed from the domain, which are part of the additionals, and
+/// returns the name at the end of the chain that holds the answers.
+///
+/// Every CNAME record must be the certified CNAME RRset of a name without records of the looked up type, and the
+/// chain must start at the domain and continue at the target of the previous CNAME record. Without answers, the
+/// name at the end of the chain must not be an alias either, unless the lookup stops following the chain there.
+fn verify_cname_chain(
+    state_tree: &HashTree,
+    domain: &str,
+    record_type: &str,
+    lookup: &DomainLookup,
+) -> Result<String, CnsError> {
+    let cnames = lookup
+        .additionals
+        .iter()
+        .filter(|record| record.record_type == CNAME_RECORD_TYPE)
+        .collect::<Vec<_>>();
+
+    let mut name = domain.to_string();
+    for cname in &cnames {
+        if record_type == CNAME_RECORD_TYPE || cname.name != name {
+            return Err(verification_failed(
+                "The CNAME records do not form a chain that starts at the domain",
+            ));
+        }
+
+        verify_rrset(state_tree, &name, record_type, &[])?;
+        verify_rrset(
+            state_tree,
+            &name,
+            CNAME_RECORD_TYPE,
+            std::slice::from_ref(*cname),
+        )?;
+        name = cname.data.clone();
+    }
+
+    if lookup.answers.is_empty()
+        && record_type != CNAME_RECORD_TYPE
+        && verify_rrset(state_tree, &name, CNAME_RECORD_TYPE, &[]).is_err()
+        && !is_cname_chain_stop(state_tree, domain, &name, &cnames)?
+    {
+        return Err(verification_failed(
+            "The CNAME chain ends before the records of the lookup",
+        ));
+    }
+
+    Ok(name)
+}
+
+/// Returns true if the lookup stops following the CNAME records at the name, which happens after
+/// [MAX_CNAME_CHAIN_LENGTH] records, at a name that was already visited and at a name of another zone.
+fn is_cname_chain_stop(
+    state_tree: &HashTree,
+    domain: &str,
+    name: &str,
+    cnames: &[&DomainRecord],
+) -> Result<bool, CnsError> {
+    Ok(cnames.len() >= MAX_CNAME_CHAIN_LENGTH
+        || cnames.iter().any(|cname| cname.name == name)
+        || zone_apex(state_tree, domain)? != zone_apex(state_tree, name)?)
+}
+
+/// Returns the apex of the zone of the name, which is the closest name at or above it with a certified SOA record.
+fn zone_apex(state_tree: &HashTree, name: &str) -> Result<Option<String>, CnsError> {
+    let mut name = name;
+    loop {
+        match state_tree.lookup_path([
+            CERTIFIED_RECORDS_LABEL,
+            name.as_bytes(),
+            SOA_RECORD_TYPE.as_bytes(),
+        ]) {
+            LookupResult::Found(_) => return Ok(Some(name.to_string())),
+            LookupResult::Absent => (),
+            _ => {
+                return Err(verification_failed(
+                    "The state tree does not contain the zone of the domain",
+                ))
+            }
+        }
+
+        match name
+            .split_once('.')
+            .filter(|(_, parent)| !parent.is_empty())
+        {
+            Some((_, parent)) => name = parent,
+            None => return Ok(None),
+        }
+    }
+}
+
+/// Verifies that the answers are the certified RRset of the domain and record type.
+fn verify_rrset(
+    state_tree: &HashTree,
+    domain: &str,
+    record_type: &str,
+    answers: &[DomainRecord],
+) -> Result<(), CnsError> {
+    if answers
+        .iter()
+        .any(|record| record.name != domain || record.record_type != record_type)
+    {
+        return Err(verification_failed(
+            "The answers do not match the domain and record type of the lookup",
+        ));
+    }
+
+    let (certified_name, answers) = certified_answers(state_tree, domain, answers)?;
+    match state_tree.lookup_path([
+        CERTIFIED_RECORDS_LABEL,
+        certified_name.as_bytes(),
+        record_type.as_bytes(),
+    ]) {
+        LookupResult::Found(hash) if hash == rrset_hash(&answers) && !answers.is_empty() => Ok(()),
+        LookupResult::Absent if answers.is_empty() => Ok(()),
+        _ => Err(verification_failed(
+            "The answers do not match the certified records",
+        )),
+    }
+}
+
+/// Verifies that the answers are all the certified RRsets of the domain, used by lookups of the "ANY" type.
+fn verify_all_rrsets(
+    state_tree: &HashTree,
+    domain: &str,
+    answers: &[DomainRecord],
+) -> Result<(), CnsError> {
+    if answers.iter().any(|record| record.name != domain) {
+        return Err(verification_failed(
+            "The answers do not match the domain of the lookup",
+        ));
+    }
+
+    let (certified_name, answers) = certified_answers(state_tree, domain, answers)?;
+    let mut rrsets = BTreeMap::<&[u8], Vec<DomainRecord>>::new();
+    for record in &answers {
+        rrsets
+            .entry(record.record_type.as_bytes())
+            .or_default()
+            .push(record.clone());
+    }
+
+    let certified_rrsets =
+        match state_tree.lookup_subtree(&[CERTIFIED_RECORDS_LABEL, certified_name.as_bytes()]) {
+            SubtreeLookupResult::Found(subtree) => {
+                let mut certified_rrsets = BTreeMap::new();
+                collect_leaves(subtree.as_ref(), &mut certified_rrsets)?;
+
+                certified_rrsets
+            }
+            SubtreeLookupResult::Absent => BTreeMap::new(),
+            SubtreeLookupResult::Unknown => {
+                return Err(verification_failed(
+                    "The state tree does not contain the domain",
+                ))
+            }
+        };
+
+    let is_certified = certified_rrsets.len() == rrsets.len()
+        && rrsets.iter().all(|(record_type, records)| {
+            certified_rrsets
+                .get(*record_type)
+                .is_some_and(|hash| hash.as_slice() == rrset_hash(records))
+        });
+
+    match is_certified {
+        true => Ok(()),
+        false => Err(verification_failed(
+            "The answers do not match the certified records",
+        )),
+    }
+}
+
+/// Returns the name that the answers of the domain are certified at together with the answers as they are
+/// certified, which are renamed after the wildcard name that they were synthesized from when the domain has no
+/// certified records of its own.
+///
+/// The wildcard name is the closest one to the domain with certified records that is not above an ancestor with
+/// certified records, which always stops at the apex of the zone since it has an SOA record. Lookups without
+/// answers are verified against the same name, hence removing the answers synthesized from a wildcard name is
+/// detected too.
+///
+/// Names that only exist because of the names below them have no certified records, hence lookups without answers
+/// fail the verification when a wildcard name above such a name has records of the looked up type.
+fn certified_answers(
+    state_tree: &HashTree,
+    domain: &str,
+    answers: &[DomainRecord],
+) -> Result<(String, Vec<DomainRecord>), CnsError> {
+    if !is_absent(state_tree, domain)? {
+        return Ok((domain.to_string(), answers.to_vec()));
+    }
+
+    let mut suffix = domain;
+    while let Some((_, parent)) = suffix
+        .split_once('.')
+        .filter(|(_, parent)| !parent.is_empty())
+    {
+        let wildcard_name = format!("{}.{}", WILDCARD_LABEL, parent);
+        if !is_absent(state_tree, &wildcard_name)? {
+            let answers = answers
+                .iter()
+                .map(|record| DomainRecord {
+                    name: wildcard_name.clone(),
+                    ..record.clone()
+                })
+                .collect();
+
+            return Ok((wildcard_name, answers));
+        }
+
+        if !is_absent(state_tree, parent)? {
+            break;
+        }
+
+        suffix = parent;
+    }
+
+    Ok((domain.to_string(), answers.to_vec()))
+}
+
+/// Returns true if the state tree proves that the name has no certified records.
+fn is_absent(state_tree: &HashTree, name: &str) -> Result<bool, CnsError> {
+    match state_tree.lookup_subtree(&[CERTIFIED_RECORDS_LABEL, name.as_bytes()]) {
+        SubtreeLookupResult::Found(_) => Ok(false),
+        SubtreeLookupResult::Absent => Ok(true),
+        SubtreeLookupResult::Unknown => Err(verification_failed(
+            "The state tree does not contain the domain",
+        )),
+    }
+}
+
+/// Collects the labeled leaves of a subtree, pruned branches are rejected since the subtree could not be proven
+/// to be complete.
+fn collect_leaves(
+    node: &HashTreeNode,
+    leaves: &mut BTreeMap<Vec<u8>, Vec<u8>>,
+) -> Result<(), CnsError> {
+    match node {
+        HashTreeNode::Empty() => Ok(()),
+        HashTreeNode::Fork(nodes) => {
+            collect_leaves(&nodes.0, leaves)?;
+            collect_leaves(&nodes.1, leaves)
+        }
+        HashTreeNode::Labeled(label, node) => match node.as_ref() {
+            HashTreeNode::Leaf(value) => {
+                leaves.insert(label.as_bytes().to_vec(), value.clone());
+
+                Ok(())
+            }
+            _ => Err(verification_failed("Unexpected node in the state tree")),
+        },
+        HashTreeNode::Leaf(_) | HashTreeNode::Pruned(_) => Err(verification_failed(
+            "The state tree does not contain all the records of the domain",
+        )),
+    }
+}
+
+/// Verifies the signature of the certificate, following its delegation if any.
+fn verify_certificate(
+    certificate: &Certificate,
+    canister_id: CanisterId,
+    root_key: &[u8],
+) -> Result<(), CnsError> {
+    let public_key = match &certificate.delegation {
+        Some(delegation) => {
+            let delegation_certificate: Certificate =
+                serde_cbor::from_slice(&delegation.certificate).map_err(|e| {
+                    verification_failed(&format!("Malformed delegation certificate: {}", e))
+                })?;
+            if delegation_certificate.delegation.is_some() {
+                return Err(verification_failed("Nested delegations are not allowed"));
+            }
+
+            verify_certificate(&delegation_certificate, canister_id, root_key)?;
+            verify_delegation_canister_ranges(
+                &delegation_certificate,
+                &delegation.subnet_id,
+                canister_id,
+            )?;
+
+            match delegation_certificate.tree.lookup_path([
+                b"subnet".as_slice(),
+                &delegation.subnet_id,
+                b"public_key".as_slice(),
+            ]) {
+                LookupResult::Found(public_key) => public_key.to_vec(),
+                _ => return Err(verification_failed("Missing subnet public key")),
+            }
+        }
+        None => root_key.to_vec(),
+    };
+
+    if public_key.len() != DER_PREFIX.len() + BLS_KEY_LENGTH || !public_key.starts_with(&DER_PREFIX)
+    {
+        return Err(verification_failed("Malformed public key"));
+    }
+
+    let mut message = IC_STATE_ROOT_DOMAIN_SEPARATOR.to_vec();
+    message.extend_from_slice(&certificate.tree.digest());
+
+    ic_verify_bls_signature::verify_bls_signature(
+        &certificate.signature,
+        &message,
+        &public_key[DER_PREFIX.len()..],
+    )
+    .map_err(|_| verification_failed("Invalid certificate signature"))
+}
+
+/// Verifies that the subnet of the delegation is allowed to certify the data of the canister.
+fn verify_delegation_canister_ranges(
+    delegation_certificate: &Certificate,
+    subnet_id: &[u8],
+    canister_id: CanisterId,
+) -> Result<(), CnsError> {
+    let canister_ranges = match delegation_certificate.tree.lookup_path([
+        b"subnet".as_slice(),
+        subnet_id,
+        b"canister_ranges".as_slice(),
+    ]) {
+        LookupResult::Found(canister_ranges) => canister_ranges,
+        _ => return Err(verification_failed("Missing subnet canister ranges")),
+    };
+    let canister_ranges: Vec<(ByteBuf, ByteBuf)> = serde_cbor::from_slice(canister_ranges)
+        .map_err(|e| verification_failed(&format!("Malformed canister ranges: {}", e)))?;
+
+    let canister_id = canister_id.as_slice();
+    match canister_ranges
+        .iter()
+        .any(|(low, high)| low.as_slice() <= canister_id && canister_id <= high.as_slice())
+    {
+        true => Ok(()),
+        false => Err(verification_failed(
+            "The subnet is not authorized to certify the naming canister",
+        )),
+    }
+}
+
+/// Verifies that the certificate is recent enough.
+fn verify_certificate_time(
+    certificate: &Certificate,
+    current_time_nanos: u64,
+) -> Result<(), CnsError> {
+    let certificate_time = match certificate.tree.lookup_path([b"time"]) {
+        LookupResult::Found(time) => decode_leb128(time)?,
+        _ => return Err(verification_failed("Missing certificate time")),
+    };
+
+    match current_time_nanos.saturating_sub(certificate_time) > MAX_CERTIFICATE_AGE_NANOS {
+        true => Err(verification_failed("The certificate is too old")),
+        false => Ok(()),
+    }
+}
+
+/// Decodes an unsigned LEB128 number, which is the encoding of the time in the state tree.
+fn decode_leb128(bytes: &[u8]) -> Result<u64, CnsError> {
+    let mut value = 0u64;
+    for (index, byte) in bytes.iter().enumerate() {
+        let shift = 7 * index as u32;
+        let bits = u64::from(byte & 0x7f);
+        if shift >= u64::BITS || (bits << shift) >> shift != bits {
+            break;
+        }
+
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(verification_failed("Malformed certificate time"))
+}
+
+fn verification_failed(message: &str) -> CnsError {
+    CnsError::VerificationFailed(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Certification;
+    use candid::{Nat, Principal};
+    use cns_domain_registry::{
+        api::{DomainRecordInput, ManageRecordsInput, ManageRecordsOperation},
+        services::{CertificationService, LookupService, RecordsService},
+        types::CallContext,
+    };
+    use ic_certification::{fork, label, leaf, Delegation};
+    use ic_verify_bls_signature::PrivateKey;
+
+    const NOW: u64 = 1_700_000_000_000_000_000;
+
+    fn naming_canister_id() -> Principal {
+        Principal::from_slice(&[0, 0, 0, 0, 0, 0, 0, 1, 1, 1])
+    }
+
+    fn private_key(seed: u8) -> PrivateKey {
+        PrivateKey::deserialize(&[seed; 32]).unwrap()
+    }
+
+    fn der_public_key(private_key: &PrivateKey) -> Vec<u8> {
+        [DER_PREFIX.as_slice(), &private_key.public_key().serialize()].concat()
+    }
+
+    fn encode_leb128(mut value: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn sign_certificate(
+        tree: HashTree,
+        private_key: &PrivateKey,
+        delegation: Option<Delegation>,
+    ) -> Vec<u8> {
+        let mut message = IC_STATE_ROOT_DOMAIN_SEPARATOR.to_vec();
+        message.extend_from_slice(&tree.digest());
+
+        serde_cbor::to_vec(&Certificate {
+            tree,
+            signature: private_key.sign(&message).serialize().to_vec(),
+            delegation,
+        })
+        .unwrap()
+    }
+
+    fn canister_tree(certified_data: &[u8], time: u64) -> HashTree {
+        fork(
+            label(
+                "canister",
+                label(
+                    naming_canister_id().as_slice(),
+                    label("certified_data", leaf(certified_data)),
+                ),
+            ),
+            label("time", leaf(encode_leb128(time))),
+        )
+    }
+
+    fn append_records(domain: &str, records: &[(&str, &str, &str)]) {
+        RecordsService::default()
+            .manage_records(
+                &CallContext::new(Principal::anonymous(), true, 0),
+                &ManageRecordsInput {
+                    domain: String::from(domain),
+                    operations: vec![ManageRecordsOperation::Append(
+                        records
+                            .iter()
+                            .map(|(name, record_type, data)| DomainRecordInput {
+                                name: String::from(*name),
+                                record_type: String::from(*record_type),
+                                ttl: Nat::from(60u32),
+                                data: String::from(*data),
+                            })
+                            .collect(),
+                    )],
+                    sign_with_tecdsa: None,
+                },
+            )
+            .unwrap();
+    }
+
+    /// Answers the lookup like the naming canister would in a non-replicated query signed by the root key.
+    fn certified_lookup(domain: &str, record_type: &str, time: u64) -> DomainLookup {
+        certify(
+            domain,
+            record_type,
+            LookupService::default().lookup(domain, record_type),
+            time,
+        )
+    }
+
+    /// Certifies the answers of the lookup with the witness that the naming canister would return for them, which
+    /// is what a single replica could respond with after tampering with the answers.
+    fn certify(
+        domain: &str,
+        record_type: &str,
+        mut lookup: DomainLookup,
+        time: u64,
+    ) -> DomainLookup {
+        let certification_service = CertificationService::default();
+        lookup.certification = Some(Certification {
+            ic_certificate: sign_certificate(
+                canister_tree(&certification_service.certified_data(), time),
+                &private_key(1),
+                None,
+            ),
+            state_tree: certification_service.witness(domain, record_type, &lookup),
+        });
+
+        lookup
+    }
+
+    fn verify(domain: &str, record_type: &str, lookup: &DomainLookup) -> Result<(), CnsError> {
+        verify_domain_lookup(
+            naming_canister_id(),
+            domain,
+            record_type,
+            lookup,
+            &der_public_key(&private_key(1)),
+            NOW,
+        )
+        .map(|_| ())
+    }
+
+    fn setup_records() {
+        append_records(
+            "verified.tld.",
+            &[
+                ("verified.tld.", "TXT", "first"),
+                ("verified.tld.", "TXT", "second"),
+                ("verified.tld.", "CID", "qoctq-giaaa-aaaaa-aaaea-cai"),
+                ("wiki.verified.tld.", "TXT", "wiki"),
+            ],
+        );
+    }
+
+    #[test]
+    fn verifies_certified_answers() {
+        setup_records();
+
+        for (domain, record_type, answers) in [
+            ("verified.tld.", "TXT", 2),
+            ("Verified.tld.", "cid", 1),
+            // the SOA record of the zone is also part of the apex records
+            ("verified.tld.", "ANY", 4),
+            ("wiki.verified.tld.", "TXT", 1),
+            ("verified.tld.", "NC", 0),
+            ("unknown.verified.tld.", "TXT", 0),
+            ("unknown.tld.", "ANY", 0),
+        ] {
+            let lookup = certified_lookup(domain, record_type, NOW);
+            let result = verify_domain_lookup(
+                naming_canister_id(),
+                domain,
+                record_type,
+                &lookup,
+                &der_public_key(&private_key(1)),
+                NOW,
+            );
+
+            assert_eq!(result.map(|records| records.len()), Ok(answers));
+        }
+    }
+
+    #[test]
+    fn verifies_followed_cname_chains() {
+        setup_records();
+        append_records(
+            "verified.tld.",
+            &[
+                ("alias.verified.tld.", "CNAME", "wiki.verified.tld."),
+                ("dangling.verified.tld.", "CNAME", "unknown.verified.tld."),
+            ],
+        );
+
+        let lookup = certified_lookup("alias.verified.tld.", "TXT", NOW);
+        assert_eq!(lookup.additionals.len(), 1);
+        assert_eq!(
+            verify_domain_lookup(
+                naming_canister_id(),
+                "alias.verified.tld.",
+                "TXT",
+                &lookup,
+                &der_public_key(&private_key(1)),
+                NOW,
+            )
+            .map(|records| records.len()),
+            Ok(1)
+        );
+        assert_eq!(
+            verify(
+                "dangling.verified.tld.",
+                "TXT",
+                &certified_lookup("dangling.verified.tld.", "TXT", NOW)
+            ),
+            Ok(())
+        );
+
+        let mut without_chain = lookup.clone();
+        without_chain.additionals.clear();
+        assert_eq!(
+            verify("alias.verified.tld.", "TXT", &without_chain),
+            Err(verification_failed(
+                "The answers do not match the domain and record type of the lookup"
+            ))
+        );
+
+        let mut redirected_chain = lookup.clone();
+        redirected_chain.additionals[0].data = String::from("other.verified.tld.");
+        assert_eq!(
+            verify("alias.verified.tld.", "TXT", &redirected_chain),
+            Err(verification_failed(
+                "The answers do not match the certified records"
+            ))
+        );
+
+        let mut unrelated_chain = lookup.clone();
+        unrelated_chain.additionals[0].name = String::from("other.verified.tld.");
+        assert_eq!(
+            verify("alias.verified.tld.", "TXT", &unrelated_chain),
+            Err(verification_failed(
+                "The CNAME records do not form a chain that starts at the domain"
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_cut_cname_chains() {
+        setup_records();
+        append_records(
+            "verified.tld.",
+            &[
+                ("alias.verified.tld.", "CNAME", "wiki.verified.tld."),
+                ("second.verified.tld.", "CNAME", "alias.verified.tld."),
+                ("loop.verified.tld.", "CNAME", "second-loop.verified.tld."),
+                ("second-loop.verified.tld.", "CNAME", "loop.verified.tld."),
+                ("external.verified.tld.", "CNAME", "www.other.tld."),
+            ],
+        );
+        append_records(
+            "other.tld.",
+            &[("www.other.tld.", "CNAME", "wiki.verified.tld.")],
+        );
+
+        for (domain, additionals) in [
+            ("alias.verified.tld.", 0),
+            ("second.verified.tld.", 0),
+            ("second.verified.tld.", 1),
+        ] {
+            let mut cut_chain = LookupService::default().lookup(domain, "TXT");
+            cut_chain.answers.clear();
+            cut_chain.additionals.truncate(additionals);
+            let cut_chain = certify(domain, "TXT", cut_chain, NOW);
+
+            assert_eq!(
+                verify(domain, "TXT", &cut_chain),
+                Err(verification_failed(
+                    "The CNAME chain ends before the records of the lookup"
+                ))
+            );
+        }
+
+        // the lookup stops at names that were already visited and at names of other zones
+        for (domain, additionals) in [("loop.verified.tld.", 2), ("external.verified.tld.", 1)] {
+            let lookup = certified_lookup(domain, "TXT", NOW);
+
+            assert!(lookup.answers.is_empty());
+            assert_eq!(lookup.additionals.len(), additionals);
+            assert_eq!(verify(domain, "TXT", &lookup), Ok(()));
+        }
+    }
+
+    #[test]
+    fn verifies_wildcard_answers() {
+        setup_records();
+        append_records(
+            "verified.tld.",
+            &[
+                ("*.verified.tld.", "TXT", "wildcard"),
+                ("*.wiki.verified.tld.", "CID", "qoctq-giaaa-aaaaa-aaaea-cai"),
+            ],
+        );
+
+        for (domain, record_type, answers) in [
+            ("www.verified.tld.", "TXT", 1),
+            ("www.verified.tld.", "ANY", 1),
+            ("a.b.verified.tld.", "TXT", 1),
+            ("www.wiki.verified.tld.", "CID", 1),
+            ("www.wiki.verified.tld.", "TXT", 0),
+            ("wiki.verified.tld.", "TXT", 1),
+        ] {
+            let lookup = certified_lookup(domain, record_type, NOW);
+            assert_eq!(lookup.answers.len(), answers);
+            assert!(lookup.answers.iter().all(|record| record.name == domain));
+            assert_eq!(verify(domain, record_type, &lookup), Ok(()));
+        }
+
+        let mut tampered = certified_lookup("www.verified.tld.", "TXT", NOW);
+        tampered.answers[0].data = String::from("tampered");
+        assert_eq!(
+            verify("www.verified.tld.", "TXT", &tampered),
+            Err(verification_failed(
+                "The answers do not match the certified records"
+            ))
+        );
+
+        for (domain, record_type) in [
+            ("www.verified.tld.", "TXT"),
+            ("a.b.verified.tld.", "TXT"),
+            ("www.wiki.verified.tld.", "CID"),
+            ("www.verified.tld.", "ANY"),
+        ] {
+            let mut stripped = LookupService::default().lookup(domain, record_type);
+            stripped.answers.clear();
+            let stripped = certify(domain, record_type, stripped, NOW);
+
+            assert_eq!(
+                verify(domain, record_type, &stripped),
+                Err(verification_failed(
+                    "The answers do not match the certified records"
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_answers_that_do_not_match_the_witness() {
+        setup_records();
+        let lookup = certified_lookup("verified.tld.", "TXT", NOW);
+
+        let mut tampered = lookup.clone();
+        tampered.answers[0].data = String::from("tampered");
+        assert!(verify("verified.tld.", "TXT", &tampered).is_err());
+
+        let mut incomplete = lookup.clone();
+        incomplete.answers.pop();
+        assert!(verify("verified.tld.", "TXT", &incomplete).is_err());
+
+        let mut empty = lookup.clone();
+        empty.answers.clear();
+        assert!(verify("verified.tld.", "TXT", &empty).is_err());
+
+        let mut incomplete_any = certified_lookup("verified.tld.", "ANY", NOW);
+        incomplete_any
+            .answers
+            .retain(|record| record.record_type == "TXT");
+        assert!(verify("verified.tld.", "ANY", &incomplete_any).is_err());
+
+        // the witness of another lookup does not prove the answers
+        assert!(verify("wiki.verified.tld.", "TXT", &lookup).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_certificates() {
+        setup_records();
+        let lookup = certified_lookup("verified.tld.", "TXT", NOW);
+
+        let mut uncertified = lookup.clone();
+        uncertified.certification = None;
+        assert_eq!(
+            verify("verified.tld.", "TXT", &uncertified),
+            Err(verification_failed("The lookup is not certified"))
+        );
+
+        assert_eq!(
+            verify_domain_lookup(
+                naming_canister_id(),
+                "verified.tld.",
+                "TXT",
+                &lookup,
+                &der_public_key(&private_key(2)),
+                NOW,
+            ),
+            Err(verification_failed("Invalid certificate signature"))
+        );
+
+        assert_eq!(
+            verify_domain_lookup(
+                Principal::anonymous(),
+                "verified.tld.",
+                "TXT",
+                &lookup,
+                &der_public_key(&private_key(1)),
+                NOW,
+            ),
+            Err(verification_failed("Missing certified data"))
+        );
+
+        let outdated =
+            certified_lookup("verified.tld.", "TXT", NOW - MAX_CERTIFICATE_AGE_NANOS - 1);
+        assert_eq!(
+            verify("verified.tld.", "TXT", &outdated),
+            Err(verification_failed("The certificate is too old"))
+        );
+
+        // the certificate was issued for the records before the change
+        append_records("verified.tld.", &[("verified.tld.", "TXT", "third")]);
+        let mut outdated_state_tree = lookup.clone();
+        outdated_state_tree.certification = Some(Certification {
+            state_tree: CertificationService::default().witness("verified.tld.", "TXT", &lookup),
+            ..lookup.certification.clone().unwrap()
+        });
+        assert_eq!(
+            verify("verified.tld.", "TXT", &outdated_state_tree),
+            Err(verification_failed(
+                "The state tree does not match the certified data"
+            ))
+        );
+    }
+
+    #[test]
+    fn verifies_delegated_certificates() {
+        setup_records();
+        let subnet_id = Principal::from_slice(&[7; 29]);
+        let delegated_lookup = |canister_ranges: Vec<(Principal, Principal)>| {
+            let subnet_key = private_key(3);
+            let canister_ranges = serde_cbor::to_vec(
+                &canister_ranges
+                    .iter()
+                    .map(|(low, high)| {
+                        (
+                            ByteBuf::from(low.as_slice()),
+                            ByteBuf::from(high.as_slice()),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap();
+            let delegation_tree = label(
+                "subnet",
+                label(
+                    subnet_id.as_slice(),
+                    fork(
+                        label("canister_ranges", leaf(canister_ranges)),
+                        label("public_key", leaf(der_public_key(&subnet_key))),
+                    ),
+                ),
+            );
+            let certification_service = CertificationService::default();
+            let mut lookup = LookupService::default().lookup("verified.tld.", "TXT");
+            lookup.certification = Some(Certification {
+                ic_certificate: sign_certificate(
+                    canister_tree(&certification_service.certified_data(), NOW),
+                    &subnet_key,
+                    Some(Delegation {
+                        subnet_id: subnet_id.as_slice().to_vec(),
+                        certificate: sign_certificate(delegation_tree, &private_key(1), None),
+                    }),
+                ),
+                state_tree: certification_service.witness("verified.tld.", "TXT", &lookup),
+            });
+
+            lookup
+        };
+
+        assert_eq!(
+            verify(
+                "verified.tld.",
+                "TXT",
+                &delegated_lookup(vec![(naming_canister_id(), naming_canister_id())])
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify(
+                "verified.tld.",
+                "TXT",
+                &delegated_lookup(vec![(Principal::anonymous(), Principal::anonymous())])
+            ),
+            Err(verification_failed(
+                "The subnet is not authorized to certify the naming canister"
+            ))
+        );
+    }
+
+    #[test]
+    fn decodes_leb128() {
+        for value in [0, 1, 127, 128, 300, NOW, u64::MAX] {
+            assert_eq!(decode_leb128(&encode_leb128(value)), Ok(value));
+        }
+
+        assert!(decode_leb128(&[0x80]).is_err());
+        assert!(decode_leb128(&[0xff; 11]).is_err());
+    }
+}
@@ -1,9 +1,15 @@
-use candid::{CandidType, Deserialize, Nat, Principal};
-pub use cns_domain_registry::types::RegisterResult;
+use candid::{CandidType, Deserialize, Principal};
+pub use cns_domain_registry::{
+    api::{Certification, DomainLookup, DomainRecord, RegistrationRecords},
+    types::{RegisterResult, RegistrationController, RegistrationControllerRole},
+};
 use ic_cdk::api::call::{call, RejectionCode};
 use lazy_static::lazy_static;
 use std::sync::{Arc, Mutex};
 
+mod certification;
+pub use certification::{verify_domain_lookup, MAX_CERTIFICATE_AGE_NANOS};
+
 type CanisterId = Principal;
 type SubnetId = Principal;
 
@@ -24,6 +30,7 @@ pub enum CnsError {
     CallFailed((RejectionCode, String)),
     MalformedData(String),
     Internal(String),
+    VerificationFailed(String),
 }
 
 impl std::fmt::Display for CnsError {
@@ -38,42 +45,6 @@ impl From<(RejectionCode, String)> for CnsError {
     }
 }
 
-// TODO: use DomainRecord and DomainLookup defined in cns_domain_registry.
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct DomainRecord {
-    pub name: String,
-    pub record_type: String,
-    pub ttl: Nat,
-    pub data: String,
-}
-
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct DomainLookup {
-    pub answers: Vec<DomainRecord>,
-    pub additionals: Vec<DomainRecord>,
-    pub authorities: Vec<DomainRecord>,
-}
-
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub enum RegistrationControllerRole {
-    Registrar,
-    Registrant,
-    Technical,
-    Administrative,
-}
-
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct RegistrationController {
-    pub controller_id: Principal,
-    pub roles: Vec<RegistrationControllerRole>,
-}
-
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct RegistrationRecords {
-    pub controllers: Vec<RegistrationController>,
-    pub records: Option<Vec<DomainRecord>>,
-}
-
 fn get_principal_id_from_records(
     records: &[DomainRecord],
     context: &str,
@@ -151,17 +122,11 @@ pub async fn lookup_subnet(subnet_name: &str) -> Result<SubnetId, CnsError> {
     get_principal_id_from_records(&lookup.answers, &format!("SID lookup for {}", subnet_name))
 }
 
-pub async fn register_domain(domain: &str, cid: CanisterId) -> Result<(), CnsError> {
+pub async fn register_domain(domain: &str, records: Vec<DomainRecord>) -> Result<(), CnsError> {
     let nc_cid = lookup_nc(domain).await?;
-    let record = DomainRecord {
-        name: domain.to_string(),
-        record_type: "CID".to_string(),
-        ttl: Nat::from(3600u32),
-        data: cid.to_string(),
-    };
     let registration_records = RegistrationRecords {
         controllers: vec![],
-        records: Some(vec![record]),
+        records: Some(records),
     };
     let (register,): (RegisterResult,) = call(
         nc_cid,
@@ -97,12 +97,18 @@ impl CnsFixture {
             .expect("Failed registering NC for icp");
     }
 
-    fn register_domain(&self, domain: &str, cid_text: &str) -> Result<(), CnsError> {
+    fn register_domain(&self, domain: &str, record_type: &str, data: &str) -> Result<(), CnsError> {
+        let records = vec![DomainRecord {
+            name: domain.to_string(),
+            record_type: record_type.to_string(),
+            ttl: Nat::from(3600u32),
+            data: data.to_string(),
+        }];
         let response = self.pic.update_call(
             self.test_client,
             Principal::anonymous(),
             "register_domain",
-            encode_args((&domain, &cid_text)).expect("failed encoding args"),
+            encode_args((&domain, &records)).expect("failed encoding args"),
         );
         let Ok(WasmResult::Reply(reply)) = response else {
             panic!("call failed: {:?}", response);
@@ -172,7 +178,7 @@ fn should_register_and_lookup_domains() {
         ("nns_governance.icp.", "rrkah-fqaaa-aaaaa-aaaaq-cai"),
         ("nns_registry.icp.", "rwlgt-iiaaa-aaaaa-aaaaa-cai"),
     ] {
-        let result = env.register_domain(domain, cid_text);
+        let result = env.register_domain(domain, "CID", cid_text);
         assert!(result.is_ok(), "Domain registration failed: {:?}", result);
         let result = env.lookup_domain(domain);
         assert_matches!(result, Ok(cid) if (cid.to_string() == cid_text));
@@ -181,19 +187,21 @@ fn should_register_and_lookup_domains() {
     }
 }
 
+#[test]
 fn should_register_and_lookup_subnets() {
     let env = CnsFixture::init();
     env.register_icp_nc();
-    for (subnet, sid_text) in [
-        ("example.subnet.icp.", "r7inp-6aaaa-aaaaa-aaabq-cai"),
-        ("nns_governance.subnet.icp.", "rrkah-fqaaa-aaaaa-aaaaq-cai"),
-        ("nns_registry.subnet.icp.", "rwlgt-iiaaa-aaaaa-aaaaa-cai"),
+    for (subnet, public_key) in [
+        ("app-1.subnet.icp.", [1; 32]),
+        ("sys-2.subnet.icp.", [2; 32]),
+        ("app-fiduciary-3.subnet.icp.", [3; 32]),
     ] {
-        let result = env.register_domain(subnet, sid_text);
+        let sid_text = Principal::self_authenticating(public_key).to_text();
+        let result = env.register_domain(subnet, "SID", &sid_text);
         assert!(result.is_ok(), "Domain registration failed: {:?}", result);
         let result = env.lookup_subnet(subnet);
         assert_matches!(result, Ok(sid) if (sid.to_string() == sid_text));
-        let result = env.name_for_subnet(sid_text);
+        let result = env.name_for_subnet(&sid_text);
         assert_matches!(result, Ok(sn) if (subnet == sn));
     }
 }
@@ -206,7 +214,7 @@ fn should_not_register_and_lookup_if_missing_nc() {
         ("nns_governance.icp.", "rrkah-fqaaa-aaaaa-aaaaq-cai"),
         ("nns_registry.icp.", "rwlgt-iiaaa-aaaaa-aaaaa-cai"),
     ] {
-        let result = env.register_domain(domain, cid_text);
+        let result = env.register_domain(domain, "CID", cid_text);
         assert_matches!(result, Err(err) if (err.to_string().contains("No record for NC")));
         let result = env.lookup_domain(domain);
         assert_matches!(result, Err(err) if (err.to_string().contains("No record for NC")));
@@ -222,7 +230,7 @@ fn should_not_register_and_lookup_if_not_icp_tld() {
         ("nns_governance.org.", "rrkah-fqaaa-aaaaa-aaaaq-cai"),
         ("nns_registry.edu.", "rwlgt-iiaaa-aaaaa-aaaaa-cai"),
     ] {
-        let result = env.register_domain(domain, cid_text);
+        let result = env.register_domain(domain, "CID", cid_text);
         assert_matches!(result, Err(err) if (err.to_string().contains("No record for NC")));
         let result = env.lookup_domain(domain);
         assert_matches!(result, Err(err) if (err.to_string().contains("No record for NC")));
@@ -241,7 +249,7 @@ fn should_not_register_non_test_domains_if_not_controller() {
         ("nns_governance.icp.", "rrkah-fqaaa-aaaaa-aaaaq-cai"),
         ("nns_registry.icp.", "rwlgt-iiaaa-aaaaa-aaaaa-cai"),
     ] {
-        let result = env.register_domain(domain, cid_text);
+        let result = env.register_domain(domain, "CID", cid_text);
         assert_matches!(result, Err(err) if (err.to_string().contains("Currently only a canister controller can register non-test")));
         let result = env.lookup_domain(domain);
         assert_matches!(result, Err(err) if (err.to_string().contains("No record for CID lookup")));
@@ -260,7 +268,7 @@ fn should_register_test_domains_if_not_controller() {
         ("nns_governance.test.icp.", "rrkah-fqaaa-aaaaa-aaaaq-cai"),
         ("nns_registry.test.icp.", "rwlgt-iiaaa-aaaaa-aaaaa-cai"),
     ] {
-        let result = env.register_domain(domain, cid_text);
+        let result = env.register_domain(domain, "CID", cid_text);
         assert!(result.is_ok(), "Domain registration failed: {:?}", result);
         let result = env.lookup_domain(domain);
         assert_matches!(result, Ok(cid) if (cid.to_string() == cid_text));